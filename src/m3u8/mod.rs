@@ -2,4 +2,5 @@ pub mod parser;
 pub mod playlist;
 pub mod tags;
 mod tests;
+pub mod types;
 pub mod validation;
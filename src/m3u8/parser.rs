@@ -26,11 +26,121 @@
 ///
 pub fn parse_attributes(input: &str) -> Result<std::collections::HashMap<String, String>, String> {
     let mut attributes = std::collections::HashMap::new();
-    for part in input.split(',') {
+    let mut in_quotes = false;
+    let mut start = 0;
+    let mut parts = Vec::new();
+
+    // Split on commas that are not inside a quoted-string, so that values such as
+    // `CODECS="avc1.64001f,mp4a.40.2"` are kept intact.
+    for (i, c) in input.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => {
+                parts.push(&input[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if in_quotes {
+        return Err(format!(
+            "Unterminated quoted-string in attributes: {}",
+            input
+        ));
+    }
+    parts.push(&input[start..]);
+
+    for part in parts {
         let parts: Vec<&str> = part.splitn(2, '=').collect();
         if parts.len() == 2 {
-            attributes.insert(parts[0].to_string(), parts[1].trim_matches('"').to_string());
+            attributes.insert(
+                parts[0].trim().to_string(),
+                parts[1].trim().trim_matches('"').to_string(),
+            );
         }
     }
     Ok(attributes)
 }
+
+/// Parses an optional attribute value into `T`.
+///
+/// Returns `Ok(None)` if the attribute is absent and an error message if it is
+/// present but cannot be parsed.
+pub(crate) fn parse_attribute<T>(
+    attributes: &std::collections::HashMap<String, String>,
+    name: &str,
+) -> Result<Option<T>, String>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    attributes
+        .get(name)
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|e| format!("Invalid {} attribute \"{}\": {}", name, value, e))
+        })
+        .transpose()
+}
+
+/// Parses a required attribute value into `T`, failing if the attribute is absent.
+pub(crate) fn parse_required_attribute<T>(
+    attributes: &std::collections::HashMap<String, String>,
+    tag: &str,
+    name: &str,
+) -> Result<T, String>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    parse_attribute(attributes, name)?.ok_or_else(|| format!("{} is missing {}", tag, name))
+}
+
+/// Parses an optional enumerated-string attribute whose value is `YES` or `NO`.
+pub(crate) fn parse_yes_no(
+    attributes: &std::collections::HashMap<String, String>,
+    name: &str,
+) -> Result<Option<bool>, String> {
+    match attributes.get(name).map(String::as_str) {
+        None => Ok(None),
+        Some("YES") => Ok(Some(true)),
+        Some("NO") => Ok(Some(false)),
+        Some(value) => Err(format!(
+            "Invalid {} attribute \"{}\": expected YES or NO",
+            name, value
+        )),
+    }
+}
+
+/// Returns `true` if `text` begins with one or more whitespace-separated
+/// `key="value"` pairs followed by a comma.
+///
+/// This is the shape of the attributes used by IPTV playlists in the `EXTINF`
+/// title position, e.g. `#EXTINF:-1 tvg-id="news" group-title="News",News`.
+pub(crate) fn has_leading_attributes(text: &str) -> bool {
+    let mut rest = text;
+    loop {
+        let key_end = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+            .unwrap_or(rest.len());
+        if key_end == 0 {
+            return false;
+        }
+        let Some(value) = rest[key_end..].strip_prefix("=\"") else {
+            return false;
+        };
+        let Some(close) = value.find('"') else {
+            return false;
+        };
+        rest = &value[close + 1..];
+        if rest.starts_with(',') {
+            return true;
+        }
+        let trimmed = rest.trim_start();
+        if trimmed.len() == rest.len() {
+            return false;
+        }
+        rest = trimmed;
+    }
+}
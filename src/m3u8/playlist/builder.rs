@@ -1,5 +1,6 @@
 use crate::m3u8::playlist::Playlist;
 use crate::m3u8::tags::Tag;
+use crate::m3u8::types::{ByteRange, Codecs, DecimalDuration, Resolution};
 use crate::m3u8::validation::ValidationError;
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -8,6 +9,7 @@ use std::rc::Rc;
 #[derive(Clone)]
 pub struct PlaylistBuilder {
     tags: Rc<RefCell<Vec<Tag>>>,
+    auto_version: bool,
 }
 
 impl Default for PlaylistBuilder {
@@ -21,6 +23,7 @@ impl PlaylistBuilder {
     pub fn new() -> Self {
         Self {
             tags: Rc::new(RefCell::new(Vec::new())),
+            auto_version: false,
         }
     }
 
@@ -36,11 +39,28 @@ impl PlaylistBuilder {
         self
     }
 
+    /// Makes `build` write the lowest `EXT-X-VERSION` supporting every feature
+    /// of the playlist, replacing any version added with `version`.
+    ///
+    /// The version tag is placed right after `EXTM3U`.
+    pub fn auto_version(mut self) -> Self {
+        self.auto_version = true;
+        self
+    }
+
     /// Adds an `ExtInf` tag.
-    pub fn extinf(self, url: &str, duration: f32, title: Option<String>) -> Self {
+    ///
+    /// The duration accepts anything convertible into a `DecimalDuration`, such as
+    /// `5.005` or a `DecimalDuration` parsed from text.
+    pub fn extinf(
+        self,
+        url: &str,
+        duration: impl Into<DecimalDuration>,
+        title: Option<String>,
+    ) -> Self {
         self.tags
             .borrow_mut()
-            .push(Tag::ExtInf(url.to_string(), duration, title));
+            .push(Tag::ExtInf(url.to_string(), duration.into(), title));
         self
     }
 
@@ -94,10 +114,10 @@ impl PlaylistBuilder {
     }
 
     /// Adds an `ExtXMap` tag.
-    pub fn map(self, uri: &str, byterange: Option<&str>) -> Self {
+    pub fn map(self, uri: &str, byterange: Option<ByteRange>) -> Self {
         self.tags.borrow_mut().push(Tag::ExtXMap {
             uri: uri.to_string(),
-            byterange: byterange.map(|s| s.to_string()),
+            byterange,
         });
         self
     }
@@ -117,10 +137,8 @@ impl PlaylistBuilder {
     }
 
     /// Adds an `ExtXByteRange` tag.
-    pub fn byte_range(self, byterange: &str) -> Self {
-        self.tags
-            .borrow_mut()
-            .push(Tag::ExtXByteRange(byterange.to_string()));
+    pub fn byte_range(self, byterange: ByteRange) -> Self {
+        self.tags.borrow_mut().push(Tag::ExtXByteRange(byterange));
         self
     }
 
@@ -168,9 +186,11 @@ impl PlaylistBuilder {
     #[allow(clippy::too_many_arguments)]
     pub fn stream_inf(
         self,
+        uri: &str,
         bandwidth: u32,
-        codecs: Option<&str>,
-        resolution: Option<&str>,
+        average_bandwidth: Option<u32>,
+        codecs: Option<Codecs>,
+        resolution: Option<Resolution>,
         frame_rate: Option<f32>,
         audio: Option<&str>,
         video: Option<&str>,
@@ -178,9 +198,11 @@ impl PlaylistBuilder {
         closed_captions: Option<&str>,
     ) -> Self {
         self.tags.borrow_mut().push(Tag::ExtXStreamInf {
+            uri: uri.to_string(),
             bandwidth,
-            codecs: codecs.map(|s| s.to_string()),
-            resolution: resolution.map(|s| s.to_string()),
+            average_bandwidth,
+            codecs,
+            resolution,
             frame_rate,
             audio: audio.map(|s| s.to_string()),
             video: video.map(|s| s.to_string()),
@@ -194,15 +216,15 @@ impl PlaylistBuilder {
     pub fn iframe_stream_inf(
         self,
         bandwidth: u32,
-        codecs: Option<&str>,
-        resolution: Option<&str>,
+        codecs: Option<Codecs>,
+        resolution: Option<Resolution>,
         frame_rate: Option<f32>,
         uri: &str,
     ) -> Self {
         self.tags.borrow_mut().push(Tag::ExtXIFrameStreamInf {
             bandwidth,
-            codecs: codecs.map(|s| s.to_string()),
-            resolution: resolution.map(|s| s.to_string()),
+            codecs,
+            resolution,
             frame_rate,
             uri: uri.to_string(),
         });
@@ -234,7 +256,19 @@ impl PlaylistBuilder {
     pub fn session_data(self, id: &str, value: &str, language: Option<&str>) -> Self {
         self.tags.borrow_mut().push(Tag::ExtXSessionData {
             id: id.to_string(),
-            value: value.to_string(),
+            value: Some(value.to_string()),
+            uri: None,
+            language: language.map(|s| s.to_string()),
+        });
+        self
+    }
+
+    /// Adds an `ExtXSessionData` tag whose data is loaded from `uri`.
+    pub fn session_data_uri(self, id: &str, uri: &str, language: Option<&str>) -> Self {
+        self.tags.borrow_mut().push(Tag::ExtXSessionData {
+            id: id.to_string(),
+            value: None,
+            uri: Some(uri.to_string()),
             language: language.map(|s| s.to_string()),
         });
         self
@@ -252,9 +286,15 @@ impl PlaylistBuilder {
 
     /// Constructs the final `Playlist` and validates it.
     pub fn build(self) -> Result<Playlist, Vec<ValidationError>> {
-        let playlist = Playlist {
-            tags: self.tags.borrow().clone(),
-        };
+        let mut playlist = Playlist::new(self.tags.borrow().clone());
+        if self.auto_version {
+            let version = playlist.minimum_version();
+            playlist
+                .tags
+                .retain(|tag| !matches!(tag, Tag::ExtXVersion(_)));
+            let index = usize::from(matches!(playlist.tags.first(), Some(Tag::ExtM3U)));
+            playlist.tags.insert(index, Tag::ExtXVersion(version));
+        }
         match playlist.validate() {
             Ok(_) => Ok(playlist),
             Err(errors) => Err(errors),
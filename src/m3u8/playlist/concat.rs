@@ -0,0 +1,133 @@
+//! Concatenation of media playlists.
+//!
+//! Stitching playlists that were packaged separately, such as a pre-roll, the
+//! main content and a post-roll, requires a discontinuity at every boundary,
+//! since timestamps and encoding parameters may change, and the encryption
+//! and initialization state of each playlist must not leak into the next.
+//!
+//! URIs are copied as written. Use absolute URIs, or playlists that share a
+//! base URI, so that they still resolve in the combined playlist.
+//!
+//! # Example
+//!
+//! ```
+//! use m3u8_parser::m3u8::playlist::Playlist;
+//!
+//! let ad = Playlist::from_reader("#EXTM3U\n#EXT-X-TARGETDURATION:5\n#EXTINF:5,\nad.ts\n".as_bytes())
+//!     .unwrap();
+//! let content = Playlist::from_reader(
+//!     "#EXTM3U\n#EXT-X-TARGETDURATION:10\n#EXTINF:9.5,\nmain.ts\n".as_bytes(),
+//! )
+//! .unwrap();
+//!
+//! let stitched = Playlist::concat(&[ad, content]).unwrap();
+//! assert_eq!(
+//!     stitched.to_string(),
+//!     "#EXTM3U\n#EXT-X-VERSION:3\n#EXT-X-TARGETDURATION:10\n#EXT-X-PLAYLIST-TYPE:VOD\n\
+//!      #EXTINF:5,\nad.ts\n#EXT-X-DISCONTINUITY\n#EXTINF:9.5,\nmain.ts\n#EXT-X-ENDLIST\n"
+//! );
+//! ```
+
+use crate::m3u8::playlist::segment::key_format;
+use crate::m3u8::playlist::Playlist;
+use crate::m3u8::tags::Tag;
+
+impl Playlist {
+    /// Concatenates media playlists into a single VOD playlist.
+    ///
+    /// An `EXT-X-DISCONTINUITY` is inserted before the first segment of every
+    /// playlist but the first. Keys in effect at the end of a playlist are
+    /// cleared with `EXT-X-KEY:METHOD=NONE` unless the next playlist replaces
+    /// them. `EXT-X-TARGETDURATION` is set to the longest rounded segment
+    /// duration and `EXT-X-VERSION` to the minimum version the result
+    /// requires. `EXT-X-INDEPENDENT-SEGMENTS` is kept if every playlist has it.
+    ///
+    /// Playlists without segments are skipped. Returns an error for master
+    /// playlists, and for a playlist without `EXT-X-MAP` following one with
+    /// it, as an initialization section cannot be unset.
+    pub fn concat(playlists: &[Playlist]) -> Result<Playlist, String> {
+        let mut body: Vec<Tag> = Vec::new();
+        // The keys and initialization section in effect at the end of `body`.
+        let mut keys: Vec<Tag> = Vec::new();
+        let mut map: Option<Tag> = None;
+
+        for (index, playlist) in playlists.iter().enumerate() {
+            if playlist.tags.iter().any(Tag::is_master_tag) {
+                return Err(format!("Playlist {} is a master playlist", index));
+            }
+            let segments = playlist.segments();
+            let (Some(first), Some(last)) = (segments.first(), segments.last()) else {
+                continue;
+            };
+            let start = playlist.segments_start();
+            let group = &playlist.tags[start..=first.tag_index];
+            let tags = &playlist.tags[start..=last.tag_index];
+
+            if !body.is_empty() {
+                if !group
+                    .iter()
+                    .any(|tag| matches!(tag, Tag::ExtXDiscontinuity))
+                {
+                    body.push(Tag::ExtXDiscontinuity);
+                }
+                let replaced = keys
+                    .iter()
+                    .all(|key| group.iter().any(|tag| key_format(tag) == key_format(key)));
+                if !replaced {
+                    body.push(Tag::ExtXKey {
+                        method: "NONE".to_string(),
+                        uri: None,
+                        iv: None,
+                        keyformat: None,
+                        keyformatversions: None,
+                    });
+                    keys.clear();
+                }
+                if map.is_some() && !group.iter().any(|tag| matches!(tag, Tag::ExtXMap { .. })) {
+                    return Err(format!(
+                        "Playlist {} has no EXT-X-MAP but follows a playlist with one",
+                        index
+                    ));
+                }
+            }
+
+            for tag in tags {
+                match tag {
+                    Tag::ExtXKey { method, .. } if method == "NONE" => keys.clear(),
+                    Tag::ExtXKey { .. } => {
+                        keys.retain(|key| key_format(key) != key_format(tag));
+                        keys.push(tag.clone());
+                    }
+                    Tag::ExtXMap { .. } => map = Some(tag.clone()),
+                    _ => {}
+                }
+            }
+            body.extend_from_slice(tags);
+        }
+
+        let mut result = Playlist::new(body);
+        let target_duration = result.minimum_target_duration();
+        let independent = playlists
+            .iter()
+            .filter(|playlist| !playlist.segments().is_empty())
+            .all(|playlist| {
+                playlist
+                    .tags
+                    .iter()
+                    .any(|tag| matches!(tag, Tag::ExtXIndependentSegments))
+            });
+
+        let mut header = vec![
+            Tag::ExtM3U,
+            Tag::ExtXVersion(result.minimum_version()),
+            Tag::ExtXTargetDuration(target_duration),
+            Tag::ExtXPlaylistType("VOD".to_string()),
+        ];
+        if independent && !result.tags.is_empty() {
+            header.push(Tag::ExtXIndependentSegments);
+        }
+        result.tags.splice(0..0, header);
+        result.tags.push(Tag::ExtXEndList);
+        Ok(result)
+    }
+}
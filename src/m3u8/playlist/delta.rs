@@ -0,0 +1,140 @@
+//! Playlist delta updates (RFC 8216bis section 6.2.5.1).
+//!
+//! A server that declares `CAN-SKIP-UNTIL` in `EXT-X-SERVER-CONTROL` may answer
+//! a `_HLS_skip=YES` request with a delta update: the oldest segments of the
+//! playlist are replaced by a single `EXT-X-SKIP` tag, and the client rebuilds
+//! the full playlist from the copy it already has.
+//!
+//! # Example
+//!
+//! ```
+//! use m3u8_parser::m3u8::playlist::Playlist;
+//! use m3u8_parser::m3u8::types::DecimalDuration;
+//!
+//! let playlist = Playlist::from_reader(
+//!     "#EXTM3U\n#EXT-X-TARGETDURATION:4\n#EXT-X-SERVER-CONTROL:CAN-SKIP-UNTIL=8\n\
+//!      #EXT-X-MEDIA-SEQUENCE:10\n#EXTINF:4,\na.ts\n#EXTINF:4,\nb.ts\n\
+//!      #EXTINF:4,\nc.ts\n#EXTINF:4,\nd.ts\n"
+//!         .as_bytes(),
+//! )
+//! .unwrap();
+//!
+//! let delta = playlist
+//!     .delta_update(DecimalDuration::from_secs(8), &[])
+//!     .unwrap();
+//! assert_eq!(
+//!     delta.to_string(),
+//!     "#EXTM3U\n#EXT-X-VERSION:9\n#EXT-X-TARGETDURATION:4\n\
+//!      #EXT-X-SERVER-CONTROL:CAN-SKIP-UNTIL=8\n#EXT-X-MEDIA-SEQUENCE:10\n\
+//!      #EXT-X-SKIP:SKIPPED-SEGMENTS=2\n#EXTINF:4,\nc.ts\n#EXTINF:4,\nd.ts\n"
+//! );
+//! assert_eq!(playlist.apply_delta_update(&delta).unwrap().segments().len(), 4);
+//! ```
+
+use crate::m3u8::playlist::Playlist;
+use crate::m3u8::tags::Tag;
+use crate::m3u8::types::DecimalDuration;
+
+impl Playlist {
+    /// Returns the delta update of this media playlist.
+    ///
+    /// The oldest segments that end at least `can_skip_until` before the end
+    /// of the playlist are replaced by `EXT-X-SKIP`, which also lists
+    /// `recently_removed_dateranges`. `EXT-X-VERSION` is raised to 9 if needed.
+    /// If nothing is skipped and no date range was removed, the playlist is
+    /// returned unchanged. Returns an error for master playlists.
+    pub fn delta_update(
+        &self,
+        can_skip_until: DecimalDuration,
+        recently_removed_dateranges: &[String],
+    ) -> Result<Playlist, String> {
+        if self.tags.iter().any(Tag::is_master_tag) {
+            return Err("A master playlist has no delta update".to_string());
+        }
+
+        let timeline = self.timeline();
+        let skip_boundary = timeline.total_duration() - can_skip_until;
+        let skipped = timeline
+            .entries()
+            .iter()
+            .take_while(|entry| entry.end() <= skip_boundary)
+            .count();
+        if skipped == 0 && recently_removed_dateranges.is_empty() {
+            return Ok(Playlist::new(self.tags.clone()));
+        }
+
+        let start = self.segments_start();
+        let tail_start = match skipped {
+            0 => start,
+            _ => timeline.entries()[skipped - 1].tag_index + 1,
+        };
+        let mut tags = self.tags[..start].to_vec();
+        tags.push(Tag::ExtXSkip {
+            skipped_segments: skipped as u64,
+            recently_removed_dateranges: (!recently_removed_dateranges.is_empty())
+                .then(|| recently_removed_dateranges.to_vec()),
+        });
+        tags.extend_from_slice(&self.tags[tail_start..]);
+
+        let mut delta = Playlist::new(tags);
+        let version = delta.minimum_version();
+        match delta
+            .tags
+            .iter_mut()
+            .find(|tag| matches!(tag, Tag::ExtXVersion(_)))
+        {
+            Some(Tag::ExtXVersion(current)) => *current = (*current).max(version),
+            _ => {
+                let index = usize::from(matches!(delta.tags.first(), Some(Tag::ExtM3U)));
+                delta.tags.insert(index, Tag::ExtXVersion(version));
+            }
+        }
+        Ok(delta)
+    }
+
+    /// Rebuilds the full playlist from `delta`, a delta update of this
+    /// playlist, by replacing its `EXT-X-SKIP` tag with the skipped segments.
+    ///
+    /// A delta without `EXT-X-SKIP` is a full playlist and is returned as is.
+    /// Returns an error if `EXT-X-SKIP` follows a media segment or if the
+    /// skipped segments, numbered from the delta's `EXT-X-MEDIA-SEQUENCE`, are
+    /// not all in this playlist.
+    pub fn apply_delta_update(&self, delta: &Playlist) -> Result<Playlist, String> {
+        let skip = delta
+            .tags
+            .iter()
+            .enumerate()
+            .find_map(|(index, tag)| match tag {
+                Tag::ExtXSkip {
+                    skipped_segments, ..
+                } => Some((index, *skipped_segments)),
+                _ => None,
+            });
+        let Some((index, skipped)) = skip else {
+            return Ok(Playlist::new(delta.tags.clone()));
+        };
+        if delta.segments_start() < index {
+            return Err("EXT-X-SKIP must precede the first media segment".to_string());
+        }
+
+        let mut tags = delta.tags[..index].to_vec();
+        if skipped > 0 {
+            let first = delta.media_sequence();
+            let available = self.media_sequence();
+            let count = self.segments().len() as u64;
+            if first < available || first + skipped > available + count {
+                return Err(format!(
+                    "Skipped segments {}..{} are not in the previous playlist, which has {}..{}",
+                    first,
+                    first + skipped,
+                    available,
+                    available + count
+                ));
+            }
+            let offset = (first - available) as usize;
+            tags.extend(self.segment_tags(offset..offset + skipped as usize));
+        }
+        tags.extend_from_slice(&delta.tags[index + 1..]);
+        Ok(Playlist::new(tags))
+    }
+}
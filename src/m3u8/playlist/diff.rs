@@ -0,0 +1,572 @@
+//! Semantic comparison of two playlists.
+//!
+//! Comparing two fetches of the same playlist line by line buries the relevant
+//! changes under shifted segments. `Playlist::diff` instead matches segments by
+//! media sequence number, variants by URI, in order when several share one,
+//! and renditions by type, group and name, and reports what changed as a list
+//! of `Change`s, which `Diff` renders one per line.
+//!
+//! # Example
+//!
+//! ```
+//! use m3u8_parser::m3u8::playlist::Playlist;
+//!
+//! let before = Playlist::from_reader(
+//!     "#EXTM3U\n#EXT-X-TARGETDURATION:4\n#EXT-X-MEDIA-SEQUENCE:7\n\
+//!      #EXTINF:4,\na.ts\n#EXTINF:4,\nb.ts\n"
+//!         .as_bytes(),
+//! )
+//! .unwrap();
+//! let after = Playlist::from_reader(
+//!     "#EXTM3U\n#EXT-X-TARGETDURATION:4\n#EXT-X-MEDIA-SEQUENCE:8\n\
+//!      #EXTINF:4,\nb.ts\n#EXTINF:3.5,\nc.ts\n"
+//!         .as_bytes(),
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(
+//!     before.diff(&after).to_string(),
+//!     "EXT-X-MEDIA-SEQUENCE changed from 7 to 8\n\
+//!      segment 7 removed: a.ts (4s)\n\
+//!      segment 9 added: c.ts (3.5s)\n"
+//! );
+//! ```
+
+use crate::m3u8::parser::parse_attributes;
+use crate::m3u8::playlist::segment::{is_segment_tag, key_format};
+use crate::m3u8::playlist::Playlist;
+use crate::m3u8::tags::Tag;
+use crate::m3u8::types::DecimalDuration;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::ops::Range;
+
+/// The URI and duration of a media segment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentSummary {
+    /// The segment URI.
+    pub uri: String,
+    /// The `EXTINF` duration.
+    pub duration: DecimalDuration,
+}
+
+impl fmt::Display for SegmentSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}s)", self.uri, self.duration)
+    }
+}
+
+/// A difference between two playlists.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// A playlist tag, such as `EXT-X-TARGETDURATION`, was added, removed or
+    /// changed. Values are written as in the playlist, without the tag name.
+    HeaderChanged {
+        name: &'static str,
+        old: Option<String>,
+        new: Option<String>,
+    },
+    /// A segment was added at the end of the playlist.
+    SegmentAdded {
+        media_sequence: u64,
+        segment: SegmentSummary,
+    },
+    /// A segment was removed from the start or end of the playlist.
+    SegmentRemoved {
+        media_sequence: u64,
+        segment: SegmentSummary,
+    },
+    /// A segment with the same media sequence number has a different URI or
+    /// duration.
+    SegmentChanged {
+        media_sequence: u64,
+        old: SegmentSummary,
+        new: SegmentSummary,
+    },
+    /// An added segment uses other keys than the segment before it.
+    KeyRotated { media_sequence: u64, keys: Vec<Tag> },
+    /// A segment present in both playlists is encrypted with other keys.
+    KeyChanged {
+        media_sequence: u64,
+        old: Vec<Tag>,
+        new: Vec<Tag>,
+    },
+    /// A variant stream or I-frame variant was added.
+    VariantAdded { uri: String },
+    /// A variant stream or I-frame variant was removed.
+    VariantRemoved { uri: String },
+    /// An attribute of a variant stream or I-frame variant changed.
+    VariantChanged {
+        uri: String,
+        attribute: String,
+        old: Option<String>,
+        new: Option<String>,
+    },
+    /// A rendition was added.
+    RenditionAdded {
+        type_: String,
+        group_id: String,
+        name: String,
+    },
+    /// A rendition was removed.
+    RenditionRemoved {
+        type_: String,
+        group_id: String,
+        name: String,
+    },
+    /// An attribute of a rendition changed.
+    RenditionChanged {
+        type_: String,
+        group_id: String,
+        name: String,
+        attribute: String,
+        old: Option<String>,
+        new: Option<String>,
+    },
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::HeaderChanged { name, old, new } => write_change(f, name, old, new),
+            Change::SegmentAdded {
+                media_sequence,
+                segment,
+            } => write!(f, "segment {} added: {}", media_sequence, segment),
+            Change::SegmentRemoved {
+                media_sequence,
+                segment,
+            } => write!(f, "segment {} removed: {}", media_sequence, segment),
+            Change::SegmentChanged {
+                media_sequence,
+                old,
+                new,
+            } => write!(
+                f,
+                "segment {} changed from {} to {}",
+                media_sequence, old, new
+            ),
+            Change::KeyRotated {
+                media_sequence,
+                keys,
+            } => write!(
+                f,
+                "key rotated at segment {}: {}",
+                media_sequence,
+                describe_keys(keys)
+            ),
+            Change::KeyChanged {
+                media_sequence,
+                old,
+                new,
+            } => write!(
+                f,
+                "key of segment {} changed from {} to {}",
+                media_sequence,
+                describe_keys(old),
+                describe_keys(new)
+            ),
+            Change::VariantAdded { uri } => write!(f, "variant {} added", uri),
+            Change::VariantRemoved { uri } => write!(f, "variant {} removed", uri),
+            Change::VariantChanged {
+                uri,
+                attribute,
+                old,
+                new,
+            } => write_change(f, &format!("variant {} {}", uri, attribute), old, new),
+            Change::RenditionAdded {
+                type_,
+                group_id,
+                name,
+            } => write!(f, "rendition {}/{}/{} added", type_, group_id, name),
+            Change::RenditionRemoved {
+                type_,
+                group_id,
+                name,
+            } => write!(f, "rendition {}/{}/{} removed", type_, group_id, name),
+            Change::RenditionChanged {
+                type_,
+                group_id,
+                name,
+                attribute,
+                old,
+                new,
+            } => write_change(
+                f,
+                &format!("rendition {}/{}/{} {}", type_, group_id, name, attribute),
+                old,
+                new,
+            ),
+        }
+    }
+}
+
+/// Writes that `subject` was added, removed or changed.
+fn write_change(
+    f: &mut fmt::Formatter<'_>,
+    subject: &str,
+    old: &Option<String>,
+    new: &Option<String>,
+) -> fmt::Result {
+    match (old, new) {
+        (None, Some(new)) if new.is_empty() => write!(f, "{} added", subject),
+        (None, Some(new)) => write!(f, "{} added: {}", subject, new),
+        (Some(_), None) => write!(f, "{} removed", subject),
+        (Some(old), Some(new)) => write!(f, "{} changed from {} to {}", subject, old, new),
+        (None, None) => write!(f, "{} unchanged", subject),
+    }
+}
+
+/// Describes the keys in effect for a segment.
+fn describe_keys(keys: &[Tag]) -> String {
+    if keys.is_empty() {
+        return "none".to_string();
+    }
+    keys.iter()
+        .map(|key| tag_value(key).unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The changes between two playlists, in the order header, variants,
+/// renditions and segments.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Diff {
+    pub changes: Vec<Change>,
+}
+
+impl Diff {
+    /// Returns `true` if the playlists are equivalent.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl fmt::Display for Diff {
+    /// Writes one change per line.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+impl Playlist {
+    /// Returns the changes from this playlist to `other`.
+    pub fn diff(&self, other: &Playlist) -> Diff {
+        let mut changes = Vec::new();
+        diff_header(self, other, &mut changes);
+        diff_variants(self, other, &mut changes);
+        diff_renditions(self, other, &mut changes);
+        diff_segments(self, other, &mut changes);
+        Diff { changes }
+    }
+}
+
+/// Returns `true` for tags that are compared as part of the header.
+fn is_header_tag(tag: &Tag) -> bool {
+    !is_segment_tag(tag)
+        && !matches!(
+            tag,
+            Tag::ExtXStreamInf { .. } | Tag::ExtXIFrameStreamInf { .. } | Tag::ExtXMedia { .. }
+        )
+}
+
+/// Returns the text of `tag` after the tag name, if it has a value.
+fn tag_value(tag: &Tag) -> Option<String> {
+    let text = tag.to_string();
+    let first_line = text.lines().next().unwrap_or_default();
+    first_line
+        .split_once(':')
+        .map(|(_, value)| value.to_string())
+}
+
+/// Returns the attributes of a tag with an attribute list.
+fn tag_attributes(tag: &Tag) -> BTreeMap<String, String> {
+    tag_value(tag)
+        .and_then(|value| parse_attributes(&value).ok())
+        .map(|attributes| attributes.into_iter().collect())
+        .unwrap_or_default()
+}
+
+/// Reports the attributes that differ between `old` and `new`.
+fn diff_attributes(
+    old: &Tag,
+    new: &Tag,
+    mut change: impl FnMut(String, Option<String>, Option<String>),
+) {
+    let old = tag_attributes(old);
+    let new = tag_attributes(new);
+    let names: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    for name in names {
+        let (old, new) = (old.get(name), new.get(name));
+        if old != new {
+            change(name.clone(), old.cloned(), new.cloned());
+        }
+    }
+}
+
+fn diff_header(old: &Playlist, new: &Playlist, changes: &mut Vec<Change>) {
+    // Tags that may repeat, such as `EXT-X-DEFINE`, are compared as a whole.
+    let header = |playlist: &Playlist| {
+        let mut header: BTreeMap<&'static str, Vec<String>> = BTreeMap::new();
+        for tag in playlist.tags.iter().filter(|tag| is_header_tag(tag)) {
+            header
+                .entry(tag.name())
+                .or_default()
+                .push(tag_value(tag).unwrap_or_default());
+        }
+        header
+    };
+    let (old_header, new_header) = (header(old), header(new));
+
+    // Report in the order the tags appear in `old`, then in `new`.
+    let mut names: Vec<&'static str> = Vec::new();
+    for tag in old
+        .tags
+        .iter()
+        .chain(&new.tags)
+        .filter(|tag| is_header_tag(tag))
+    {
+        if !names.contains(&tag.name()) {
+            names.push(tag.name());
+        }
+    }
+    for name in names {
+        let (old_values, new_values) = (old_header.get(name), new_header.get(name));
+        if old_values != new_values {
+            changes.push(Change::HeaderChanged {
+                name,
+                old: old_values.map(|values| values.join(", ")),
+                new: new_values.map(|values| values.join(", ")),
+            });
+        }
+    }
+}
+
+fn diff_variants(old: &Playlist, new: &Playlist, changes: &mut Vec<Change>) {
+    let variants = |playlist: &Playlist| -> Vec<(String, Tag)> {
+        playlist
+            .tags
+            .iter()
+            .filter_map(|tag| match tag {
+                Tag::ExtXStreamInf { uri, .. } | Tag::ExtXIFrameStreamInf { uri, .. } => {
+                    Some((uri.clone(), tag.clone()))
+                }
+                _ => None,
+            })
+            .collect()
+    };
+    let (old_variants, new_variants) = (variants(old), variants(new));
+
+    // Variants that share a URI, e.g. with different audio groups, are
+    // matched one-to-one in the order they appear.
+    let mut matched = vec![false; new_variants.len()];
+    for (uri, tag) in &old_variants {
+        let position =
+            new_variants
+                .iter()
+                .zip(&matched)
+                .position(|((other, other_tag), &matched)| {
+                    !matched && other == uri && other_tag.name() == tag.name()
+                });
+        match position {
+            None => changes.push(Change::VariantRemoved { uri: uri.clone() }),
+            Some(position) => {
+                matched[position] = true;
+                diff_attributes(tag, &new_variants[position].1, |attribute, old, new| {
+                    changes.push(Change::VariantChanged {
+                        uri: uri.clone(),
+                        attribute,
+                        old,
+                        new,
+                    })
+                });
+            }
+        }
+    }
+    for ((uri, _), matched) in new_variants.iter().zip(matched) {
+        if !matched {
+            changes.push(Change::VariantAdded { uri: uri.clone() });
+        }
+    }
+}
+
+fn diff_renditions(old: &Playlist, new: &Playlist, changes: &mut Vec<Change>) {
+    let renditions = |playlist: &Playlist| -> Vec<((String, String, String), Tag)> {
+        playlist
+            .tags
+            .iter()
+            .filter_map(|tag| match tag {
+                Tag::ExtXMedia {
+                    type_,
+                    group_id,
+                    name,
+                    ..
+                } => Some((
+                    (
+                        type_.clone(),
+                        group_id.clone(),
+                        name.clone().unwrap_or_default(),
+                    ),
+                    tag.clone(),
+                )),
+                _ => None,
+            })
+            .collect()
+    };
+    let (old_renditions, new_renditions) = (renditions(old), renditions(new));
+    let find = |renditions: &[((String, String, String), Tag)], key: &(String, String, String)| {
+        renditions
+            .iter()
+            .find(|(other, _)| other == key)
+            .map(|(_, tag)| tag.clone())
+    };
+
+    for (key, tag) in &old_renditions {
+        let (type_, group_id, name) = key.clone();
+        match find(&new_renditions, key) {
+            None => changes.push(Change::RenditionRemoved {
+                type_,
+                group_id,
+                name,
+            }),
+            Some(new_tag) => diff_attributes(tag, &new_tag, |attribute, old, new| {
+                changes.push(Change::RenditionChanged {
+                    type_: type_.clone(),
+                    group_id: group_id.clone(),
+                    name: name.clone(),
+                    attribute,
+                    old,
+                    new,
+                })
+            }),
+        }
+    }
+    for (key, _) in &new_renditions {
+        if find(&old_renditions, key).is_none() {
+            let (type_, group_id, name) = key.clone();
+            changes.push(Change::RenditionAdded {
+                type_,
+                group_id,
+                name,
+            });
+        }
+    }
+}
+
+/// The segments of a playlist by media sequence number, with the URI,
+/// duration and keys in effect of each.
+struct NumberedSegments {
+    segments: BTreeMap<u64, (SegmentSummary, Option<Vec<Tag>>)>,
+    /// The media sequence numbers replaced by `EXT-X-SKIP`.
+    skipped: Range<u64>,
+}
+
+impl NumberedSegments {
+    /// Returns `true` if the playlist may have the segment but skipped it.
+    fn skips(&self, media_sequence: u64) -> bool {
+        self.skipped.contains(&media_sequence)
+    }
+}
+
+/// Numbers the segments of `playlist`. The keys in effect are unknown (`None`)
+/// after `EXT-X-SKIP`, which also skips the keys of the skipped segments, until
+/// the next `EXT-X-KEY`.
+fn numbered_segments(playlist: &Playlist) -> NumberedSegments {
+    let mut segments = BTreeMap::new();
+    let mut keys: Option<Vec<Tag>> = Some(Vec::new());
+    let mut media_sequence = playlist.media_sequence();
+    let mut skipped = media_sequence..media_sequence;
+    for tag in &playlist.tags {
+        match tag {
+            Tag::ExtXKey { method, .. } if method == "NONE" => keys = Some(Vec::new()),
+            Tag::ExtXKey { .. } => {
+                let keys = keys.get_or_insert_with(Vec::new);
+                keys.retain(|key| key_format(key) != key_format(tag));
+                keys.push(tag.clone());
+            }
+            Tag::ExtXSkip {
+                skipped_segments, ..
+            } if *skipped_segments > 0 => {
+                skipped = media_sequence..media_sequence + skipped_segments;
+                media_sequence = skipped.end;
+                keys = None;
+            }
+            Tag::ExtInf(uri, duration, _) => {
+                let segment = SegmentSummary {
+                    uri: uri.clone(),
+                    duration: *duration,
+                };
+                segments.insert(media_sequence, (segment, keys.clone()));
+                media_sequence += 1;
+            }
+            _ => {}
+        }
+    }
+    NumberedSegments { segments, skipped }
+}
+
+fn diff_segments(old: &Playlist, new: &Playlist, changes: &mut Vec<Change>) {
+    let old = numbered_segments(old);
+    let new = numbered_segments(new);
+    let sequences: BTreeSet<u64> = old
+        .segments
+        .keys()
+        .chain(new.segments.keys())
+        .copied()
+        .collect();
+
+    for media_sequence in sequences {
+        match (
+            old.segments.get(&media_sequence),
+            new.segments.get(&media_sequence),
+        ) {
+            // A segment replaced by `EXT-X-SKIP` is still in the playlist.
+            (Some(_), None) if new.skips(media_sequence) => {}
+            (None, Some(_)) if old.skips(media_sequence) => {}
+            (Some((segment, _)), None) => changes.push(Change::SegmentRemoved {
+                media_sequence,
+                segment: segment.clone(),
+            }),
+            (None, Some((segment, keys))) => {
+                changes.push(Change::SegmentAdded {
+                    media_sequence,
+                    segment: segment.clone(),
+                });
+                let previous_keys = media_sequence
+                    .checked_sub(1)
+                    .and_then(|previous| new.segments.get(&previous))
+                    .and_then(|(_, keys)| keys.as_ref());
+                if let (Some(previous_keys), Some(keys)) = (previous_keys, keys) {
+                    if previous_keys != keys {
+                        changes.push(Change::KeyRotated {
+                            media_sequence,
+                            keys: keys.clone(),
+                        });
+                    }
+                }
+            }
+            (Some((old_segment, old_keys)), Some((new_segment, new_keys))) => {
+                if old_segment != new_segment {
+                    changes.push(Change::SegmentChanged {
+                        media_sequence,
+                        old: old_segment.clone(),
+                        new: new_segment.clone(),
+                    });
+                }
+                if let (Some(old_keys), Some(new_keys)) = (old_keys, new_keys) {
+                    if old_keys != new_keys {
+                        changes.push(Change::KeyChanged {
+                            media_sequence,
+                            old: old_keys.clone(),
+                            new: new_keys.clone(),
+                        });
+                    }
+                }
+            }
+            (None, None) => {}
+        }
+    }
+}
@@ -0,0 +1,336 @@
+//! Filtering and re-ordering of master playlist variants.
+//!
+//! Devices differ in the bandwidth, resolution and codecs they can handle, so
+//! a master playlist is often trimmed per device before it is served. Every
+//! operation here leaves a playlist that was valid still valid: variants that
+//! refer to an audio or video group whose renditions were all removed are
+//! removed with it, subtitle and closed-caption references to an emptied group
+//! are dropped, rendition groups no variant refers to any more are removed, a
+//! group that lost its default rendition gets a new one, and I-frame variants
+//! whose resolution no variant stream has any more are removed. An operation
+//! that would remove every variant stream fails and leaves the playlist as it
+//! was.
+//!
+//! # Example
+//!
+//! ```
+//! use m3u8_parser::m3u8::playlist::Playlist;
+//!
+//! let mut playlist = Playlist::from_reader(
+//!     "#EXTM3U\n#EXT-X-STREAM-INF:BANDWIDTH=5000000,CODECS=\"hvc1.2.4.L153.B0\"\nhevc.m3u8\n\
+//!      #EXT-X-STREAM-INF:BANDWIDTH=3000000,CODECS=\"avc1.64001f\"\nhigh.m3u8\n\
+//!      #EXT-X-STREAM-INF:BANDWIDTH=1000000,CODECS=\"avc1.4d401f\"\nlow.m3u8\n"
+//!         .as_bytes(),
+//! )
+//! .unwrap();
+//!
+//! playlist.remove_codecs(&["hvc1", "hev1"]).unwrap();
+//! playlist.sort_variants_by_bandwidth(false);
+//! assert_eq!(
+//!     playlist.to_string(),
+//!     "#EXTM3U\n#EXT-X-STREAM-INF:BANDWIDTH=1000000,CODECS=\"avc1.4d401f\"\nlow.m3u8\n\
+//!      #EXT-X-STREAM-INF:BANDWIDTH=3000000,CODECS=\"avc1.64001f\"\nhigh.m3u8\n"
+//! );
+//! ```
+
+use crate::m3u8::playlist::Playlist;
+use crate::m3u8::tags::Tag;
+use crate::m3u8::types::Resolution;
+use std::collections::HashSet;
+
+impl Playlist {
+    /// Keeps the variants for which `f` returns `true`.
+    ///
+    /// `f` is called with every `ExtXStreamInf` and `ExtXIFrameStreamInf` tag.
+    /// Returns an error if the playlist is not a master playlist or if no
+    /// variant stream would remain.
+    pub fn retain_variants<F>(&mut self, mut f: F) -> Result<(), String>
+    where
+        F: FnMut(&Tag) -> bool,
+    {
+        self.filter_master(|tag| match tag {
+            Tag::ExtXStreamInf { .. } | Tag::ExtXIFrameStreamInf { .. } => f(tag),
+            _ => true,
+        })
+    }
+
+    /// Keeps the alternative renditions for which `f` returns `true`.
+    ///
+    /// `f` is called with every `ExtXMedia` tag. Returns an error if the
+    /// playlist is not a master playlist or if no variant stream would remain.
+    pub fn retain_renditions<F>(&mut self, mut f: F) -> Result<(), String>
+    where
+        F: FnMut(&Tag) -> bool,
+    {
+        self.filter_master(|tag| match tag {
+            Tag::ExtXMedia { .. } => f(tag),
+            _ => true,
+        })
+    }
+
+    /// Removes the variants whose `BANDWIDTH` exceeds `max`.
+    pub fn limit_bandwidth(&mut self, max: u32) -> Result<(), String> {
+        self.retain_variants(|tag| match tag {
+            Tag::ExtXStreamInf { bandwidth, .. } | Tag::ExtXIFrameStreamInf { bandwidth, .. } => {
+                *bandwidth <= max
+            }
+            _ => true,
+        })
+    }
+
+    /// Removes the variants whose `RESOLUTION` is wider or taller than `max`.
+    /// Variants without a resolution are kept.
+    pub fn limit_resolution(&mut self, max: Resolution) -> Result<(), String> {
+        self.retain_variants(|tag| match tag {
+            Tag::ExtXStreamInf { resolution, .. } | Tag::ExtXIFrameStreamInf { resolution, .. } => {
+                resolution.is_none_or(|resolution| {
+                    resolution.width <= max.width && resolution.height <= max.height
+                })
+            }
+            _ => true,
+        })
+    }
+
+    /// Removes the variants whose `CODECS` list one of `sample_entries`, such
+    /// as `["hvc1", "hev1"]` to remove HEVC variants.
+    pub fn remove_codecs(&mut self, sample_entries: &[&str]) -> Result<(), String> {
+        self.retain_variants(|tag| match tag {
+            Tag::ExtXStreamInf { codecs, .. } | Tag::ExtXIFrameStreamInf { codecs, .. } => {
+                codecs.as_ref().is_none_or(|codecs| {
+                    !sample_entries
+                        .iter()
+                        .any(|sample_entry| codecs.contains(sample_entry))
+                })
+            }
+            _ => true,
+        })
+    }
+
+    /// Removes the rendition group `group_id` of type `type_` (e.g. `AUDIO`).
+    pub fn remove_media_group(&mut self, type_: &str, group_id: &str) -> Result<(), String> {
+        self.retain_renditions(|tag| {
+            !matches!(
+                tag,
+                Tag::ExtXMedia { type_: t, group_id: g, .. } if t == type_ && g == group_id
+            )
+        })
+    }
+
+    /// Removes the renditions of type `type_` whose `LANGUAGE` is `language`,
+    /// compared case-insensitively.
+    pub fn remove_language(&mut self, type_: &str, language: &str) -> Result<(), String> {
+        self.retain_renditions(|tag| {
+            !matches!(
+                tag,
+                Tag::ExtXMedia { type_: t, language: Some(l), .. }
+                    if t == type_ && l.eq_ignore_ascii_case(language)
+            )
+        })
+    }
+
+    /// Sorts the variant streams by `BANDWIDTH`, in ascending order unless
+    /// `descending` is set. Variants with equal bandwidth keep their order, and
+    /// the other tags keep their position.
+    pub fn sort_variants_by_bandwidth(&mut self, descending: bool) {
+        let positions: Vec<usize> = (0..self.tags.len())
+            .filter(|&index| matches!(self.tags[index], Tag::ExtXStreamInf { .. }))
+            .collect();
+        let bandwidth = |index: usize| match self.tags[index] {
+            Tag::ExtXStreamInf { bandwidth, .. } => bandwidth,
+            _ => 0,
+        };
+        let mut order = positions.clone();
+        order.sort_by(|&a, &b| {
+            let ordering = bandwidth(a).cmp(&bandwidth(b));
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+
+        let tags: Vec<Tag> = order
+            .iter()
+            .map(|&index| self.tags[index].clone())
+            .collect();
+        for (position, tag) in positions.into_iter().zip(tags) {
+            self.tags[position] = tag;
+        }
+    }
+
+    /// Removes the rendition groups no variant stream refers to and the
+    /// I-frame variants whose resolution no variant stream has.
+    pub fn remove_orphaned_renditions(&mut self) -> Result<(), String> {
+        self.filter_master(|_| true)
+    }
+
+    /// Removes the tags for which `keep` returns `false`, then the variants and
+    /// renditions left without a counterpart.
+    fn filter_master<F>(&mut self, mut keep: F) -> Result<(), String>
+    where
+        F: FnMut(&Tag) -> bool,
+    {
+        if !self
+            .tags
+            .iter()
+            .any(|tag| matches!(tag, Tag::ExtXStreamInf { .. }))
+        {
+            return Err("Not a master playlist".to_string());
+        }
+
+        let mut tags = self.tags.clone();
+        let groups = media_groups(tags.iter());
+        let defaults: HashSet<(String, String)> = tags
+            .iter()
+            .filter_map(|tag| match tag {
+                Tag::ExtXMedia {
+                    type_,
+                    group_id,
+                    default: Some(true),
+                    ..
+                } => Some((type_.clone(), group_id.clone())),
+                _ => None,
+            })
+            .collect();
+
+        tags.retain(|tag| keep(tag));
+        prune(&mut tags, &groups);
+
+        if !tags
+            .iter()
+            .any(|tag| matches!(tag, Tag::ExtXStreamInf { .. }))
+        {
+            return Err("No variant stream would remain".to_string());
+        }
+
+        // Promote the first remaining rendition of a group that lost its default.
+        let mut has_default = media_groups(tags.iter().filter(|tag| {
+            matches!(
+                tag,
+                Tag::ExtXMedia {
+                    default: Some(true),
+                    ..
+                }
+            )
+        }));
+        for tag in &mut tags {
+            if let Tag::ExtXMedia {
+                type_,
+                group_id,
+                default,
+                autoplay,
+                ..
+            } = tag
+            {
+                let group = (type_.clone(), group_id.clone());
+                if defaults.contains(&group) && has_default.insert(group) {
+                    *default = Some(true);
+                    *autoplay = Some(true);
+                }
+            }
+        }
+
+        self.tags = tags;
+        Ok(())
+    }
+}
+
+/// Returns the `(TYPE, GROUP-ID)` of every `ExtXMedia` tag.
+fn media_groups<'a>(tags: impl Iterator<Item = &'a Tag>) -> HashSet<(String, String)> {
+    tags.filter_map(|tag| match tag {
+        Tag::ExtXMedia {
+            type_, group_id, ..
+        } => Some((type_.clone(), group_id.clone())),
+        _ => None,
+    })
+    .collect()
+}
+
+/// Removes the variants and renditions that lost their counterpart, until none
+/// is left. `groups` are the rendition groups before filtering; references to
+/// groups that never existed are left for validation to report.
+fn prune(tags: &mut Vec<Tag>, groups: &HashSet<(String, String)>) {
+    loop {
+        let before = tags.len();
+        let remaining = media_groups(tags.iter());
+        let removed = |type_: &str, group_id: &str| {
+            let group = (type_.to_string(), group_id.to_string());
+            groups.contains(&group) && !remaining.contains(&group)
+        };
+
+        tags.retain(|tag| match tag {
+            Tag::ExtXStreamInf { audio, video, .. } => {
+                !audio
+                    .as_deref()
+                    .is_some_and(|group| removed("AUDIO", group))
+                    && !video
+                        .as_deref()
+                        .is_some_and(|group| removed("VIDEO", group))
+            }
+            _ => true,
+        });
+        for tag in tags.iter_mut() {
+            if let Tag::ExtXStreamInf {
+                subtitle,
+                closed_captions,
+                ..
+            } = tag
+            {
+                if subtitle
+                    .as_deref()
+                    .is_some_and(|group| removed("SUBTITLES", group))
+                {
+                    *subtitle = None;
+                }
+                if closed_captions
+                    .as_deref()
+                    .is_some_and(|group| removed("CLOSED-CAPTIONS", group))
+                {
+                    *closed_captions = None;
+                }
+            }
+        }
+
+        let mut referenced: HashSet<(String, String)> = HashSet::new();
+        let mut resolutions: HashSet<Resolution> = HashSet::new();
+        for tag in tags.iter() {
+            if let Tag::ExtXStreamInf {
+                audio,
+                video,
+                subtitle,
+                closed_captions,
+                resolution,
+                ..
+            } = tag
+            {
+                let references = [
+                    ("AUDIO", audio),
+                    ("VIDEO", video),
+                    ("SUBTITLES", subtitle),
+                    ("CLOSED-CAPTIONS", closed_captions),
+                ];
+                for (type_, group_id) in references {
+                    if let Some(group_id) = group_id {
+                        referenced.insert((type_.to_string(), group_id.clone()));
+                    }
+                }
+                resolutions.extend(*resolution);
+            }
+        }
+
+        tags.retain(|tag| match tag {
+            Tag::ExtXMedia {
+                type_, group_id, ..
+            } => referenced.contains(&(type_.clone(), group_id.clone())),
+            Tag::ExtXIFrameStreamInf {
+                resolution: Some(resolution),
+                ..
+            } => resolutions.is_empty() || resolutions.contains(resolution),
+            _ => true,
+        });
+
+        if tags.len() == before {
+            break;
+        }
+    }
+}
@@ -0,0 +1,400 @@
+//! Generation of live media playlists.
+//!
+//! A `LivePlaylistWindow` keeps the most recent segments of a live stream and
+//! renders a `Playlist` snapshot after every change. Segments leave the window
+//! from the start as new ones are appended, which advances
+//! `EXT-X-MEDIA-SEQUENCE` and, for expiring discontinuities,
+//! `EXT-X-DISCONTINUITY-SEQUENCE` as RFC 8216 section 6.2.2 requires. The key,
+//! initialization section and date-time in effect for the first segment are
+//! carried over when the segments that declared them expire.
+//!
+//! # Example
+//!
+//! ```
+//! use m3u8_parser::m3u8::playlist::live::{LivePlaylistWindow, LiveSegment, WindowLimit};
+//! use m3u8_parser::m3u8::types::DecimalDuration;
+//!
+//! let mut window = LivePlaylistWindow::new(4, WindowLimit::Segments(3));
+//! for index in 0..5 {
+//!     let segment = LiveSegment::new(&format!("segment{}.ts", index), DecimalDuration::from_secs(4));
+//!     window.push_segment(segment).unwrap();
+//! }
+//!
+//! let playlist = window.playlist().unwrap();
+//! assert_eq!(playlist.media_sequence(), 2);
+//! assert_eq!(playlist.segments().len(), 3);
+//! assert_eq!(playlist.validate(), Ok(()));
+//! ```
+
+use crate::m3u8::playlist::segment::key_format;
+use crate::m3u8::playlist::Playlist;
+use crate::m3u8::tags::Tag;
+use crate::m3u8::types::{DateTime, DecimalDuration};
+use std::collections::VecDeque;
+
+/// A media segment appended to a `LivePlaylistWindow`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiveSegment {
+    /// The segment URI.
+    pub uri: String,
+    /// The segment duration.
+    pub duration: DecimalDuration,
+    /// The `EXTINF` title, if any.
+    pub title: Option<String>,
+    /// Whether the segment follows a discontinuity.
+    pub discontinuity: bool,
+    /// The wall-clock time at which the segment begins, if known.
+    pub program_date_time: Option<DateTime>,
+    /// An `ExtXKey` tag taking effect with this segment.
+    pub key: Option<Tag>,
+    /// An `ExtXMap` tag taking effect with this segment.
+    pub map: Option<Tag>,
+}
+
+impl LiveSegment {
+    /// Creates a segment with the given URI and duration.
+    pub fn new(uri: &str, duration: DecimalDuration) -> Self {
+        Self {
+            uri: uri.to_string(),
+            duration,
+            title: None,
+            discontinuity: false,
+            program_date_time: None,
+            key: None,
+            map: None,
+        }
+    }
+
+    /// Marks the segment as following a discontinuity.
+    pub fn discontinuity(mut self) -> Self {
+        self.discontinuity = true;
+        self
+    }
+
+    /// Sets the wall-clock time at which the segment begins.
+    pub fn program_date_time(mut self, date_time: DateTime) -> Self {
+        self.program_date_time = Some(date_time);
+        self
+    }
+
+    /// Sets the `ExtXKey` tag taking effect with this segment.
+    pub fn key(mut self, key: Tag) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    /// Sets the `ExtXMap` tag taking effect with this segment.
+    pub fn map(mut self, map: Tag) -> Self {
+        self.map = Some(map);
+        self
+    }
+}
+
+/// How many segments a `LivePlaylistWindow` keeps.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WindowLimit {
+    /// Keeps the given number of segments.
+    Segments(usize),
+    /// Keeps the fewest segments lasting at least the given duration.
+    Duration(DecimalDuration),
+}
+
+/// A segment in the window together with its partial segments.
+#[derive(Debug, Clone)]
+struct WindowSegment {
+    segment: LiveSegment,
+    parts: Vec<(String, DecimalDuration)>,
+}
+
+/// A sliding window over the segments of a live stream.
+///
+/// Segments are never removed while that would make the playlist shorter than
+/// three target durations. A window created with `event` never removes
+/// segments and renders `EXT-X-PLAYLIST-TYPE:EVENT`.
+#[derive(Debug, Clone)]
+pub struct LivePlaylistWindow {
+    target_duration: u64,
+    limit: Option<WindowLimit>,
+    part_target: Option<DecimalDuration>,
+    segments: VecDeque<WindowSegment>,
+    pending_parts: Vec<(String, DecimalDuration)>,
+    media_sequence: u64,
+    discontinuity_sequence: u64,
+    // State declared by expired segments that still applies to the first one.
+    leading_keys: Vec<Tag>,
+    leading_map: Option<Tag>,
+    leading_date_time: Option<DateTime>,
+    finished: bool,
+}
+
+impl LivePlaylistWindow {
+    /// Creates a sliding window with the given target duration, in seconds.
+    pub fn new(target_duration: u64, limit: WindowLimit) -> Self {
+        Self {
+            target_duration,
+            limit: Some(limit),
+            part_target: None,
+            segments: VecDeque::new(),
+            pending_parts: Vec::new(),
+            media_sequence: 0,
+            discontinuity_sequence: 0,
+            leading_keys: Vec::new(),
+            leading_map: None,
+            leading_date_time: None,
+            finished: false,
+        }
+    }
+
+    /// Creates an event playlist window, which keeps every segment.
+    pub fn event(target_duration: u64) -> Self {
+        Self {
+            limit: None,
+            ..Self::new(target_duration, WindowLimit::Segments(0))
+        }
+    }
+
+    /// Enables low-latency partial segments with the given part target duration.
+    ///
+    /// The playlist then advertises `EXT-X-PART-INF` and an
+    /// `EXT-X-SERVER-CONTROL` with `CAN-BLOCK-RELOAD=YES` and a `PART-HOLD-BACK`
+    /// of three part target durations, so the server must support blocking
+    /// playlist reloads.
+    pub fn part_target(mut self, part_target: DecimalDuration) -> Self {
+        self.part_target = Some(part_target);
+        self
+    }
+
+    /// Returns the media sequence number of the first segment in the window.
+    pub fn media_sequence(&self) -> u64 {
+        self.media_sequence
+    }
+
+    /// Returns the discontinuity sequence number of the first segment in the window.
+    pub fn discontinuity_sequence(&self) -> u64 {
+        self.discontinuity_sequence
+    }
+
+    /// Appends a partial segment of the segment being produced.
+    ///
+    /// The pending parts become the parts of the next segment passed to
+    /// `push_segment`.
+    pub fn push_part(&mut self, uri: &str, duration: DecimalDuration) -> Result<Playlist, String> {
+        self.check_open()?;
+        let Some(part_target) = self.part_target else {
+            return Err("Partial segments require a part target duration".to_string());
+        };
+        if duration > part_target {
+            return Err(format!(
+                "Part {} lasts {}s, longer than the part target of {}s",
+                uri, duration, part_target
+            ));
+        }
+        self.pending_parts.push((uri.to_string(), duration));
+        self.playlist()
+    }
+
+    /// Appends a segment, expires the segments that left the window and returns
+    /// the new playlist.
+    ///
+    /// Returns an error if the segment is longer than the target duration, if
+    /// its `key` or `map` is not an `ExtXKey` or `ExtXMap` tag, or if the
+    /// window was finished.
+    pub fn push_segment(&mut self, segment: LiveSegment) -> Result<Playlist, String> {
+        self.check_open()?;
+        if segment.duration.round_secs() > self.target_duration as i64 {
+            return Err(format!(
+                "Segment {} lasts {}s, longer than the target duration of {}s",
+                segment.uri, segment.duration, self.target_duration
+            ));
+        }
+        if !matches!(segment.key, None | Some(Tag::ExtXKey { .. })) {
+            return Err(format!("Key of segment {} is not EXT-X-KEY", segment.uri));
+        }
+        if !matches!(segment.map, None | Some(Tag::ExtXMap { .. })) {
+            return Err(format!("Map of segment {} is not EXT-X-MAP", segment.uri));
+        }
+
+        self.segments.push_back(WindowSegment {
+            segment,
+            parts: std::mem::take(&mut self.pending_parts),
+        });
+        while self.should_expire() {
+            self.expire();
+        }
+        self.playlist()
+    }
+
+    /// Ends the stream: the returned playlist, and every later snapshot, ends
+    /// with `EXT-X-ENDLIST`. Pending partial segments are dropped.
+    pub fn finish(&mut self) -> Result<Playlist, String> {
+        self.finished = true;
+        self.pending_parts.clear();
+        self.playlist()
+    }
+
+    /// Renders the current window as a media playlist.
+    ///
+    /// Returns an error if the discontinuity sequence number no longer fits
+    /// `EXT-X-DISCONTINUITY-SEQUENCE`.
+    pub fn playlist(&self) -> Result<Playlist, String> {
+        let mut tags = Vec::new();
+        tags.push(Tag::ExtXTargetDuration(self.target_duration));
+        if self.limit.is_none() {
+            tags.push(Tag::ExtXPlaylistType("EVENT".to_string()));
+        }
+        if let Some(part_target) = self.part_target {
+            tags.push(Tag::ExtXServerControl {
+                can_skip_until: None,
+                can_skip_dateranges: None,
+                hold_back: None,
+                part_hold_back: Some(part_target + part_target + part_target),
+                can_block_reload: Some(true),
+            });
+            tags.push(Tag::ExtXPartInf {
+                part_target_duration: part_target,
+                part_hold_back: None,
+                part_number: None,
+            });
+        }
+        tags.push(Tag::ExtXMediaSequence(self.media_sequence));
+        if self.discontinuity_sequence > 0 {
+            let discontinuity_sequence = u32::try_from(self.discontinuity_sequence)
+                .map_err(|_| "EXT-X-DISCONTINUITY-SEQUENCE overflows".to_string())?;
+            tags.push(Tag::ExtXDiscontinuitySequence(discontinuity_sequence));
+        }
+
+        // Parts are only listed for the segments ending within three target
+        // durations of the end of the playlist (RFC 8216bis section 4.4.4.9).
+        let part_window = DecimalDuration::from_secs(self.target_duration * 3);
+        let mut remaining: DecimalDuration = self
+            .pending_parts
+            .iter()
+            .map(|(_, duration)| *duration)
+            .sum();
+
+        let mut rendered: Vec<Vec<Tag>> = Vec::with_capacity(self.segments.len());
+        for (index, WindowSegment { segment, parts }) in self.segments.iter().enumerate().rev() {
+            let mut segment_tags = Vec::new();
+            if segment.discontinuity {
+                segment_tags.push(Tag::ExtXDiscontinuity);
+            }
+            if index == 0 {
+                if segment.key.is_none() {
+                    segment_tags.extend(self.leading_keys.iter().cloned());
+                }
+                if segment.map.is_none() {
+                    segment_tags.extend(self.leading_map.clone());
+                }
+            }
+            segment_tags.extend(segment.key.clone());
+            segment_tags.extend(segment.map.clone());
+            let date_time = match index {
+                0 => segment.program_date_time.or(self.leading_date_time),
+                _ => segment.program_date_time,
+            };
+            if let Some(date_time) = date_time {
+                segment_tags.push(Tag::ExtXProgramDateTime(date_time.to_string()));
+            }
+            if remaining < part_window {
+                segment_tags.extend(parts.iter().map(|(uri, duration)| Tag::ExtXPart {
+                    uri: uri.clone(),
+                    duration: Some(*duration),
+                }));
+            }
+            remaining += segment.duration;
+            segment_tags.push(Tag::ExtInf(
+                segment.uri.clone(),
+                segment.duration,
+                segment.title.clone(),
+            ));
+            rendered.push(segment_tags);
+        }
+        tags.extend(rendered.into_iter().rev().flatten());
+
+        tags.extend(
+            self.pending_parts
+                .iter()
+                .map(|(uri, duration)| Tag::ExtXPart {
+                    uri: uri.clone(),
+                    duration: Some(*duration),
+                }),
+        );
+        if self.finished {
+            tags.push(Tag::ExtXEndList);
+        }
+
+        let mut playlist = Playlist::new(tags);
+        let version = playlist.minimum_version();
+        playlist
+            .tags
+            .splice(0..0, [Tag::ExtM3U, Tag::ExtXVersion(version)]);
+        Ok(playlist)
+    }
+
+    fn check_open(&self) -> Result<(), String> {
+        if self.finished {
+            Err("The live playlist has ended".to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns `true` if the first segment has left the window.
+    fn should_expire(&self) -> bool {
+        let Some(limit) = self.limit else {
+            return false;
+        };
+        let Some(first) = self.segments.front() else {
+            return false;
+        };
+        let total: DecimalDuration = self
+            .segments
+            .iter()
+            .map(|window_segment| window_segment.segment.duration)
+            .sum();
+        let remaining = total - first.segment.duration;
+        if remaining < DecimalDuration::from_secs(self.target_duration * 3) {
+            return false;
+        }
+        match limit {
+            WindowLimit::Segments(count) => self.segments.len() > count,
+            WindowLimit::Duration(duration) => remaining >= duration,
+        }
+    }
+
+    /// Removes the first segment, keeping the state it declared.
+    fn expire(&mut self) {
+        let Some(WindowSegment { segment, .. }) = self.segments.pop_front() else {
+            return;
+        };
+        self.media_sequence += 1;
+        if segment.discontinuity {
+            self.discontinuity_sequence += 1;
+        }
+        if let Some(key) = segment.key {
+            match &key {
+                Tag::ExtXKey { method, .. } if method == "NONE" => self.leading_keys.clear(),
+                _ => {
+                    self.leading_keys
+                        .retain(|leading| key_format(leading) != key_format(&key));
+                    self.leading_keys.push(key);
+                }
+            }
+        }
+        if segment.map.is_some() {
+            self.leading_map = segment.map;
+        }
+        self.leading_date_time = segment
+            .program_date_time
+            .or(self.leading_date_time)
+            .map(|date_time| date_time.add_duration(segment.duration));
+        // A discontinuity breaks the extrapolation of the wall clock.
+        if self
+            .segments
+            .front()
+            .is_some_and(|next| next.segment.discontinuity)
+        {
+            self.leading_date_time = None;
+        }
+    }
+}
@@ -21,50 +21,171 @@
 //! ## Structs
 //!
 //! - `Playlist`: A struct representing an M3U8 playlist that contains a vector of `Tag` items.
+//!   It implements `Display`, so `playlist.to_string()` returns the playlist text.
+//! - `SourceMap`: The source line of each tag of a playlist read from text, and the URL it
+//!   was fetched from. `SourceMap::absolute_uri` resolves a URI of the playlist against that URL.
 //!
 //! ## Methods
 //!
+//! - `new(tags: Vec<Tag>) -> Self`: Creates a `Playlist` from a list of tags.
 //! - `from_reader<R: BufRead>(reader: R) -> Result<Self, String>`: Creates a new `Playlist` by reading tags from a buffered reader.
+//! - `from_reader_with_source<R: BufRead>(reader: R) -> Result<(Self, SourceMap), String>`: Also returns the source line of each tag.
 //! - `from_file<P: AsRef<Path>>(path: P) -> Result<Self, String>`: Creates a new `Playlist` by reading tags from a specified file.
+//! - `write_to<W: Write>(&self, writer: W) -> io::Result<()>`: Writes the playlist to any writer.
 //! - `write_to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()>`: Writes the playlist to a specified file.
 //! - `validate(&self) -> Result<(), Vec<ValidationError>>`: Validates the playlist according to RFC 8216, returning any validation errors.
+//! - `repair(&mut self) -> RepairReport`: Fixes common defects in place and reports each fix.
+//! - `segments(&self) -> Vec<Segment>`: Groups the tags into media segments.
+//! - `program_date_times(&self) -> Vec<Option<DateTime>>`: Computes the wall-clock start time of every segment.
+//! - `byte_ranges(&self) -> Vec<Option<ByteRange>>`: Resolves the byte range of every segment to an absolute offset.
+//! - `timeline(&self) -> Timeline`: Places every segment on the media timeline for seeking.
+//! - `slice(&self, range, mode) -> Result<Playlist, String>`: Cuts a media playlist between two media times or date-times.
+//! - `concat(playlists: &[Playlist]) -> Result<Playlist, String>`: Stitches media playlists into a single VOD playlist.
+//! - `to_vod(&self) -> Result<Playlist, String>`: Converts a live or event playlist to VOD, optionally trimmed with `to_vod_range`.
+//! - `resolve_uris(&mut self, base: &str)`: Resolves every URI against a base URL (RFC 3986).
+//! - `map_uris(&mut self, f)`: Rewrites every URI of the playlist, given its `UriKind`.
+//! - `retain_variants(&mut self, f) -> Result<(), String>`: Filters the variants of a master playlist, keeping it valid.
+//! - `sort_variants_by_bandwidth(&mut self, descending: bool)`: Re-orders the variant streams.
+//! - `diff(&self, other: &Playlist) -> Diff`: Lists the segment, header, key, variant and rendition changes between two playlists.
+//! - `delta_update(&self, can_skip_until, recently_removed_dateranges) -> Result<Playlist, String>`: Replaces the oldest segments with `EXT-X-SKIP`.
+//! - `apply_delta_update(&self, delta: &Playlist) -> Result<Playlist, String>`: Rebuilds the full playlist from a delta update.
 
 pub mod builder;
-
+pub mod concat;
+pub mod delta;
+pub mod diff;
+pub mod filter;
+pub mod live;
+pub mod repair;
+pub mod segment;
+pub mod slice;
+pub mod timeline;
+pub mod uri;
+pub mod vod;
+pub mod writer;
+
+use crate::m3u8::parser::{
+    parse_attribute, parse_attributes, parse_required_attribute, parse_yes_no,
+};
 use crate::m3u8::tags::Tag;
-use crate::m3u8::validation::ValidationError;
+use crate::m3u8::types::{ByteRange, DateTime, DecimalDuration};
+use crate::m3u8::validation::{
+    byte_range, ll_hls, master, media, version, Findings, ValidationError,
+};
+use std::fmt;
 use std::fs::File;
-use std::io::{self, BufRead, BufReader, Write};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
 use std::path::Path;
+use writer::PlaylistWriter;
 
 use regex::Regex;
 
 /// Represents a playlist containing multiple tags.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Default, PartialEq)]
 pub struct Playlist {
     pub tags: Vec<Tag>,
 }
 
+/// Where a playlist was read from: the source line of each tag and the URL
+/// the playlist was fetched from.
+///
+/// Returned by `Playlist::from_reader_with_source` alongside the playlist.
+/// The line numbers refer to the tags as parsed and are not updated when
+/// `Playlist::tags` is modified.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceMap {
+    lines: Vec<Option<usize>>,
+    url: Option<String>,
+}
+
+impl SourceMap {
+    /// Returns the 1-based source line of the tag at `tag_index`, if known.
+    pub fn line_number(&self, tag_index: usize) -> Option<usize> {
+        self.lines.get(tag_index).copied().flatten()
+    }
+
+    /// Records a tag inserted at `index` that has no source line.
+    pub(crate) fn insert(&mut self, index: usize) {
+        if !self.lines.is_empty() {
+            self.lines.insert(index.min(self.lines.len()), None);
+        }
+    }
+
+    /// Forgets the line of the tag removed from `index`.
+    pub(crate) fn remove(&mut self, index: usize) {
+        if index < self.lines.len() {
+            self.lines.remove(index);
+        }
+    }
+}
+
 impl Playlist {
+    /// Creates a `Playlist` from a list of tags.
+    pub fn new(tags: Vec<Tag>) -> Self {
+        Self { tags }
+    }
+
     /// Creates a new `Playlist` by reading tags from a buffered reader.
-    pub fn from_reader<R: BufRead>(mut reader: R) -> Result<Self, String> {
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, String> {
+        Self::from_reader_with_source(reader).map(|(playlist, _)| playlist)
+    }
+
+    /// Reads a playlist like `from_reader`, also returning the source line of
+    /// each tag.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use m3u8_parser::m3u8::playlist::Playlist;
+    ///
+    /// let (playlist, source) = Playlist::from_reader_with_source(
+    ///     "#EXTM3U\n\n#EXT-X-TARGETDURATION:4\n".as_bytes(),
+    /// )
+    /// .unwrap();
+    /// assert_eq!(playlist.tags.len(), 2);
+    /// assert_eq!(source.line_number(1), Some(3));
+    /// ```
+    pub fn from_reader_with_source<R: BufRead>(mut reader: R) -> Result<(Self, SourceMap), String> {
         let mut tags = Vec::new();
+        let mut lines = Vec::new();
 
         let mut content = String::new();
         reader
             .read_to_string(&mut content)
             .map_err(|e| e.to_string())?;
 
-        for line in content.split("#") {
-            if line.is_empty() {
-                continue;
+        // Every line starting with `#` begins a new tag; URI lines are attached
+        // to the tag they follow (e.g. `EXTINF` or `EXT-X-STREAM-INF`). Tags
+        // between an `EXTINF` and its URI, such as `EXT-X-BYTERANGE`, are moved
+        // before the `EXTINF` so that they precede the segment they apply to.
+        let mut chunks: Vec<(usize, String)> = Vec::new();
+        let mut pending_segment = None;
+        for (line_index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if let Some(tag) = line.strip_prefix('#') {
+                if tag.starts_with("EXTINF") {
+                    pending_segment = Some(chunks.len());
+                }
+                chunks.push((line_index + 1, tag.to_string()));
+            } else if !line.is_empty() {
+                if let Some(index) = pending_segment.take() {
+                    let segment = chunks.remove(index);
+                    chunks.push(segment);
+                }
+                if let Some((_, chunk)) = chunks.last_mut() {
+                    chunk.push('\n');
+                    chunk.push_str(line);
+                }
             }
+        }
 
-            if let Some(tag) = Self::parse_line(line)? {
+        for (line, chunk) in &chunks {
+            if let Some(tag) = Self::parse_line(chunk)? {
                 tags.push(tag);
+                lines.push(Some(*line));
             }
         }
-        Ok(Playlist { tags })
+        Ok((Self::new(tags), SourceMap { lines, url: None }))
     }
 
     /// Creates a new `Playlist` by reading tags from a file.
@@ -73,32 +194,64 @@ impl Playlist {
         Self::from_reader(BufReader::new(file))
     }
 
+    /// Writes the playlist to any writer, such as a socket or a `Vec<u8>`.
+    ///
+    /// Use [`PlaylistWriter`] to control the output format.
+    pub fn write_to<W: Write>(&self, writer: W) -> io::Result<()> {
+        PlaylistWriter::default().write(self, writer)
+    }
+
     /// Writes the playlist to a file.
     pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
-        let mut file = File::create(path)?;
-        for tag in &self.tags {
-            writeln!(file, "{}", tag)?;
-        }
-        Ok(())
+        self.write_to(BufWriter::new(File::create(path)?))
     }
 
     /// Validates the playlist according to RFC 8216.
+    ///
+    /// Besides the values of individual tags this checks the playlist-level
+    /// rules of section 6.2.1: segment durations against the target duration,
+    /// tag placement and uniqueness, and that master and media playlist tags
+    /// are not mixed. For master playlists it also checks that variant streams
+    /// only reference rendition groups that exist, and that the declared
+    /// `EXT-X-VERSION` supports every feature the playlist uses (section 7).
+    /// Low-latency playlists are checked against the partial segment and
+    /// server control rules of RFC 8216bis.
+    ///
+    /// Every problem is treated as an error. Use a
+    /// [`Validator`](crate::m3u8::validation::Validator) to validate against a
+    /// specific profile with warnings and per-rule severities.
+    ///
+    /// The errors carry no location. Only the `Validator` reports the
+    /// offending tag of each finding, and its source line once located with
+    /// the `SourceMap` returned by `from_reader_with_source`.
     pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
-        let mut errors = Vec::new();
+        let errors = self.validation_errors().into_errors();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Runs every check of `validate` and returns the errors found.
+    pub(crate) fn validation_errors(&self) -> Findings {
+        let mut errors = Findings::default();
 
         if !self.tags.iter().any(|tag| matches!(tag, Tag::ExtM3U)) {
             errors.push(ValidationError::MissingExtM3U);
         }
 
-        for tag in &self.tags {
-            self.validate_tag(tag, &mut errors);
+        for (index, tag) in self.tags.iter().enumerate() {
+            self.validate_tag(index, tag, &mut errors);
         }
 
-        if errors.is_empty() {
-            Ok(())
-        } else {
-            Err(errors)
-        }
+        media::validate_structure(self, &mut errors);
+        byte_range::validate_byte_ranges(self, &mut errors);
+        master::validate_references(self, &mut errors);
+        version::validate_version(self, &mut errors);
+        ll_hls::validate_low_latency(self, &mut errors);
+
+        errors
     }
 
     fn parse_line(line: &str) -> Result<Option<Tag>, String> {
@@ -161,7 +314,8 @@ impl Playlist {
 
         if trimmed.starts_with("EXT-X-KEY") {
             // Example: #EXT-X-KEY:METHOD=AES-128,URI="https://example.com/key",IV="0x1234567890ABCDEF",KEYFORMAT="identity",KEYFORMATVERSIONS="1"
-            let key_re = Regex::new(r#"EXT-X-KEY:METHOD=([A-Za-z0-9\-]+),URI="([^"]+)"(?:,IV="([^"]*)")?(?:,KEYFORMAT="([^"]+)")?(?:,KEYFORMATVERSIONS="([^"]+)")?"#).unwrap();
+            // `URI` is absent when `METHOD` is `NONE`.
+            let key_re = Regex::new(r#"EXT-X-KEY:METHOD=([A-Za-z0-9\-]+)(?:,URI="([^"]+)")?(?:,IV="([^"]*)")?(?:,KEYFORMAT="([^"]+)")?(?:,KEYFORMATVERSIONS="([^"]+)")?"#).unwrap();
 
             if let Some(caps) = key_re.captures(trimmed) {
                 let method = caps.get(1).map(|m| m.as_str()).unwrap_or_default();
@@ -182,16 +336,20 @@ impl Playlist {
 
         if trimmed.starts_with("EXT-X-MAP") {
             // Example: #EXT-X-MAP:URI="init.mp4",BYTERANGE="800@0"
-            let map_re = Regex::new(r#"EXT-X-MAP:URI="([^"]+)"(?:,BYTERANGE="([^"]+)")?"#).unwrap();
+            let map_re = Regex::new(r#"EXT-X-MAP:URI="([^"]+)"(?:,BYTERANGE="([^"]*)")?"#).unwrap();
             if let Some(caps) = map_re.captures(trimmed) {
                 let uri = caps.get(1).unwrap().as_str();
-                let byterange = caps.get(2).map(|m| m.as_str().to_string());
-                if byterange.clone().is_none() || byterange.clone().unwrap() == "" {
-                    return Ok(Some(Tag::ExtXMap {
-                        uri: uri.to_string(),
-                        byterange: None,
-                    }));
-                }
+                let byterange = caps.get(2).map(|m| m.as_str()).filter(|m| !m.is_empty());
+                let byterange = match byterange.map(|m| (m, m.parse::<ByteRange>())) {
+                    None => None,
+                    Some((_, Ok(byterange))) => Some(byterange),
+                    Some((raw, Err(_))) => {
+                        return Ok(Some(Tag::MalformedByteRange {
+                            line: trimmed.to_string(),
+                            byte_range: raw.to_string(),
+                        }))
+                    }
+                };
 
                 return Ok(Some(Tag::ExtXMap {
                     uri: uri.to_string(),
@@ -213,67 +371,54 @@ impl Playlist {
             return Ok(Some(Tag::ExtXDiscontinuity));
         }
 
-        if trimmed.starts_with("EXT-X-PART") {
-            // Example: #EXT-X-PART:URI="part1.ts",DURATION=5.0
-            let part_re = Regex::new(r#"EXT-X-PART:URI="([^\"]+)",DURATION=([\d\.]+)"#).unwrap();
-            if let Some(caps) = part_re.captures(trimmed) {
-                let uri = caps.get(1).unwrap().as_str();
-                let duration = caps.get(2).unwrap().as_str().parse().unwrap();
-                return Ok(Some(Tag::ExtXPart {
-                    uri: uri.to_string(),
-                    duration: Some(duration),
-                }));
-            }
-        }
-
-        if trimmed.starts_with("EXT-X-PART-INF") {
-            // Example: #EXT-X-PART-INF:PART-TARGET-DURATION=5.0,PART-HOLD-BACK=2.0
-            let part_inf_re = Regex::new(
-                r#"EXT-X-PART-INF:PART-TARGET-DURATION=([\d\.]+),PART-HOLD-BACK=([\d\.]+)"#,
-            )
-            .unwrap();
-            if let Some(caps) = part_inf_re.captures(trimmed) {
-                let part_target_duration = caps.get(1).unwrap().as_str().parse().unwrap();
-                let part_hold_back = caps.get(2).map(|m| m.as_str().parse().unwrap());
-                return Ok(Some(Tag::ExtXPartInf {
-                    part_target_duration,
-                    part_hold_back,
-                    part_number: None,
-                }));
-            }
-        }
-
-        if trimmed.starts_with("EXT-X-SERVER-CONTROL") {
-            // Example: #EXT-X-SERVER-CONTROL:CAN-PLAY=YES,CAN-SEEK=YES,CAN-PAUSE=YES,MIN-BUFFER-TIME=10.0
-            let server_control_re = Regex::new(r#"EXT-X-SERVER-CONTROL:CAN-PLAY=(\w+),CAN-SEEK=(\w+),CAN-PAUSE=(\w+),MIN-BUFFER-TIME=([\d\.]+)"#).unwrap();
-            if let Some(caps) = server_control_re.captures(trimmed) {
-                let can_play = caps.get(1).unwrap().as_str() == "YES";
-                let can_seek = caps.get(2).unwrap().as_str() == "YES";
-                let can_pause = caps.get(3).unwrap().as_str() == "YES";
-                let min_buffer_time = caps.get(4).unwrap().as_str().parse().unwrap();
-                return Ok(Some(Tag::ExtXServerControl {
-                    can_play: Some(can_play),
-                    can_seek: Some(can_seek),
-                    can_pause: Some(can_pause),
-                    min_buffer_time: Some(min_buffer_time),
-                }));
-            }
-        }
-
-        if trimmed.starts_with("EXT-X-SKIP") {
-            // Example: #EXT-X-SKIP:SKIPPED-SEGMENTS=3,URI="skip_segment2.ts"
-            let skip_re =
-                Regex::new(r#"EXT-X-SKIP:SKIPPED-SEGMENTS=(\d+),URI="([^\"]+)""#).unwrap();
-            if let Some(caps) = skip_re.captures(trimmed) {
-                let skipped_segments = caps.get(1).unwrap().as_str().parse().unwrap();
-                let uri = caps.get(2).unwrap().as_str();
-                return Ok(Some(Tag::ExtXSkip {
-                    uri: uri.to_string(),
-                    skipped_segments,
-                    duration: None,
-                    reason: None,
-                }));
-            }
+        if let Some(attributes) = trimmed.strip_prefix("EXT-X-PART:") {
+            // Example: #EXT-X-PART:DURATION=0.33334,URI="part1.mp4"
+            let attributes = parse_attributes(attributes)?;
+            return Ok(Some(Tag::ExtXPart {
+                uri: parse_required_attribute(&attributes, "EXT-X-PART", "URI")?,
+                duration: parse_attribute(&attributes, "DURATION")?,
+            }));
+        }
+
+        if let Some(attributes) = trimmed.strip_prefix("EXT-X-PART-INF:") {
+            // Example: #EXT-X-PART-INF:PART-TARGET=0.33334
+            let attributes = parse_attributes(attributes)?;
+            return Ok(Some(Tag::ExtXPartInf {
+                part_target_duration: parse_required_attribute(
+                    &attributes,
+                    "EXT-X-PART-INF",
+                    "PART-TARGET",
+                )?,
+                part_hold_back: parse_attribute(&attributes, "PART-HOLD-BACK")?,
+                part_number: parse_attribute(&attributes, "PART-NUMBER")?,
+            }));
+        }
+
+        if let Some(attributes) = trimmed.strip_prefix("EXT-X-SERVER-CONTROL:") {
+            // Example: #EXT-X-SERVER-CONTROL:CAN-BLOCK-RELOAD=YES,PART-HOLD-BACK=1.0,CAN-SKIP-UNTIL=12.0
+            let attributes = parse_attributes(attributes)?;
+            return Ok(Some(Tag::ExtXServerControl {
+                can_skip_until: parse_attribute(&attributes, "CAN-SKIP-UNTIL")?,
+                can_skip_dateranges: parse_yes_no(&attributes, "CAN-SKIP-DATERANGES")?,
+                hold_back: parse_attribute(&attributes, "HOLD-BACK")?,
+                part_hold_back: parse_attribute(&attributes, "PART-HOLD-BACK")?,
+                can_block_reload: parse_yes_no(&attributes, "CAN-BLOCK-RELOAD")?,
+            }));
+        }
+
+        if let Some(attributes) = trimmed.strip_prefix("EXT-X-SKIP:") {
+            // Example: #EXT-X-SKIP:SKIPPED-SEGMENTS=3,RECENTLY-REMOVED-DATERANGES="ad1\tad2"
+            let attributes = parse_attributes(attributes)?;
+            return Ok(Some(Tag::ExtXSkip {
+                skipped_segments: parse_required_attribute(
+                    &attributes,
+                    "EXT-X-SKIP",
+                    "SKIPPED-SEGMENTS",
+                )?,
+                recently_removed_dateranges: attributes
+                    .get("RECENTLY-REMOVED-DATERANGES")
+                    .map(|ids| ids.split('\t').map(str::to_string).collect()),
+            }));
         }
 
         if trimmed.starts_with("EXT-X-START") {
@@ -294,148 +439,150 @@ impl Playlist {
             return Ok(Some(Tag::ExtXIndependentSegments));
         }
 
+        if trimmed.starts_with("EXT-X-GAP") {
+            return Ok(Some(Tag::ExtXGap));
+        }
+
         if trimmed.starts_with("EXT-X-STREAM-INF") {
             // Example: #EXT-X-STREAM-INF:BANDWIDTH=500000,RESOLUTION=640x360,CODECS="avc1.42c01e,mp4a.40.2"
-            let stream_inf_re = Regex::new(
-                r#"EXT-X-STREAM-INF:BANDWIDTH=(\d+),RESOLUTION=([^,]+),CODECS="([^"]+)"\s*(\S+)"#,
-            )
-            .unwrap();
+            let stream_inf_re = Regex::new(r#"EXT-X-STREAM-INF:([^\n]*)"#).unwrap();
             if let Some(caps) = stream_inf_re.captures(trimmed) {
-                let bandwidth = caps.get(1).unwrap().as_str().parse().unwrap();
-                let resolution = caps.get(2).unwrap().as_str().to_string();
-                let codecs = caps.get(3).unwrap().as_str().to_string();
+                let attributes = parse_attributes(caps.get(1).unwrap().as_str())?;
+                let uri = trimmed
+                    .split_once('\n')
+                    .map(|(_, uri)| uri.trim())
+                    .filter(|uri| !uri.is_empty())
+                    .ok_or_else(|| "EXT-X-STREAM-INF is not followed by a URI".to_string())?;
                 return Ok(Some(Tag::ExtXStreamInf {
-                    bandwidth,
-                    resolution: Some(resolution),
-                    codecs: Some(codecs),
-                    frame_rate: None,
-                    audio: None,
-                    video: None,
-                    subtitle: None,
-                    closed_captions: None,
+                    uri: uri.to_string(),
+                    bandwidth: parse_required_attribute(
+                        &attributes,
+                        "EXT-X-STREAM-INF",
+                        "BANDWIDTH",
+                    )?,
+                    average_bandwidth: parse_attribute(&attributes, "AVERAGE-BANDWIDTH")?,
+                    codecs: parse_attribute(&attributes, "CODECS")?,
+                    resolution: parse_attribute(&attributes, "RESOLUTION")?,
+                    frame_rate: parse_attribute(&attributes, "FRAME-RATE")?,
+                    audio: attributes.get("AUDIO").cloned(),
+                    video: attributes.get("VIDEO").cloned(),
+                    subtitle: attributes.get("SUBTITLES").cloned(),
+                    closed_captions: attributes.get("CLOSED-CAPTIONS").cloned(),
                 }));
             }
         }
 
-        if trimmed.starts_with("EXT-X-MEDIA") {
+        if trimmed.starts_with("EXT-X-MEDIA:") {
             // Example: #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID="audio",NAME="English",LANGUAGE="en",DEFAULT=YES,AUTOSELECT=YES,URI="audio_en.m3u8"
-            let media_re = Regex::new(r#"EXT-X-MEDIA:TYPE=(\w+),GROUP-ID="([^"]+)",(?:NAME="([^"]+)")?,(?:LANGUAGE="([^"]+)")?,(?:DEFAULT=(YES|NO))?,(?:AUTOSELECT=(YES|NO))?,(?:URI="([^"]+)")?,(?:CHARACTERISTICS=([^,]+))?,(?:LANGUAGE-CODEC="([^"]+)")?,(?:INSTREAM-ID="([^"]+)")?,(?:FORCED=(YES|NO))?"#).unwrap();
+            let media_re = Regex::new(r#"EXT-X-MEDIA:(.*)"#).unwrap();
             if let Some(caps) = media_re.captures(trimmed) {
-                let type_ = caps.get(1).unwrap().as_str().to_string();
-                let group_id = caps.get(2).unwrap().as_str().to_string();
-                let name = Some(caps.get(3).unwrap().as_str().to_string());
-                let language = Some(caps.get(4).unwrap().as_str().to_string());
-                let default = Some(caps.get(5).unwrap().as_str() == "YES");
-                let auto_select = Some(caps.get(6).unwrap().as_str() == "YES");
-                let uri = Some(caps.get(7).unwrap().as_str().to_string());
-                let instream_id = Some(caps.get(8).unwrap().as_str().to_string());
-                let language_codec = Some(caps.get(9).unwrap().as_str().to_string());
-                let characteristics = Some(caps.get(10).unwrap().as_str().to_string());
-                let forced = Some(caps.get(11).unwrap().as_str() == "YES");
+                let tag = "EXT-X-MEDIA";
+                let attributes = parse_attributes(caps.get(1).unwrap().as_str())?;
 
                 return Ok(Some(Tag::ExtXMedia {
-                    type_,
-                    group_id,
-                    name,
-                    language,
-                    instream_id,
-                    language_codec,
-                    default,
-                    autoplay: auto_select,
-                    characteristics,
-                    uri,
-                    forced,
+                    type_: parse_required_attribute(&attributes, tag, "TYPE")?,
+                    group_id: parse_required_attribute(&attributes, tag, "GROUP-ID")?,
+                    name: attributes.get("NAME").cloned(),
+                    language: attributes.get("LANGUAGE").cloned(),
+                    instream_id: attributes.get("INSTREAM-ID").cloned(),
+                    language_codec: attributes.get("LANGUAGE-CODEC").cloned(),
+                    default: parse_yes_no(&attributes, "DEFAULT")?,
+                    autoplay: parse_yes_no(&attributes, "AUTOSELECT")?,
+                    characteristics: attributes.get("CHARACTERISTICS").cloned(),
+                    uri: attributes.get("URI").cloned(),
+                    forced: parse_yes_no(&attributes, "FORCED")?,
                 }));
             }
         }
 
-        if trimmed.starts_with("EXT-X-RENDITION-REPORT") {
-            // Example: #EXT-X-RENDITION-REPORT:URI="rendition_report.m3u8",BANDWIDTH=1000000
-            let rendition_report_re =
-                Regex::new(r#"EXT-X-RENDITION-REPORT:URI="([^"]+)",BANDWIDTH=(\d+)"#).unwrap();
-            if let Some(caps) = rendition_report_re.captures(trimmed) {
-                let uri = caps.get(1).unwrap().as_str().to_string();
-                let bandwidth = caps.get(2).unwrap().as_str().parse().unwrap();
-                return Ok(Some(Tag::ExtXRenditionReport { uri, bandwidth }));
-            }
+        if let Some(attributes) = trimmed.strip_prefix("EXT-X-RENDITION-REPORT:") {
+            // Example: #EXT-X-RENDITION-REPORT:URI="../1M/live.m3u8",LAST-MSN=273,LAST-PART=2
+            let attributes = parse_attributes(attributes)?;
+            return Ok(Some(Tag::ExtXRenditionReport {
+                uri: parse_required_attribute(&attributes, "EXT-X-RENDITION-REPORT", "URI")?,
+                last_msn: parse_attribute(&attributes, "LAST-MSN")?,
+                last_part: parse_attribute(&attributes, "LAST-PART")?,
+            }));
         }
 
         if trimmed.starts_with("EXT-X-BYTERANGE") {
             // Example: #EXT-X-BYTERANGE:500@1000
             let byte_range_re = Regex::new(r#"EXT-X-BYTERANGE:([^\s]+)"#).unwrap();
             if let Some(caps) = byte_range_re.captures(trimmed) {
-                let byte_range = caps.get(1).unwrap().as_str().to_string();
-                return Ok(Some(Tag::ExtXByteRange(byte_range)));
+                let raw = caps.get(1).unwrap().as_str();
+                return Ok(Some(match raw.parse() {
+                    Ok(byte_range) => Tag::ExtXByteRange(byte_range),
+                    Err(_) => Tag::MalformedByteRange {
+                        line: trimmed.to_string(),
+                        byte_range: raw.to_string(),
+                    },
+                }));
             }
         }
 
         if trimmed.starts_with("EXT-X-I-FRAME-STREAM-INF") {
             // Example: #EXT-X-I-FRAME-STREAM-INF:BANDWIDTH=300000,URI="iframe.m3u8"
-            let iframe_re =
-                Regex::new(r#"EXT-X-I-FRAME-STREAM-INF:BANDWIDTH=(\d+),URI="([^"]+)""#).unwrap();
+            let iframe_re = Regex::new(r#"EXT-X-I-FRAME-STREAM-INF:(.*)"#).unwrap();
             if let Some(caps) = iframe_re.captures(trimmed) {
-                let bandwidth = caps.get(1).unwrap().as_str().parse().unwrap();
-                let uri = caps.get(2).unwrap().as_str().to_string();
+                let tag = "EXT-X-I-FRAME-STREAM-INF";
+                let attributes = parse_attributes(caps.get(1).unwrap().as_str())?;
                 return Ok(Some(Tag::ExtXIFrameStreamInf {
-                    bandwidth,
-                    codecs: None,
-                    resolution: None,
-                    frame_rate: None,
-                    uri,
+                    bandwidth: parse_required_attribute(&attributes, tag, "BANDWIDTH")?,
+                    codecs: parse_attribute(&attributes, "CODECS")?,
+                    resolution: parse_attribute(&attributes, "RESOLUTION")?,
+                    frame_rate: parse_attribute(&attributes, "FRAME-RATE")?,
+                    uri: parse_required_attribute(&attributes, tag, "URI")?,
                 }));
             }
         }
 
-        if trimmed.starts_with("EXT-X-SESSION-DATA") {
+        if let Some(attributes) = trimmed.strip_prefix("EXT-X-SESSION-DATA:") {
             // Example: #EXT-X-SESSION-DATA:ID="session1",VALUE="value1",LANGUAGE="en"
-            let session_data_re =
-                Regex::new(r#"EXT-X-SESSION-DATA:ID="([^"]+)",VALUE="([^"]+)",LANGUAGE="([^"]+)""#)
-                    .unwrap();
-            if let Some(caps) = session_data_re.captures(trimmed) {
-                let id = caps.get(1).unwrap().as_str().to_string();
-                let value = caps.get(2).unwrap().as_str().to_string();
-                let language = Some(caps.get(3).unwrap().as_str().to_string());
-                return Ok(Some(Tag::ExtXSessionData {
-                    id,
-                    value,
-                    language,
-                }));
-            }
-        }
-
-        if trimmed.starts_with("EXT-X-PRELOAD-HINT") {
-            // Example: #EXT-X-PRELOAD-HINT:URI="preload_segment.ts",BYTERANGE="1000@2000"
-            let preload_hint_re =
-                Regex::new(r#"EXT-X-PRELOAD-HINT:URI="([^"]+)",BYTERANGE="([^"]+)""#).unwrap();
-            if let Some(caps) = preload_hint_re.captures(trimmed) {
-                let uri = caps.get(1).unwrap().as_str().to_string();
-                let byterange = Some(caps.get(2).unwrap().as_str().to_string());
-                return Ok(Some(Tag::ExtXPreloadHint { uri, byterange }));
-            }
+            let attributes = parse_attributes(attributes)?;
+            return Ok(Some(Tag::ExtXSessionData {
+                id: parse_required_attribute(&attributes, "EXT-X-SESSION-DATA", "ID")?,
+                value: attributes.get("VALUE").cloned(),
+                uri: attributes.get("URI").cloned(),
+                language: attributes.get("LANGUAGE").cloned(),
+            }));
+        }
+
+        if let Some(attributes) = trimmed.strip_prefix("EXT-X-PRELOAD-HINT:") {
+            // Example: #EXT-X-PRELOAD-HINT:TYPE=PART,URI="part3.mp4",BYTERANGE-START=1000
+            let attributes = parse_attributes(attributes)?;
+            return Ok(Some(Tag::ExtXPreloadHint {
+                type_: parse_required_attribute(&attributes, "EXT-X-PRELOAD-HINT", "TYPE")?,
+                uri: parse_required_attribute(&attributes, "EXT-X-PRELOAD-HINT", "URI")?,
+                byterange_start: parse_attribute(&attributes, "BYTERANGE-START")?,
+                byterange_length: parse_attribute(&attributes, "BYTERANGE-LENGTH")?,
+            }));
         }
 
         if trimmed.starts_with("EXTINF") {
-            // let split = trimmed.split("\n").collect::<Vec<_>>();
-            //
-            // let metadata_line = split.get(0).unwrap();
-            // let segment = split.get(1).unwrap();
-
-            let extinf_re = Regex::new(r#"EXTINF:(\d+(\.\d+)?),\s*(.*?),?\s*(\S+)"#).unwrap();
-            if let Some(caps) = extinf_re.captures(trimmed) {
-                let duration: f32 = caps.get(1).unwrap().as_str().parse().unwrap();
-                let title = caps
-                    .get(3)
-                    .map(|m| m.as_str().trim().to_string())
-                    .unwrap_or_else(|| "".to_string());
-                let segment = caps.get(4).unwrap().as_str().trim().to_string();
-
-                if title.is_empty() {
-                    return Ok(Some(Tag::ExtInf(segment, duration, None)));
-                }
-
-                // Return parsed values wrapped in Tag::ExtInf
-                return Ok(Some(Tag::ExtInf(segment, duration, Some(title))));
-            }
+            // Example: #EXTINF:10.0,Title, possibly with commas
+            //          https://media.example.com/first.ts
+            let (info, uri) = trimmed.split_once('\n').unwrap_or((trimmed, ""));
+            let info = info.trim_start_matches("EXTINF").trim_start_matches(':');
+            let uri = uri.trim();
+            if uri.is_empty() {
+                return Err(format!("EXTINF is not followed by a URI: {}", info));
+            }
+
+            // The duration ends at the comma, or at the whitespace preceding
+            // IPTV-style attributes.
+            let end = info
+                .find(|c: char| c == ',' || c.is_whitespace())
+                .unwrap_or(info.len());
+            let duration: DecimalDuration = info[..end].parse()?;
+            let rest = info[end..].trim_start();
+            let title = rest.strip_prefix(',').unwrap_or(rest).trim();
+
+            let title = if title.is_empty() {
+                None
+            } else {
+                Some(title.to_string())
+            };
+            return Ok(Some(Tag::ExtInf(uri.to_string(), duration, title)));
         }
 
         if trimmed.starts_with("EXT-X-SESSION-KEY") {
@@ -454,59 +601,93 @@ impl Playlist {
         Ok(None)
     }
 
-    fn validate_tag(&self, tag: &Tag, errors: &mut Vec<ValidationError>) {
+    fn validate_tag(&self, index: usize, tag: &Tag, errors: &mut Findings) {
         match tag {
-            Tag::ExtXVersion(version) => {
-                if *version < 1 || *version > 7 {
-                    errors.push(ValidationError::InvalidVersion(*version));
-                }
+            Tag::ExtXVersion(version) if *version < 1 || *version > version::MAX_VERSION => {
+                errors.push_at(index, ValidationError::InvalidVersion(*version));
             }
-            Tag::ExtInf(_, duration, _) if *duration <= 0.0 => {
-                errors.push(ValidationError::InvalidDuration(*duration));
+            Tag::ExtInf(_, duration, _) if duration.as_micros() <= 0 => {
+                errors.push_at(index, ValidationError::InvalidDuration(*duration));
             }
             Tag::ExtXTargetDuration(duration) if *duration == 0 => {
-                errors.push(ValidationError::InvalidTargetDuration(*duration));
+                errors.push_at(index, ValidationError::InvalidTargetDuration(*duration));
             }
             Tag::ExtXKey { method, .. }
                 if !matches!(method.as_str(), "NONE" | "AES-128" | "SAMPLE-AES") =>
             {
-                errors.push(ValidationError::InvalidKeyMethod(method.clone()));
+                errors.push_at(index, ValidationError::InvalidKeyMethod(method.clone()));
             }
             Tag::ExtXMap { uri, .. } if uri.is_empty() => {
-                errors.push(ValidationError::InvalidMapUri);
+                errors.push_at(index, ValidationError::InvalidMapUri);
             }
-            Tag::ExtXProgramDateTime(date_time) if date_time.is_empty() => {
-                errors.push(ValidationError::InvalidProgramDateTime);
+            Tag::ExtXMap {
+                byterange: Some(byterange),
+                ..
             }
-            Tag::ExtXGap => {
-                // Validation for EXT-X-GAP if necessary
-                // TODO: maybe we can make it configurable?
+            | Tag::ExtXByteRange(byterange)
+                if byterange.length == 0 =>
+            {
+                errors.push_at(
+                    index,
+                    ValidationError::InvalidByteRange(byterange.to_string()),
+                );
+            }
+            Tag::ExtXStreamInf {
+                resolution: Some(resolution),
+                ..
+            } if resolution.width == 0 || resolution.height == 0 => {
+                errors.push_at(
+                    index,
+                    ValidationError::InvalidStreamInf(format!(
+                        "RESOLUTION must not be zero: {}",
+                        resolution
+                    )),
+                );
+            }
+            Tag::ExtXStreamInf {
+                codecs: Some(codecs),
+                ..
+            } if codecs.is_empty() => {
+                errors.push_at(
+                    index,
+                    ValidationError::InvalidStreamInf("CODECS must not be empty".to_string()),
+                );
+            }
+            Tag::ExtXIFrameStreamInf {
+                resolution: Some(resolution),
+                ..
+            } if resolution.width == 0 || resolution.height == 0 => {
+                errors.push_at(
+                    index,
+                    ValidationError::InvalidIFrameStreamInf(format!(
+                        "RESOLUTION must not be zero: {}",
+                        resolution
+                    )),
+                );
+            }
+            Tag::ExtXProgramDateTime(date_time) if date_time.parse::<DateTime>().is_err() => {
+                errors.push_at(index, ValidationError::InvalidProgramDateTime);
             }
             Tag::ExtXBitrate(bitrate) if bitrate < &0 => {
-                errors.push(ValidationError::InvalidBitrate(*bitrate));
-            }
-            Tag::ExtXIndependentSegments => {
-                // No specific validation needed
+                errors.push_at(index, ValidationError::InvalidBitrate(*bitrate));
             }
             Tag::ExtXStart { time_offset, .. } if time_offset.is_empty() => {
-                errors.push(ValidationError::InvalidStartOffset);
-            }
-            Tag::ExtXSkip { duration, .. } if duration.unwrap() <= 0.0 => {
-                errors.push(ValidationError::InvalidSkipTag(
-                    "Duration must be positive".to_string(),
-                ));
+                errors.push_at(index, ValidationError::InvalidStartOffset);
             }
             Tag::ExtXPreloadHint { uri, .. } if uri.is_empty() => {
-                errors.push(ValidationError::InvalidPreloadHintUri);
+                errors.push_at(index, ValidationError::InvalidPreloadHintUri);
             }
             Tag::ExtXRenditionReport { uri, .. } if uri.is_empty() => {
-                errors.push(ValidationError::InvalidRenditionReportUri);
-            }
-            Tag::ExtXServerControl { .. } => {
-                // Add specific validations if needed
-                // TODO: maybe we can make it configurable?
+                errors.push_at(index, ValidationError::InvalidRenditionReportUri);
             }
             _ => {}
         }
     }
 }
+
+impl fmt::Display for Playlist {
+    /// Formats the playlist as M3U8 text, one line per tag.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&PlaylistWriter::default().write_to_string(self))
+    }
+}
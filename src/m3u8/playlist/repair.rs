@@ -0,0 +1,398 @@
+//! Automatic repair of common playlist defects.
+//!
+//! Playlists from real-world encoders and packagers often violate RFC 8216 in
+//! small, mechanical ways. This module fixes the defects whose correct value
+//! can be derived from the playlist itself and reports every fix together
+//! with the `ValidationError` it resolves. Anything else is left untouched
+//! and is still reported by `Playlist::validate`.
+//!
+//! # Example
+//!
+//! ```
+//! use m3u8_parser::m3u8::playlist::Playlist;
+//! use m3u8_parser::m3u8::validation::ValidationError;
+//!
+//! let mut playlist = Playlist::from_reader(
+//!     "#EXT-X-TARGETDURATION:4\n#EXTINF:9.5,\nsegment.ts\n#EXT-X-ENDLIST\n".as_bytes(),
+//! )
+//! .unwrap();
+//!
+//! let report = playlist.repair();
+//! assert_eq!(report.repairs[0].error, ValidationError::MissingExtM3U);
+//! assert_eq!(
+//!     playlist.to_string(),
+//!     "#EXTM3U\n#EXT-X-VERSION:3\n#EXT-X-TARGETDURATION:10\n#EXTINF:9.5,\nsegment.ts\n#EXT-X-ENDLIST\n"
+//! );
+//! assert_eq!(playlist.validate(), Ok(()));
+//! ```
+
+use crate::m3u8::playlist::{Playlist, SourceMap};
+use crate::m3u8::tags::Tag;
+use crate::m3u8::validation::ValidationError;
+use std::fmt;
+use std::io::BufRead;
+
+/// Attributes whose values are quoted-strings (RFC 8216bis section 4.2).
+const QUOTED_STRING_ATTRIBUTES: &[&str] = &[
+    "URI",
+    "KEYFORMAT",
+    "KEYFORMATVERSIONS",
+    "GROUP-ID",
+    "NAME",
+    "LANGUAGE",
+    "ASSOC-LANGUAGE",
+    "STABLE-RENDITION-ID",
+    "INSTREAM-ID",
+    "CHARACTERISTICS",
+    "CHANNELS",
+    "CODECS",
+    "SUPPLEMENTAL-CODECS",
+    "AUDIO",
+    "VIDEO",
+    "SUBTITLES",
+    "CLOSED-CAPTIONS",
+    "STABLE-VARIANT-ID",
+    "PATHWAY-ID",
+    "DATA-ID",
+    "VALUE",
+    "ID",
+    "CLASS",
+    "START-DATE",
+    "END-DATE",
+    "BYTERANGE",
+    "IMPORT",
+    "RECENTLY-REMOVED-DATERANGES",
+];
+
+/// Tags whose value is an attribute list.
+const ATTRIBUTE_LIST_TAGS: &[&str] = &[
+    "EXT-X-KEY",
+    "EXT-X-SESSION-KEY",
+    "EXT-X-MAP",
+    "EXT-X-MEDIA",
+    "EXT-X-STREAM-INF",
+    "EXT-X-I-FRAME-STREAM-INF",
+    "EXT-X-SESSION-DATA",
+    "EXT-X-DATERANGE",
+    "EXT-X-DEFINE",
+    "EXT-X-SKIP",
+    "EXT-X-PART",
+    "EXT-X-PRELOAD-HINT",
+    "EXT-X-RENDITION-REPORT",
+];
+
+/// A fix applied by `Playlist::repair`.
+#[derive(Debug, PartialEq)]
+pub struct Repair {
+    /// The validation error the fix resolves.
+    pub error: ValidationError,
+    /// The 1-based source line of the repaired tag, if known.
+    pub line: Option<usize>,
+    /// A description of the change, e.g. `set EXT-X-TARGETDURATION to 10`.
+    pub action: String,
+}
+
+impl fmt::Display for Repair {
+    /// Formats the repair as `{error}: {action}`, followed by the line if known.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.error, self.action)?;
+        if let Some(line) = self.line {
+            write!(f, " (line {})", line)?;
+        }
+        Ok(())
+    }
+}
+
+/// The fixes applied by `Playlist::repair`, in the order they were applied.
+#[derive(Debug, Default, PartialEq)]
+pub struct RepairReport {
+    /// Every applied fix.
+    pub repairs: Vec<Repair>,
+}
+
+impl RepairReport {
+    /// Returns `true` if nothing was repaired.
+    pub fn is_empty(&self) -> bool {
+        self.repairs.is_empty()
+    }
+
+    fn push(&mut self, error: ValidationError, line: Option<usize>, action: String) {
+        self.repairs.push(Repair {
+            error,
+            line,
+            action,
+        });
+    }
+}
+
+impl Playlist {
+    /// Fixes the defects of the playlist that can be fixed safely:
+    ///
+    /// - a missing `#EXTM3U` header is added;
+    /// - a missing or too low `EXT-X-VERSION` is set to the minimum version
+    ///   required by the playlist, as is an invalid one;
+    /// - duplicated tags that are identical to an earlier tag are removed;
+    /// - a missing `EXT-X-TARGETDURATION`, or one lower than the longest
+    ///   segment, is set to the longest rounded segment duration.
+    ///
+    /// Returns a report of the applied fixes. Other problems are left
+    /// untouched.
+    pub fn repair(&mut self) -> RepairReport {
+        self.repair_with_source(&mut SourceMap::default())
+    }
+
+    /// Repairs the playlist like `repair`, reporting the lines of `source` and
+    /// keeping them aligned with the tags.
+    fn repair_with_source(&mut self, source: &mut SourceMap) -> RepairReport {
+        let mut report = RepairReport::default();
+        self.repair_header(source, &mut report);
+        self.repair_version(source, &mut report);
+        self.repair_duplicates(source, &mut report);
+        self.repair_target_duration(source, &mut report);
+        report
+    }
+
+    /// Reads a playlist like `from_reader`, quoting unquoted quoted-string
+    /// attribute values before parsing and then calling `repair`.
+    ///
+    /// Tags such as `EXT-X-KEY` or `EXT-X-MAP` are otherwise skipped by the
+    /// parser when their `URI` is not quoted. An unquoted list value such as
+    /// `CODECS=avc1.64001f,mp4a.40.2` is quoted as a whole.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use m3u8_parser::m3u8::playlist::Playlist;
+    /// use m3u8_parser::m3u8::tags::Tag;
+    /// use m3u8_parser::m3u8::validation::ValidationError;
+    ///
+    /// let (playlist, report) = Playlist::from_reader_repaired(
+    ///     "#EXTM3U\n#EXT-X-VERSION:6\n#EXT-X-TARGETDURATION:4\n#EXT-X-MAP:URI=init.mp4\n\
+    ///      #EXTINF:4,\nsegment.m4s\n"
+    ///         .as_bytes(),
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(
+    ///     playlist.tags[3],
+    ///     Tag::ExtXMap { uri: "init.mp4".to_string(), byterange: None }
+    /// );
+    /// assert_eq!(
+    ///     report.repairs[0].error,
+    ///     ValidationError::UnquotedAttribute {
+    ///         tag: "EXT-X-MAP".to_string(),
+    ///         attribute: "URI".to_string(),
+    ///     }
+    /// );
+    /// ```
+    pub fn from_reader_repaired<R: BufRead>(mut reader: R) -> Result<(Self, RepairReport), String> {
+        let mut content = String::new();
+        reader
+            .read_to_string(&mut content)
+            .map_err(|e| e.to_string())?;
+
+        let mut report = RepairReport::default();
+        let mut quoted = String::with_capacity(content.len());
+        for (line_index, line) in content.lines().enumerate() {
+            quoted.push_str(&quote_attributes(line, line_index + 1, &mut report));
+            quoted.push('\n');
+        }
+
+        let (mut playlist, mut source) = Self::from_reader_with_source(quoted.as_bytes())?;
+        report
+            .repairs
+            .extend(playlist.repair_with_source(&mut source).repairs);
+        Ok((playlist, report))
+    }
+
+    /// Inserts `tag` at `index`, keeping the source lines aligned.
+    fn insert_tag(&mut self, source: &mut SourceMap, index: usize, tag: Tag) {
+        self.tags.insert(index, tag);
+        source.insert(index);
+    }
+
+    /// Removes the tag at `index`, keeping the source lines aligned.
+    fn remove_tag(&mut self, source: &mut SourceMap, index: usize) {
+        self.tags.remove(index);
+        source.remove(index);
+    }
+
+    /// Returns the index just after the `EXTM3U` and `EXT-X-VERSION` tags at the
+    /// start of the playlist.
+    fn header_end(&self) -> usize {
+        self.tags
+            .iter()
+            .take_while(|tag| matches!(tag, Tag::ExtM3U | Tag::ExtXVersion(_)))
+            .count()
+    }
+
+    fn repair_header(&mut self, source: &mut SourceMap, report: &mut RepairReport) {
+        for (_, error) in self.validation_errors() {
+            if error == ValidationError::MissingExtM3U {
+                self.insert_tag(source, 0, Tag::ExtM3U);
+                report.push(error, None, "added #EXTM3U".to_string());
+            }
+        }
+    }
+
+    fn repair_version(&mut self, source: &mut SourceMap, report: &mut RepairReport) {
+        let minimum = self.minimum_version();
+        for (tag_index, error) in self.validation_errors() {
+            if !matches!(
+                error,
+                ValidationError::VersionTooLow { .. } | ValidationError::InvalidVersion(_)
+            ) {
+                continue;
+            }
+            let line = tag_index.and_then(|index| source.line_number(index));
+            let version = self.tags.iter_mut().find_map(|tag| match tag {
+                Tag::ExtXVersion(version) => Some(version),
+                _ => None,
+            });
+            let action = match version {
+                Some(version) if *version == minimum => continue,
+                Some(version) => {
+                    *version = minimum;
+                    format!("set EXT-X-VERSION to {}", minimum)
+                }
+                None => {
+                    let index = self.header_end();
+                    self.insert_tag(source, index, Tag::ExtXVersion(minimum));
+                    format!("added EXT-X-VERSION:{}", minimum)
+                }
+            };
+            report.push(error, line, action);
+        }
+    }
+
+    fn repair_duplicates(&mut self, source: &mut SourceMap, report: &mut RepairReport) {
+        let mut duplicates = Vec::new();
+        for (tag_index, error) in self.validation_errors() {
+            let (Some(index), ValidationError::DuplicateTag(name)) = (tag_index, &error) else {
+                continue;
+            };
+            // Only identical copies are safe to drop; conflicting values are
+            // left for the author to resolve.
+            if self.tags[..index].contains(&self.tags[index]) {
+                let action = format!("removed duplicate {}", name);
+                duplicates.push((index, source.line_number(index), error, action));
+            }
+        }
+        // Remove from the end so that the remaining indices stay valid.
+        for (index, ..) in duplicates.iter().rev() {
+            self.remove_tag(source, *index);
+        }
+        for (_, line, error, action) in duplicates {
+            report.push(error, line, action);
+        }
+    }
+
+    fn repair_target_duration(&mut self, source: &mut SourceMap, report: &mut RepairReport) {
+        let longest = self.minimum_target_duration();
+        let mut raised = false;
+
+        for (tag_index, error) in self.validation_errors() {
+            match error {
+                ValidationError::MissingTargetDuration if !self.segments().is_empty() => {
+                    let index = self.header_end();
+                    self.insert_tag(source, index, Tag::ExtXTargetDuration(longest));
+                    let action = format!("added EXT-X-TARGETDURATION:{}", longest);
+                    report.push(error, None, action);
+                }
+                // One repair raises the target duration above every segment.
+                ValidationError::SegmentDurationExceedsTarget { .. } if !raised => {
+                    raised = true;
+                    let line = tag_index.and_then(|index| source.line_number(index));
+                    for tag in &mut self.tags {
+                        if let Tag::ExtXTargetDuration(target_duration) = tag {
+                            *target_duration = longest;
+                        }
+                    }
+                    let action = format!("set EXT-X-TARGETDURATION to {}", longest);
+                    report.push(error, line, action);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Quotes the unquoted quoted-string attribute values of an attribute-list
+/// tag line, recording a repair for each.
+fn quote_attributes(line: &str, line_number: usize, report: &mut RepairReport) -> String {
+    let Some((name, attributes)) = line
+        .trim()
+        .strip_prefix('#')
+        .and_then(|tag| tag.split_once(':'))
+    else {
+        return line.to_string();
+    };
+    if !ATTRIBUTE_LIST_TAGS.contains(&name) {
+        return line.to_string();
+    }
+
+    // Split on commas outside quoted-strings, as `parse_attributes` does.
+    let mut items = Vec::new();
+    let mut in_quotes = false;
+    let mut start = 0;
+    for (i, c) in attributes.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => {
+                items.push(&attributes[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(&attributes[start..]);
+
+    let mut repaired: Vec<String> = Vec::new();
+    let mut open = false;
+    for item in items {
+        match item.split_once('=') {
+            Some((key, value))
+                if QUOTED_STRING_ATTRIBUTES.contains(&key)
+                    && !value.starts_with('"')
+                    && !(key == "CLOSED-CAPTIONS" && value == "NONE") =>
+            {
+                close(&mut repaired, &mut open);
+                report.push(
+                    ValidationError::UnquotedAttribute {
+                        tag: name.to_string(),
+                        attribute: key.to_string(),
+                    },
+                    Some(line_number),
+                    format!("quoted {}", key),
+                );
+                repaired.push(format!("{}=\"{}", key, value));
+                open = true;
+            }
+            // An item without a name continues an unquoted list value, such
+            // as the second codec of `CODECS=avc1.64001f,mp4a.40.2`.
+            None if open => {
+                if let Some(last) = repaired.last_mut() {
+                    last.push(',');
+                    last.push_str(item);
+                }
+            }
+            _ => {
+                close(&mut repaired, &mut open);
+                repaired.push(item.to_string());
+            }
+        }
+    }
+    close(&mut repaired, &mut open);
+
+    format!("#{}:{}", name, repaired.join(","))
+}
+
+/// Closes the quoted-string opened by the last repaired attribute, if any.
+fn close(repaired: &mut [String], open: &mut bool) {
+    if *open {
+        if let Some(last) = repaired.last_mut() {
+            last.push('"');
+        }
+        *open = false;
+    }
+}
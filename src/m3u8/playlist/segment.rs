@@ -0,0 +1,286 @@
+//! Media segments of a media playlist.
+//!
+//! A media segment is described by an `EXTINF` tag and its URI, together with
+//! the media segment tags (`EXT-X-DISCONTINUITY`, `EXT-X-PROGRAM-DATE-TIME`, ...)
+//! that precede it. This module groups the flat tag list of a `Playlist` into
+//! segments and derives per-segment information from it.
+
+use crate::m3u8::playlist::Playlist;
+use crate::m3u8::tags::Tag;
+use crate::m3u8::types::{ByteRange, DateTime, DecimalDuration};
+use std::ops::Range;
+
+/// A media segment and the tags that apply to it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment<'a> {
+    /// Index of the segment's `ExtInf` tag in `Playlist::tags`.
+    pub tag_index: usize,
+    /// The segment URI.
+    pub uri: &'a str,
+    /// The `EXTINF` duration.
+    pub duration: DecimalDuration,
+    /// The `EXTINF` title, if any.
+    pub title: Option<&'a str>,
+    /// Whether the segment is preceded by an `EXT-X-DISCONTINUITY` tag.
+    pub discontinuity: bool,
+    /// The raw `EXT-X-PROGRAM-DATE-TIME` value attached to the segment, if any.
+    pub program_date_time: Option<&'a str>,
+    /// The `EXT-X-BYTERANGE` of the segment, if any, as written in the playlist.
+    /// Use [`Playlist::byte_ranges`] to resolve implicit offsets.
+    pub byte_range: Option<ByteRange>,
+}
+
+/// Returns `true` for tags that describe the media segment they precede.
+pub(crate) fn is_segment_tag(tag: &Tag) -> bool {
+    matches!(
+        tag,
+        Tag::ExtInf(..)
+            | Tag::ExtXKey { .. }
+            | Tag::ExtXMap { .. }
+            | Tag::ExtXProgramDateTime(_)
+            | Tag::ExtXByteRange(_)
+            | Tag::MalformedByteRange { .. }
+            | Tag::ExtXDiscontinuity
+            | Tag::ExtXGap
+            | Tag::ExtXBitrate(_)
+            | Tag::ExtXPart { .. }
+    )
+}
+
+/// Returns the `KEYFORMAT` of an `ExtXKey` tag, which defaults to `identity`.
+pub(crate) fn key_format(tag: &Tag) -> Option<&str> {
+    match tag {
+        Tag::ExtXKey { keyformat, .. } => Some(keyformat.as_deref().unwrap_or("identity")),
+        _ => None,
+    }
+}
+
+impl Playlist {
+    /// Returns the `EXT-X-MEDIA-SEQUENCE` of the playlist, the media sequence
+    /// number of its first segment. Defaults to 0 when the tag is absent.
+    pub fn media_sequence(&self) -> u64 {
+        self.tags
+            .iter()
+            .find_map(|tag| match tag {
+                Tag::ExtXMediaSequence(sequence) => Some(*sequence),
+                _ => None,
+            })
+            .unwrap_or(0)
+    }
+
+    /// Returns the `EXT-X-DISCONTINUITY-SEQUENCE` of the playlist, the
+    /// discontinuity sequence number of its first segment. Defaults to 0 when
+    /// the tag is absent.
+    pub fn discontinuity_sequence(&self) -> u64 {
+        self.tags
+            .iter()
+            .find_map(|tag| match tag {
+                Tag::ExtXDiscontinuitySequence(sequence) => Some(u64::from(*sequence)),
+                _ => None,
+            })
+            .unwrap_or(0)
+    }
+
+    /// Returns the smallest valid `EXT-X-TARGETDURATION`: the longest segment
+    /// duration rounded to the nearest second, and at least 1.
+    pub(crate) fn minimum_target_duration(&self) -> u64 {
+        self.segments()
+            .iter()
+            .map(|segment| segment.duration.round_secs())
+            .max()
+            .map_or(1, |longest| longest.max(1) as u64)
+    }
+
+    /// Returns the index of the first tag describing a media segment, i.e. the
+    /// length of the playlist header.
+    pub(crate) fn segments_start(&self) -> usize {
+        self.tags
+            .iter()
+            .position(is_segment_tag)
+            .unwrap_or(self.tags.len())
+    }
+
+    /// Returns the tags of the segments in `range`, given as indices into
+    /// `segments`.
+    ///
+    /// The keys, initialization section, bitrate and program date-time that
+    /// earlier segments declared and that still apply to the first segment of
+    /// the range are repeated before it, and an implicit byte range offset of
+    /// that segment is made explicit, so that the tags can be played without
+    /// the segments preceding them.
+    pub(crate) fn segment_tags(&self, range: Range<usize>) -> Vec<Tag> {
+        let segments = self.segments();
+        let (first, last) = (range.start, range.end - 1);
+        let group_start = match first {
+            0 => self.segments_start(),
+            _ => segments[first - 1].tag_index + 1,
+        };
+        let group = &self.tags[group_start..=segments[first].tag_index];
+
+        let mut keys: Vec<&Tag> = Vec::new();
+        let mut map = None;
+        let mut bitrate = None;
+        for tag in &self.tags[self.segments_start()..group_start] {
+            match tag {
+                Tag::ExtXKey { method, .. } if method == "NONE" => keys.clear(),
+                Tag::ExtXKey { .. } => {
+                    keys.retain(|key| key_format(key) != key_format(tag));
+                    keys.push(tag);
+                }
+                Tag::ExtXMap { .. } => map = Some(tag),
+                Tag::ExtXBitrate(_) => bitrate = Some(tag),
+                _ => {}
+            }
+        }
+
+        let mut tags = Vec::new();
+        let replaced = |kind: fn(&Tag) -> bool| group.iter().any(kind);
+        for key in keys {
+            if !group.iter().any(|tag| key_format(tag) == key_format(key)) {
+                tags.push(key.clone());
+            }
+        }
+        if !replaced(|tag| matches!(tag, Tag::ExtXMap { .. })) {
+            tags.extend(map.cloned());
+        }
+        if !replaced(|tag| matches!(tag, Tag::ExtXBitrate(_))) {
+            tags.extend(bitrate.cloned());
+        }
+        if !replaced(|tag| matches!(tag, Tag::ExtXProgramDateTime(_))) {
+            if let Some(date_time) = self.program_date_times()[first] {
+                tags.push(Tag::ExtXProgramDateTime(date_time.to_string()));
+            }
+        }
+
+        let byte_range = self.byte_ranges()[first];
+        for tag in group {
+            match (tag, byte_range) {
+                (Tag::ExtXByteRange(ByteRange { offset: None, .. }), Some(byte_range)) => {
+                    tags.push(Tag::ExtXByteRange(byte_range))
+                }
+                _ => tags.push(tag.clone()),
+            }
+        }
+        tags.extend_from_slice(
+            &self.tags[segments[first].tag_index + 1..=segments[last].tag_index],
+        );
+        tags
+    }
+
+    /// Returns the media segments of the playlist in order.
+    ///
+    /// Tags between two `EXTINF` tags are attributed to the second segment.
+    pub fn segments(&self) -> Vec<Segment<'_>> {
+        let mut segments = Vec::new();
+        let mut discontinuity = false;
+        let mut program_date_time = None;
+        let mut byte_range = None;
+
+        for (tag_index, tag) in self.tags.iter().enumerate() {
+            match tag {
+                Tag::ExtXDiscontinuity => discontinuity = true,
+                Tag::ExtXProgramDateTime(date_time) => program_date_time = Some(date_time.as_str()),
+                Tag::ExtXByteRange(range) => byte_range = Some(*range),
+                Tag::ExtInf(uri, duration, title) => {
+                    segments.push(Segment {
+                        tag_index,
+                        uri,
+                        duration: *duration,
+                        title: title.as_deref(),
+                        discontinuity,
+                        program_date_time,
+                        byte_range,
+                    });
+                    discontinuity = false;
+                    program_date_time = None;
+                    byte_range = None;
+                }
+                _ => {}
+            }
+        }
+
+        segments
+    }
+
+    /// Returns the wall-clock time at which each media segment begins.
+    ///
+    /// A segment carrying an `EXT-X-PROGRAM-DATE-TIME` tag uses that value;
+    /// following segments are extrapolated by adding the `EXTINF` durations of
+    /// the segments in between. The extrapolation stops at a discontinuity that
+    /// is not accompanied by a new date-time, and segments before the first
+    /// date-time have none. Values that are not valid ISO 8601 date-times are
+    /// ignored.
+    ///
+    /// The result has one entry per element of [`Playlist::segments`].
+    pub fn program_date_times(&self) -> Vec<Option<DateTime>> {
+        let mut current: Option<DateTime> = None;
+
+        self.segments()
+            .iter()
+            .map(|segment| {
+                match segment.program_date_time.and_then(|pdt| pdt.parse().ok()) {
+                    Some(date_time) => current = Some(date_time),
+                    None if segment.discontinuity => current = None,
+                    None => {}
+                }
+                let start = current;
+                current = current.map(|date_time| date_time.add_duration(segment.duration));
+                start
+            })
+            .collect()
+    }
+
+    /// Returns the byte range of each media segment with its offset resolved.
+    ///
+    /// A range without an offset begins at the byte following the sub-range of
+    /// the previous segment, which must be a sub-range of the same resource.
+    /// Segments without `EXT-X-BYTERANGE`, and ranges whose offset cannot be
+    /// resolved, have none.
+    ///
+    /// The result has one entry per element of [`Playlist::segments`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use m3u8_parser::m3u8::playlist::Playlist;
+    /// use m3u8_parser::m3u8::types::ByteRange;
+    ///
+    /// let playlist = Playlist::from_reader(
+    ///     "#EXTM3U\n#EXT-X-VERSION:4\n#EXT-X-TARGETDURATION:4\n\
+    ///      #EXTINF:4,\n#EXT-X-BYTERANGE:1000@500\nmain.ts\n\
+    ///      #EXTINF:4,\n#EXT-X-BYTERANGE:800\nmain.ts\n"
+    ///         .as_bytes(),
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(
+    ///     playlist.byte_ranges(),
+    ///     vec![
+    ///         Some(ByteRange::new(1000, Some(500))),
+    ///         Some(ByteRange::new(800, Some(1500))),
+    ///     ]
+    /// );
+    /// ```
+    pub fn byte_ranges(&self) -> Vec<Option<ByteRange>> {
+        // The URI and end offset of the previous segment's sub-range.
+        let mut previous: Option<(&str, u64)> = None;
+
+        self.segments()
+            .iter()
+            .map(|segment| {
+                let resolved = segment.byte_range.and_then(|range| {
+                    let offset = match (range.offset, previous) {
+                        (Some(offset), _) => offset,
+                        (None, Some((uri, end))) if uri == segment.uri => end,
+                        (None, _) => return None,
+                    };
+                    Some(ByteRange::new(range.length, Some(offset)))
+                });
+                previous = resolved.and_then(|range| {
+                    Some((segment.uri, range.offset?.checked_add(range.length)?))
+                });
+                resolved
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,163 @@
+//! Slicing of media playlists by time range.
+//!
+//! A slice keeps the media segments that overlap a range of the media timeline
+//! and carries over everything a client needs to play them on their own: the
+//! keys, initialization section and bitrate in effect for the first kept
+//! segment, its program date-time and resolved byte range, and the media and
+//! discontinuity sequence numbers it had in the original playlist.
+//!
+//! # Example
+//!
+//! ```
+//! use m3u8_parser::m3u8::playlist::slice::SliceMode;
+//! use m3u8_parser::m3u8::playlist::Playlist;
+//! use m3u8_parser::m3u8::types::DecimalDuration;
+//!
+//! let playlist = Playlist::from_reader(
+//!     "#EXTM3U\n#EXT-X-TARGETDURATION:4\n#EXTINF:4,\na.ts\n#EXTINF:4,\nb.ts\n\
+//!      #EXTINF:4,\nc.ts\n#EXT-X-ENDLIST\n"
+//!         .as_bytes(),
+//! )
+//! .unwrap();
+//!
+//! let clip = playlist
+//!     .slice(DecimalDuration::from_secs(5)..DecimalDuration::from_secs(8), SliceMode::Snap)
+//!     .unwrap();
+//! assert_eq!(
+//!     clip.to_string(),
+//!     "#EXTM3U\n#EXT-X-TARGETDURATION:4\n#EXT-X-MEDIA-SEQUENCE:1\n#EXTINF:4,\nb.ts\n#EXT-X-ENDLIST\n"
+//! );
+//! ```
+
+use crate::m3u8::playlist::timeline::Timeline;
+use crate::m3u8::playlist::Playlist;
+use crate::m3u8::tags::Tag;
+use crate::m3u8::types::{DateTime, DecimalDuration};
+use std::fmt;
+use std::ops::Range;
+
+/// A bound of the range passed to `Playlist::slice`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SlicePoint {
+    /// A media time, counted from the start of the first segment.
+    MediaTime(DecimalDuration),
+    /// A wall-clock time, mapped to media time through `EXT-X-PROGRAM-DATE-TIME`.
+    DateTime(DateTime),
+}
+
+impl From<DecimalDuration> for SlicePoint {
+    fn from(time: DecimalDuration) -> Self {
+        SlicePoint::MediaTime(time)
+    }
+}
+
+impl From<DateTime> for SlicePoint {
+    fn from(date_time: DateTime) -> Self {
+        SlicePoint::DateTime(date_time)
+    }
+}
+
+impl fmt::Display for SlicePoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SlicePoint::MediaTime(time) => write!(f, "{}s", time),
+            SlicePoint::DateTime(date_time) => write!(f, "{}", date_time),
+        }
+    }
+}
+
+/// How `Playlist::slice` treats a range start that falls inside a segment.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SliceMode {
+    /// Keeps the whole segment and adds `EXT-X-START` with `PRECISE=YES`, so
+    /// that playback begins exactly at the start of the range.
+    #[default]
+    Precise,
+    /// Snaps the start of the range to the beginning of the segment.
+    Snap,
+}
+
+impl Playlist {
+    /// Returns a media playlist with the segments overlapping `range`.
+    ///
+    /// Segments are kept whole: the first kept segment contains the start of
+    /// the range and the last one contains its end. The slice ends with
+    /// `EXT-X-ENDLIST`. Returns an error if a date-time bound is not covered by
+    /// the playlist or if no segment overlaps the range.
+    pub fn slice<T: Into<SlicePoint>>(
+        &self,
+        range: Range<T>,
+        mode: SliceMode,
+    ) -> Result<Playlist, String> {
+        let timeline = self.timeline();
+        let start = resolve_point(&timeline, range.start.into(), false)?;
+        let end = resolve_point(&timeline, range.end.into(), true)?;
+
+        let entries = timeline.entries();
+        let first = entries.iter().position(|entry| entry.end() > start);
+        let last = entries.iter().rposition(|entry| entry.start < end);
+        let (first, last) = match (first, last) {
+            (Some(first), Some(last)) if start < end && first <= last => (first, last),
+            _ => return Err(format!("No segment overlaps {}s..{}s", start, end)),
+        };
+
+        let segments = self.segments();
+        let mut tags: Vec<Tag> = self.tags[..self.segments_start()]
+            .iter()
+            .filter(|tag| {
+                !matches!(
+                    tag,
+                    Tag::ExtXMediaSequence(_)
+                        | Tag::ExtXDiscontinuitySequence(_)
+                        | Tag::ExtXStart { .. }
+                        | Tag::ExtXEndList
+                )
+            })
+            .cloned()
+            .collect();
+
+        let offset = start - entries[first].start;
+        if mode == SliceMode::Precise && offset > DecimalDuration::ZERO {
+            tags.push(Tag::ExtXStart {
+                time_offset: offset.to_string(),
+                precise: Some(true),
+            });
+        }
+        tags.push(Tag::ExtXMediaSequence(self.media_sequence() + first as u64));
+        let removed_discontinuities = segments[..first]
+            .iter()
+            .filter(|segment| segment.discontinuity)
+            .count() as u64;
+        let discontinuity_sequence = self.discontinuity_sequence() + removed_discontinuities;
+        if discontinuity_sequence > 0 {
+            let discontinuity_sequence = u32::try_from(discontinuity_sequence)
+                .map_err(|_| "EXT-X-DISCONTINUITY-SEQUENCE overflows".to_string())?;
+            tags.push(Tag::ExtXDiscontinuitySequence(discontinuity_sequence));
+        }
+        tags.extend(self.segment_tags(first..last + 1));
+        tags.push(Tag::ExtXEndList);
+
+        Ok(Playlist::new(tags))
+    }
+}
+
+/// Converts a slice bound to media time. A date-time end bound may also be
+/// the end of a segment.
+fn resolve_point(
+    timeline: &Timeline,
+    point: SlicePoint,
+    is_end: bool,
+) -> Result<DecimalDuration, String> {
+    match point {
+        SlicePoint::MediaTime(time) => Ok(time),
+        SlicePoint::DateTime(date_time) => timeline
+            .media_time_at(&date_time)
+            .or_else(|| {
+                timeline.entries().iter().find_map(|entry| {
+                    let end = entry.program_date_time?.add_duration(entry.duration);
+                    (is_end && end == date_time).then(|| entry.end())
+                })
+            })
+            .ok_or_else(|| format!("{} is not covered by the playlist", point)),
+    }
+}
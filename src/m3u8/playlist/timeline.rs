@@ -0,0 +1,128 @@
+//! Media timeline of a media playlist.
+//!
+//! The timeline places every media segment on the media time axis, which
+//! starts at 0 with the first segment of the playlist and advances by the
+//! `EXTINF` durations, and relates it to the wall clock through
+//! `EXT-X-PROGRAM-DATE-TIME`.
+//!
+//! # Example
+//!
+//! ```
+//! use m3u8_parser::m3u8::playlist::Playlist;
+//! use m3u8_parser::m3u8::types::DecimalDuration;
+//!
+//! let playlist = Playlist::from_reader(
+//!     "#EXTM3U\n#EXT-X-TARGETDURATION:4\n#EXT-X-PROGRAM-DATE-TIME:2024-11-05T12:00:00Z\n\
+//!      #EXTINF:4,\na.ts\n#EXTINF:4,\nb.ts\n"
+//!         .as_bytes(),
+//! )
+//! .unwrap();
+//! let timeline = playlist.timeline();
+//!
+//! assert_eq!(timeline.total_duration(), DecimalDuration::from_secs(8));
+//! assert_eq!(timeline.segment_at("5.5".parse().unwrap()), Some(1));
+//! assert_eq!(
+//!     timeline.date_time_at("5.5".parse().unwrap()),
+//!     Some("2024-11-05T12:00:05.5Z".parse().unwrap())
+//! );
+//! ```
+
+use crate::m3u8::playlist::Playlist;
+use crate::m3u8::types::{DateTime, DecimalDuration};
+
+/// A media segment placed on the media timeline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelineEntry {
+    /// Index of the segment's `ExtInf` tag in `Playlist::tags`.
+    pub tag_index: usize,
+    /// Media time at which the segment begins.
+    pub start: DecimalDuration,
+    /// The `EXTINF` duration.
+    pub duration: DecimalDuration,
+    /// Wall-clock time at which the segment begins, as computed by
+    /// [`Playlist::program_date_times`].
+    pub program_date_time: Option<DateTime>,
+}
+
+impl TimelineEntry {
+    /// Returns the media time at which the segment ends.
+    pub fn end(&self) -> DecimalDuration {
+        self.start + self.duration
+    }
+}
+
+/// The media timeline of a playlist, with one entry per media segment.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Timeline {
+    entries: Vec<TimelineEntry>,
+}
+
+impl Timeline {
+    /// Returns the entries, in the order of [`Playlist::segments`].
+    pub fn entries(&self) -> &[TimelineEntry] {
+        &self.entries
+    }
+
+    /// Returns the sum of all segment durations.
+    pub fn total_duration(&self) -> DecimalDuration {
+        self.entries
+            .last()
+            .map_or(DecimalDuration::ZERO, TimelineEntry::end)
+    }
+
+    /// Returns the index of the segment playing at media time `time`.
+    ///
+    /// A segment covers its start up to, but excluding, its end. Returns `None`
+    /// for times before 0 or at or after the end of the playlist.
+    pub fn segment_at(&self, time: DecimalDuration) -> Option<usize> {
+        if time < DecimalDuration::ZERO || time >= self.total_duration() {
+            return None;
+        }
+        // The first segment starting after `time` follows the one playing.
+        let index = self.entries.partition_point(|entry| entry.start <= time);
+        index.checked_sub(1)
+    }
+
+    /// Returns the wall-clock time of media time `time`, if the segment playing
+    /// at that time has a program date-time.
+    pub fn date_time_at(&self, time: DecimalDuration) -> Option<DateTime> {
+        let entry = &self.entries[self.segment_at(time)?];
+        Some(entry.program_date_time?.add_duration(time - entry.start))
+    }
+
+    /// Returns the media time at which wall-clock time `date_time` is played.
+    ///
+    /// Discontinuities may reset the wall clock, so the segments are searched
+    /// in order and the first one whose date-time range contains `date_time`
+    /// is used.
+    pub fn media_time_at(&self, date_time: &DateTime) -> Option<DecimalDuration> {
+        self.entries.iter().find_map(|entry| {
+            let offset = date_time.duration_since(&entry.program_date_time?);
+            (offset >= DecimalDuration::ZERO && offset < entry.duration)
+                .then(|| entry.start + offset)
+        })
+    }
+}
+
+impl Playlist {
+    /// Returns the media timeline of the playlist.
+    pub fn timeline(&self) -> Timeline {
+        let mut start = DecimalDuration::ZERO;
+        let entries = self
+            .segments()
+            .iter()
+            .zip(self.program_date_times())
+            .map(|(segment, program_date_time)| {
+                let entry = TimelineEntry {
+                    tag_index: segment.tag_index,
+                    start,
+                    duration: segment.duration,
+                    program_date_time,
+                };
+                start += segment.duration;
+                entry
+            })
+            .collect();
+        Timeline { entries }
+    }
+}
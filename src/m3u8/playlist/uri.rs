@@ -0,0 +1,365 @@
+//! URI references of a playlist.
+//!
+//! Segment, key, map, rendition and variant URIs are kept as written, and are
+//! usually relative to the URL of the playlist itself. `resolve` implements the
+//! reference resolution of RFC 3986 section 5.2, and `Playlist::resolve_uris`
+//! applies it to every URI of a playlist, so that downloaders and proxies can
+//! work with absolute URLs. `Playlist::map_uris` rewrites URIs in any other way.
+//!
+//! # Example
+//!
+//! ```
+//! use m3u8_parser::m3u8::playlist::Playlist;
+//! use m3u8_parser::m3u8::tags::Tag;
+//!
+//! let mut playlist = Playlist::from_reader(
+//!     "#EXTM3U\n#EXT-X-TARGETDURATION:4\n#EXT-X-MAP:URI=\"../init.mp4\"\n#EXTINF:4,\nseg1.m4s\n"
+//!         .as_bytes(),
+//! )
+//! .unwrap();
+//! playlist.resolve_uris("https://cdn.example.com/live/720p/index.m3u8");
+//!
+//! assert!(matches!(
+//!     &playlist.tags[2],
+//!     Tag::ExtXMap { uri, .. } if uri == "https://cdn.example.com/live/init.mp4"
+//! ));
+//! ```
+
+use crate::m3u8::playlist::{Playlist, SourceMap};
+use crate::m3u8::tags::Tag;
+
+/// The kind of resource a URI of a playlist refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UriKind {
+    /// A media segment (`EXTINF`).
+    Segment,
+    /// A partial segment (`EXT-X-PART`).
+    Part,
+    /// A hinted part or initialization section (`EXT-X-PRELOAD-HINT`).
+    PreloadHint,
+    /// A media initialization section (`EXT-X-MAP`).
+    Map,
+    /// A media segment decryption key (`EXT-X-KEY`).
+    Key,
+    /// A decryption key preloaded from a master playlist (`EXT-X-SESSION-KEY`).
+    SessionKey,
+    /// A JSON file of session data (`EXT-X-SESSION-DATA`).
+    SessionData,
+    /// The media playlist of a variant stream (`EXT-X-STREAM-INF`).
+    Variant,
+    /// The I-frame playlist of a variant stream (`EXT-X-I-FRAME-STREAM-INF`).
+    IFrameVariant,
+    /// The media playlist of an alternative rendition (`EXT-X-MEDIA`).
+    Rendition,
+    /// The media playlist of a rendition report (`EXT-X-RENDITION-REPORT`).
+    RenditionReport,
+}
+
+/// The components of a URI reference (RFC 3986 section 3).
+struct Reference<'a> {
+    scheme: Option<&'a str>,
+    authority: Option<&'a str>,
+    path: &'a str,
+    query: Option<&'a str>,
+    fragment: Option<&'a str>,
+}
+
+impl<'a> Reference<'a> {
+    /// Splits `uri` into its components, as the regular expression of RFC 3986
+    /// appendix B does.
+    fn parse(uri: &'a str) -> Self {
+        let (rest, fragment) = match uri.split_once('#') {
+            Some((rest, fragment)) => (rest, Some(fragment)),
+            None => (uri, None),
+        };
+        let (rest, query) = match rest.split_once('?') {
+            Some((rest, query)) => (rest, Some(query)),
+            None => (rest, None),
+        };
+        let (scheme, rest) = match rest.split_once(':') {
+            Some((scheme, rest)) if is_scheme(scheme) => (Some(scheme), rest),
+            _ => (None, rest),
+        };
+        let (authority, path) = match rest.strip_prefix("//") {
+            Some(rest) => {
+                let end = rest.find('/').unwrap_or(rest.len());
+                (Some(&rest[..end]), &rest[end..])
+            }
+            None => (None, rest),
+        };
+        Reference {
+            scheme,
+            authority,
+            path,
+            query,
+            fragment,
+        }
+    }
+}
+
+/// Returns `true` if `scheme` is a valid URI scheme: a letter followed by
+/// letters, digits, `+`, `-` or `.`.
+fn is_scheme(scheme: &str) -> bool {
+    let mut chars = scheme.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+}
+
+/// Resolves the URI reference `reference` against the URI `base` (RFC 3986
+/// section 5.2).
+///
+/// `base` should be absolute. A relative `base`, such as a file path, is
+/// accepted and yields a reference relative to the same location.
+///
+/// # Example
+///
+/// ```
+/// use m3u8_parser::m3u8::playlist::uri::resolve;
+///
+/// let base = "https://example.com/hls/main.m3u8?token=1";
+/// assert_eq!(resolve(base, "low/index.m3u8"), "https://example.com/hls/low/index.m3u8");
+/// assert_eq!(resolve(base, "../keys/k1"), "https://example.com/keys/k1");
+/// assert_eq!(resolve(base, "//cdn.example.com/a.ts"), "https://cdn.example.com/a.ts");
+/// assert_eq!(resolve(base, "?token=2"), "https://example.com/hls/main.m3u8?token=2");
+/// assert_eq!(resolve("videos/main.m3u8", "../audio/en.m3u8"), "audio/en.m3u8");
+/// ```
+pub fn resolve(base: &str, reference: &str) -> String {
+    let base = Reference::parse(base);
+    let reference = Reference::parse(reference);
+
+    let (scheme, authority, path, query) = if reference.scheme.is_some() {
+        (
+            reference.scheme,
+            reference.authority,
+            remove_dot_segments(reference.path),
+            reference.query,
+        )
+    } else if reference.authority.is_some() {
+        (
+            base.scheme,
+            reference.authority,
+            remove_dot_segments(reference.path),
+            reference.query,
+        )
+    } else if reference.path.is_empty() {
+        (
+            base.scheme,
+            base.authority,
+            base.path.to_string(),
+            reference.query.or(base.query),
+        )
+    } else if reference.path.starts_with('/') {
+        (
+            base.scheme,
+            base.authority,
+            remove_dot_segments(reference.path),
+            reference.query,
+        )
+    } else {
+        let path = merge(&base, reference.path);
+        let path = if path.starts_with('/') {
+            remove_dot_segments(&path)
+        } else {
+            remove_relative_dot_segments(&path)
+        };
+        (base.scheme, base.authority, path, reference.query)
+    };
+
+    let mut uri = String::new();
+    if let Some(scheme) = scheme {
+        uri.push_str(scheme);
+        uri.push(':');
+    }
+    if let Some(authority) = authority {
+        uri.push_str("//");
+        uri.push_str(authority);
+    }
+    uri.push_str(&path);
+    if let Some(query) = query {
+        uri.push('?');
+        uri.push_str(query);
+    }
+    if let Some(fragment) = reference.fragment {
+        uri.push('#');
+        uri.push_str(fragment);
+    }
+    uri
+}
+
+/// Appends the relative path `path` to the directory of the base path (RFC 3986
+/// section 5.2.3).
+fn merge(base: &Reference, path: &str) -> String {
+    if base.authority.is_some() && base.path.is_empty() {
+        return format!("/{}", path);
+    }
+    match base.path.rfind('/') {
+        Some(end) => format!("{}{}", &base.path[..=end], path),
+        None => path.to_string(),
+    }
+}
+
+/// Interprets the `.` and `..` segments of `path` (RFC 3986 section 5.2.4).
+fn remove_dot_segments(path: &str) -> String {
+    let mut input = path;
+    let mut output = String::with_capacity(path.len());
+    while !input.is_empty() {
+        if let Some(rest) = input
+            .strip_prefix("../")
+            .or_else(|| input.strip_prefix("./"))
+        {
+            input = rest;
+        } else if input.starts_with("/./") {
+            input = &input[2..];
+        } else if input == "/." {
+            input = "/";
+        } else if input.starts_with("/../") || input == "/.." {
+            input = if input == "/.." { "/" } else { &input[3..] };
+            output.truncate(output.rfind('/').unwrap_or(0));
+        } else if input == "." || input == ".." {
+            input = "";
+        } else {
+            let start = usize::from(input.starts_with('/'));
+            let end = input[start..]
+                .find('/')
+                .map_or(input.len(), |end| end + start);
+            output.push_str(&input[..end]);
+            input = &input[end..];
+        }
+    }
+    output
+}
+
+/// Interprets the `.` and `..` segments of a path merged with a relative base.
+/// Unlike `remove_dot_segments`, the result stays relative and `..` segments
+/// that climb above the start of the path are kept.
+fn remove_relative_dot_segments(path: &str) -> String {
+    let parts: Vec<&str> = path.split('/').collect();
+    let mut segments: Vec<&str> = Vec::with_capacity(parts.len());
+    for (index, part) in parts.iter().enumerate() {
+        let last = index + 1 == parts.len();
+        match *part {
+            "." => {}
+            ".." if segments.last().is_some_and(|segment| *segment != "..") => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+        // A trailing `.` or `..` refers to a directory.
+        if last && matches!(*part, "." | "..") {
+            segments.push("");
+        }
+    }
+    match segments.join("/") {
+        path if path.is_empty() => "./".to_string(),
+        path => path,
+    }
+}
+
+impl SourceMap {
+    /// Returns the URL the playlist was fetched from, if one was set.
+    pub fn url(&self) -> Option<&str> {
+        self.url.as_deref()
+    }
+
+    /// Sets the URL the playlist was fetched from, against which its relative
+    /// URIs are resolved.
+    pub fn set_url(&mut self, url: &str) {
+        self.url = Some(url.to_string());
+    }
+
+    /// Resolves `uri`, as written in the playlist, against the playlist URL.
+    /// Returns `uri` unchanged if no URL was set.
+    pub fn absolute_uri(&self, uri: &str) -> String {
+        match &self.url {
+            Some(base) => resolve(base, uri),
+            None => uri.to_string(),
+        }
+    }
+}
+
+impl Playlist {
+    /// Replaces every URI of the playlist with its resolution against `base`.
+    ///
+    /// Every URI visited by `map_uris` is resolved. Absolute URIs are kept,
+    /// apart from the removal of `.` and `..` segments.
+    pub fn resolve_uris(&mut self, base: &str) {
+        self.map_uris(|_, uri| resolve(base, uri));
+    }
+
+    /// Replaces every URI of the playlist with the result of `f`, which is
+    /// given the kind of reference and the URI as written.
+    ///
+    /// Every URI-bearing attribute of every tag is visited, in tag order, so
+    /// that rewriting (swapping the CDN host, signing, proxying) cannot miss
+    /// one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use m3u8_parser::m3u8::playlist::uri::UriKind;
+    /// use m3u8_parser::m3u8::playlist::Playlist;
+    ///
+    /// let mut playlist = Playlist::from_reader(
+    ///     "#EXTM3U\n#EXT-X-TARGETDURATION:4\n#EXT-X-KEY:METHOD=AES-128,URI=\"key\"\n\
+    ///      #EXTINF:4,\na.ts\n"
+    ///         .as_bytes(),
+    /// )
+    /// .unwrap();
+    /// playlist.map_uris(|kind, uri| match kind {
+    ///     UriKind::Key => uri.to_string(),
+    ///     _ => format!("{}?token=abc", uri),
+    /// });
+    ///
+    /// assert_eq!(
+    ///     playlist.to_string(),
+    ///     "#EXTM3U\n#EXT-X-TARGETDURATION:4\n#EXT-X-KEY:METHOD=AES-128,URI=\"key\"\n\
+    ///      #EXTINF:4,\na.ts?token=abc\n"
+    /// );
+    /// ```
+    pub fn map_uris<F>(&mut self, mut f: F)
+    where
+        F: FnMut(UriKind, &str) -> String,
+    {
+        for tag in &mut self.tags {
+            if let Some((kind, uri)) = uri_mut(tag) {
+                *uri = f(kind, uri);
+            }
+        }
+    }
+}
+
+/// Returns the URI of `tag` and the kind of reference it is, if it has one.
+fn uri_mut(tag: &mut Tag) -> Option<(UriKind, &mut String)> {
+    match tag {
+        Tag::ExtInf(uri, ..) => Some((UriKind::Segment, uri)),
+        Tag::ExtXPart { uri, .. } => Some((UriKind::Part, uri)),
+        Tag::ExtXPreloadHint { uri, .. } => Some((UriKind::PreloadHint, uri)),
+        Tag::ExtXMap { uri, .. } => Some((UriKind::Map, uri)),
+        Tag::ExtXKey { uri, .. } => Some((UriKind::Key, uri.as_mut()?)),
+        Tag::ExtXSessionKey { uri, .. } => Some((UriKind::SessionKey, uri.as_mut()?)),
+        Tag::ExtXSessionData { uri, .. } => Some((UriKind::SessionData, uri.as_mut()?)),
+        Tag::ExtXStreamInf { uri, .. } => Some((UriKind::Variant, uri)),
+        Tag::ExtXIFrameStreamInf { uri, .. } => Some((UriKind::IFrameVariant, uri)),
+        Tag::ExtXMedia { uri, .. } => Some((UriKind::Rendition, uri.as_mut()?)),
+        Tag::ExtXRenditionReport { uri, .. } => Some((UriKind::RenditionReport, uri)),
+        Tag::ExtM3U
+        | Tag::ExtXVersion(_)
+        | Tag::ExtXPlaylistType(_)
+        | Tag::ExtXTargetDuration(_)
+        | Tag::ExtXMediaSequence(_)
+        | Tag::ExtXDiscontinuitySequence(_)
+        | Tag::ExtXEndList
+        | Tag::ExtXProgramDateTime(_)
+        | Tag::ExtXByteRange(_)
+        | Tag::MalformedByteRange { .. }
+        | Tag::ExtXDefine(_)
+        | Tag::ExtXGap
+        | Tag::ExtXBitrate(_)
+        | Tag::ExtXIndependentSegments
+        | Tag::ExtXStart { .. }
+        | Tag::ExtXServerControl { .. }
+        | Tag::ExtXPartInf { .. }
+        | Tag::ExtXSkip { .. }
+        | Tag::ExtXDiscontinuity => None,
+    }
+}
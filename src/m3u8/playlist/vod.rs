@@ -0,0 +1,104 @@
+//! Conversion of live and event media playlists to VOD.
+//!
+//! When a live event ends, its recording is published as a VOD playlist: the
+//! playlist type becomes `VOD`, `EXT-X-ENDLIST` marks it as complete, and the
+//! low-latency tags, which only make sense at the live edge, are removed.
+//!
+//! # Example
+//!
+//! ```
+//! use m3u8_parser::m3u8::playlist::Playlist;
+//!
+//! let event = Playlist::from_reader(
+//!     "#EXTM3U\n#EXT-X-VERSION:9\n#EXT-X-TARGETDURATION:4\n#EXT-X-PLAYLIST-TYPE:EVENT\n\
+//!      #EXT-X-SERVER-CONTROL:CAN-BLOCK-RELOAD=YES,PART-HOLD-BACK=3\n\
+//!      #EXT-X-PART-INF:PART-TARGET=1\n#EXTINF:4,\na.ts\n\
+//!      #EXT-X-PART:DURATION=1,URI=\"b.0.ts\"\n#EXTINF:3.5,\nb.ts\n\
+//!      #EXT-X-PRELOAD-HINT:TYPE=PART,URI=\"c.0.ts\"\n"
+//!         .as_bytes(),
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(
+//!     event.to_vod().unwrap().to_string(),
+//!     "#EXTM3U\n#EXT-X-VERSION:3\n#EXT-X-TARGETDURATION:4\n#EXT-X-PLAYLIST-TYPE:VOD\n\
+//!      #EXTINF:4,\na.ts\n#EXTINF:3.5,\nb.ts\n#EXT-X-ENDLIST\n"
+//! );
+//! ```
+
+use crate::m3u8::playlist::slice::{SliceMode, SlicePoint};
+use crate::m3u8::playlist::Playlist;
+use crate::m3u8::tags::Tag;
+use std::ops::Range;
+
+impl Playlist {
+    /// Converts a live or event media playlist to a VOD playlist.
+    ///
+    /// `EXT-X-PLAYLIST-TYPE` is set to `VOD` and `EXT-X-ENDLIST` is appended.
+    /// Partial segments, preload hints, rendition reports, `EXT-X-PART-INF`
+    /// and `EXT-X-SERVER-CONTROL` are removed. `EXT-X-TARGETDURATION` is set to
+    /// the longest rounded segment duration and `EXT-X-VERSION` to the minimum
+    /// version the result requires.
+    ///
+    /// Returns an error for master playlists and for delta updates, which must
+    /// be applied to the full playlist first.
+    pub fn to_vod(&self) -> Result<Playlist, String> {
+        if self.tags.iter().any(Tag::is_master_tag) {
+            return Err("A master playlist cannot be converted to VOD".to_string());
+        }
+        if self
+            .tags
+            .iter()
+            .any(|tag| matches!(tag, Tag::ExtXSkip { .. }))
+        {
+            return Err("A playlist delta update cannot be converted to VOD".to_string());
+        }
+
+        let mut tags: Vec<Tag> = self
+            .tags
+            .iter()
+            .filter(|tag| {
+                !matches!(
+                    tag,
+                    Tag::ExtXPart { .. }
+                        | Tag::ExtXPreloadHint { .. }
+                        | Tag::ExtXRenditionReport { .. }
+                        | Tag::ExtXPartInf { .. }
+                        | Tag::ExtXServerControl { .. }
+                        | Tag::ExtXPlaylistType(_)
+                        | Tag::ExtXEndList
+                )
+            })
+            .cloned()
+            .collect();
+        tags.push(Tag::ExtXEndList);
+        let mut vod = Playlist::new(tags);
+
+        // The header is rebuilt in the order EXTM3U, VERSION, TARGETDURATION,
+        // PLAYLIST-TYPE, followed by the other header tags.
+        let target_duration = vod.minimum_target_duration();
+        vod.tags
+            .retain(|tag| !matches!(tag, Tag::ExtXVersion(_) | Tag::ExtXTargetDuration(_)));
+        let start = usize::from(matches!(vod.tags.first(), Some(Tag::ExtM3U)));
+        vod.tags.splice(
+            start..start,
+            [
+                Tag::ExtXTargetDuration(target_duration),
+                Tag::ExtXPlaylistType("VOD".to_string()),
+            ],
+        );
+        let version = vod.minimum_version();
+        vod.tags.insert(start, Tag::ExtXVersion(version));
+        Ok(vod)
+    }
+
+    /// Converts the segments of a live or event media playlist that overlap
+    /// `range` to a VOD playlist, as `slice` followed by `to_vod` does.
+    pub fn to_vod_range<T: Into<SlicePoint>>(
+        &self,
+        range: Range<T>,
+        mode: SliceMode,
+    ) -> Result<Playlist, String> {
+        self.slice(range, mode)?.to_vod()
+    }
+}
@@ -0,0 +1,174 @@
+use crate::m3u8::playlist::Playlist;
+use crate::m3u8::tags::Tag;
+use std::io::{self, Write};
+
+/// The line terminator written between playlist lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineEnding {
+    /// `\n`
+    #[default]
+    Lf,
+    /// `\r\n`
+    CrLf,
+}
+
+impl LineEnding {
+    /// Returns the line terminator as a string.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+}
+
+/// Serialises a `Playlist` with configurable formatting.
+///
+/// `Playlist::write_to`, `Playlist::write_to_file` and the `Display`
+/// implementation of `Playlist` all use the default configuration: `\n` line
+/// endings, durations written with the precision they were parsed with and a
+/// trailing newline.
+///
+/// # Example
+///
+/// ```
+/// use m3u8_parser::m3u8::playlist::builder::PlaylistBuilder;
+/// use m3u8_parser::m3u8::playlist::writer::{LineEnding, PlaylistWriter};
+///
+/// let playlist = PlaylistBuilder::new()
+///     .extm3u()
+///     .version(3)
+///     .target_duration(10)
+///     .extinf("first.ts", 9.5, None)
+///     .end_list()
+///     .build()
+///     .unwrap();
+///
+/// let output = PlaylistWriter::new()
+///     .line_ending(LineEnding::CrLf)
+///     .float_precision(3)
+///     .write_to_string(&playlist);
+/// assert_eq!(
+///     output,
+///     "#EXTM3U\r\n#EXT-X-VERSION:3\r\n#EXT-X-TARGETDURATION:10\r\n#EXTINF:9.500,\r\nfirst.ts\r\n#EXT-X-ENDLIST\r\n"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaylistWriter {
+    line_ending: LineEnding,
+    float_precision: Option<u8>,
+    trailing_newline: bool,
+}
+
+impl Default for PlaylistWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PlaylistWriter {
+    /// Creates a `PlaylistWriter` with the default configuration.
+    pub fn new() -> Self {
+        Self {
+            line_ending: LineEnding::Lf,
+            float_precision: None,
+            trailing_newline: true,
+        }
+    }
+
+    /// Sets the line terminator.
+    pub fn line_ending(mut self, line_ending: LineEnding) -> Self {
+        self.line_ending = line_ending;
+        self
+    }
+
+    /// Writes every decimal duration (`EXTINF`, `EXT-X-PART`, `EXT-X-PART-INF`
+    /// and `EXT-X-SERVER-CONTROL`) with exactly `precision` fractional digits
+    /// instead of the precision it was parsed with.
+    pub fn float_precision(mut self, precision: u8) -> Self {
+        self.float_precision = Some(precision);
+        self
+    }
+
+    /// Sets whether the last line is followed by a line terminator.
+    pub fn trailing_newline(mut self, trailing_newline: bool) -> Self {
+        self.trailing_newline = trailing_newline;
+        self
+    }
+
+    /// Writes the playlist to `writer`.
+    pub fn write<W: Write>(&self, playlist: &Playlist, mut writer: W) -> io::Result<()> {
+        writer.write_all(self.write_to_string(playlist).as_bytes())?;
+        writer.flush()
+    }
+
+    /// Writes the playlist into a `String`.
+    pub fn write_to_string(&self, playlist: &Playlist) -> String {
+        let line_ending = self.line_ending.as_str();
+        let mut output = String::new();
+
+        for (i, tag) in playlist.tags.iter().enumerate() {
+            if i > 0 {
+                output.push_str(line_ending);
+            }
+            let tag = self.format_tag(tag);
+            // Tags followed by a URI line (e.g. `EXTINF`) contain a `\n`.
+            for (j, line) in tag.split('\n').enumerate() {
+                if j > 0 {
+                    output.push_str(line_ending);
+                }
+                output.push_str(line);
+            }
+        }
+
+        if self.trailing_newline && !playlist.tags.is_empty() {
+            output.push_str(line_ending);
+        }
+        output
+    }
+
+    fn format_tag(&self, tag: &Tag) -> String {
+        let Some(precision) = self.float_precision else {
+            return tag.to_string();
+        };
+
+        match tag {
+            Tag::ExtInf(uri, duration, title) => Tag::ExtInf(
+                uri.clone(),
+                duration.with_precision(precision),
+                title.clone(),
+            )
+            .to_string(),
+            Tag::ExtXPart { uri, duration } => Tag::ExtXPart {
+                uri: uri.clone(),
+                duration: duration.map(|d| d.with_precision(precision)),
+            }
+            .to_string(),
+            Tag::ExtXPartInf {
+                part_target_duration,
+                part_hold_back,
+                part_number,
+            } => Tag::ExtXPartInf {
+                part_target_duration: part_target_duration.with_precision(precision),
+                part_hold_back: *part_hold_back,
+                part_number: *part_number,
+            }
+            .to_string(),
+            Tag::ExtXServerControl {
+                can_skip_until,
+                can_skip_dateranges,
+                hold_back,
+                part_hold_back,
+                can_block_reload,
+            } => Tag::ExtXServerControl {
+                can_skip_until: can_skip_until.map(|d| d.with_precision(precision)),
+                can_skip_dateranges: *can_skip_dateranges,
+                hold_back: hold_back.map(|d| d.with_precision(precision)),
+                part_hold_back: part_hold_back.map(|d| d.with_precision(precision)),
+                can_block_reload: *can_block_reload,
+            }
+            .to_string(),
+            _ => tag.to_string(),
+        }
+    }
+}
@@ -1,3 +1,6 @@
+use crate::m3u8::parser::has_leading_attributes;
+use crate::m3u8::types::{ByteRange, Codecs, DecimalDuration, Resolution};
+
 /// Represents different types of tags found in an M3U8 playlist.
 ///
 /// Each variant corresponds to a specific type of tag defined in the M3U8 specification.
@@ -12,8 +15,13 @@ pub enum Tag {
     //    Media Playlist file.  It applies to the entire Media Playlist file.
     //    It is OPTIONAL.  Its format is:
     ExtXPlaylistType(String),
-    /// Represents a media segment with a duration and an optional title.
-    ExtInf(String, f32, Option<String>),
+    /// Represents a media segment with its URI, duration and an optional title.
+    ///
+    /// The title is everything after the comma following the duration and may
+    /// itself contain commas. IPTV-style attributes written between the duration
+    /// and the comma (`#EXTINF:-1 tvg-id="news",News`) are kept at the start of
+    /// the title (`tvg-id="news",News`) and written back in the same position.
+    ExtInf(String, DecimalDuration, Option<String>),
     /// Indicates the target duration for media segments.
     ExtXTargetDuration(u64),
     /// Specifies the media sequence number.
@@ -33,12 +41,24 @@ pub enum Tag {
     /// Represents a mapping to an initialization segment.
     ExtXMap {
         uri: String,
-        byterange: Option<String>,
+        byterange: Option<ByteRange>,
     },
     /// Specifies the program date and time.
+    ///
+    /// The value is kept as written; it is parsed into a
+    /// [`DateTime`](crate::m3u8::types::DateTime) during validation and by
+    /// `Playlist::program_date_times`.
     ExtXProgramDateTime(String),
     /// Represents a byte range.
-    ExtXByteRange(String),
+    ExtXByteRange(ByteRange),
+    /// An `EXT-X-BYTERANGE` or `EXT-X-MAP` tag whose byte range could not be
+    /// parsed, kept as written so that validation reports it.
+    MalformedByteRange {
+        /// The tag line without the leading `#`.
+        line: String,
+        /// The byte range as written.
+        byte_range: String,
+    },
     /// Defines a custom tag with a specific value.
     ExtXDefine(String),
     /// Represents media information.
@@ -48,6 +68,7 @@ pub enum Tag {
         name: Option<String>,
         uri: Option<String>,
         default: Option<bool>,
+        /// The `AUTOSELECT` attribute.
         autoplay: Option<bool>,
         characteristics: Option<String>,
         language: Option<String>,
@@ -57,9 +78,12 @@ pub enum Tag {
     },
     /// Represents stream information.
     ExtXStreamInf {
+        /// The URI of the variant's media playlist, written on the line after the tag.
+        uri: String,
         bandwidth: u32,
-        codecs: Option<String>,
-        resolution: Option<String>,
+        average_bandwidth: Option<u32>,
+        codecs: Option<Codecs>,
+        resolution: Option<Resolution>,
         frame_rate: Option<f32>,
         audio: Option<String>,
         video: Option<String>,
@@ -69,8 +93,8 @@ pub enum Tag {
     /// Represents an I-frame stream information.
     ExtXIFrameStreamInf {
         bandwidth: u32,
-        codecs: Option<String>,
-        resolution: Option<String>,
+        codecs: Option<Codecs>,
+        resolution: Option<Resolution>,
         frame_rate: Option<f32>,
         uri: String,
     },
@@ -87,44 +111,57 @@ pub enum Tag {
     },
     /// Provides server control information.
     ExtXServerControl {
-        can_play: Option<bool>,
-        can_seek: Option<bool>,
-        can_pause: Option<bool>,
-        min_buffer_time: Option<f32>,
+        /// The `CAN-SKIP-UNTIL` skip boundary.
+        can_skip_until: Option<DecimalDuration>,
+        can_skip_dateranges: Option<bool>,
+        hold_back: Option<DecimalDuration>,
+        part_hold_back: Option<DecimalDuration>,
+        can_block_reload: Option<bool>,
     },
     /// Represents part information.
     ExtXPartInf {
-        part_target_duration: f32,
+        part_target_duration: DecimalDuration,
         part_hold_back: Option<f32>,
         part_number: Option<u64>,
     },
     /// Represents a preload hint.
     ExtXPreloadHint {
+        /// The type of the hinted resource, `PART` or `MAP`.
+        type_: String,
         uri: String,
-        /// Optional byte range for the preload hint.
-        byterange: Option<String>,
+        byterange_start: Option<u64>,
+        byterange_length: Option<u64>,
     },
     /// Represents a rendition report.
-    ExtXRenditionReport { uri: String, bandwidth: u32 },
+    ExtXRenditionReport {
+        uri: String,
+        last_msn: Option<u64>,
+        last_part: Option<u64>,
+    },
     /// Represents a part of a media segment.
     ExtXPart {
         uri: String,
-        duration: Option<f32>,
+        duration: Option<DecimalDuration>,
         // additional fields if necessary
     },
-    /// Indicates a skip in the playlist.
+    /// Replaces the first segments of a playlist delta update.
     ExtXSkip {
-        uri: String,
-        duration: Option<f32>,
-        skipped_segments: u32,
-        reason: Option<String>,
+        /// The number of media segments replaced by the tag.
+        skipped_segments: u64,
+        /// The IDs of the `EXT-X-DATERANGE` tags removed since the last
+        /// playlist, written tab-separated.
+        recently_removed_dateranges: Option<Vec<String>>,
     },
     /// Indicates a discontinuity in the media stream.
     ExtXDiscontinuity,
     /// Represents session data for tracking and metadata.
+    ///
+    /// The data is either carried in `value` or loaded from the JSON file at
+    /// `uri`.
     ExtXSessionData {
         id: String,
-        value: String,
+        value: Option<String>,
+        uri: Option<String>,
         // Optional fields for additional parameters
         language: Option<String>,
     },
@@ -135,6 +172,91 @@ pub enum Tag {
     },
 }
 
+impl Tag {
+    /// Returns the name of the tag as written in a playlist, without the leading `#`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use m3u8_parser::m3u8::tags::Tag;
+    /// assert_eq!(Tag::ExtXVersion(3).name(), "EXT-X-VERSION");
+    /// ```
+    pub fn name(&self) -> &'static str {
+        match self {
+            Tag::ExtM3U => "EXTM3U",
+            Tag::ExtXVersion(_) => "EXT-X-VERSION",
+            Tag::ExtXPlaylistType(_) => "EXT-X-PLAYLIST-TYPE",
+            Tag::ExtInf(..) => "EXTINF",
+            Tag::ExtXTargetDuration(_) => "EXT-X-TARGETDURATION",
+            Tag::ExtXMediaSequence(_) => "EXT-X-MEDIA-SEQUENCE",
+            Tag::ExtXDiscontinuitySequence(_) => "EXT-X-DISCONTINUITY-SEQUENCE",
+            Tag::ExtXEndList => "EXT-X-ENDLIST",
+            Tag::ExtXKey { .. } => "EXT-X-KEY",
+            Tag::ExtXMap { .. } => "EXT-X-MAP",
+            Tag::ExtXProgramDateTime(_) => "EXT-X-PROGRAM-DATE-TIME",
+            Tag::ExtXByteRange(_) => "EXT-X-BYTERANGE",
+            Tag::MalformedByteRange { line, .. } if line.starts_with("EXT-X-MAP") => "EXT-X-MAP",
+            Tag::MalformedByteRange { .. } => "EXT-X-BYTERANGE",
+            Tag::ExtXDefine(_) => "EXT-X-DEFINE",
+            Tag::ExtXMedia { .. } => "EXT-X-MEDIA",
+            Tag::ExtXStreamInf { .. } => "EXT-X-STREAM-INF",
+            Tag::ExtXIFrameStreamInf { .. } => "EXT-X-I-FRAME-STREAM-INF",
+            Tag::ExtXGap => "EXT-X-GAP",
+            Tag::ExtXBitrate(_) => "EXT-X-BITRATE",
+            Tag::ExtXIndependentSegments => "EXT-X-INDEPENDENT-SEGMENTS",
+            Tag::ExtXStart { .. } => "EXT-X-START",
+            Tag::ExtXServerControl { .. } => "EXT-X-SERVER-CONTROL",
+            Tag::ExtXPartInf { .. } => "EXT-X-PART-INF",
+            Tag::ExtXPreloadHint { .. } => "EXT-X-PRELOAD-HINT",
+            Tag::ExtXRenditionReport { .. } => "EXT-X-RENDITION-REPORT",
+            Tag::ExtXPart { .. } => "EXT-X-PART",
+            Tag::ExtXSkip { .. } => "EXT-X-SKIP",
+            Tag::ExtXDiscontinuity => "EXT-X-DISCONTINUITY",
+            Tag::ExtXSessionData { .. } => "EXT-X-SESSION-DATA",
+            Tag::ExtXSessionKey { .. } => "EXT-X-SESSION-KEY",
+        }
+    }
+
+    /// Returns `true` if the tag may only appear in a master playlist.
+    pub fn is_master_tag(&self) -> bool {
+        matches!(
+            self,
+            Tag::ExtXMedia { .. }
+                | Tag::ExtXStreamInf { .. }
+                | Tag::ExtXIFrameStreamInf { .. }
+                | Tag::ExtXSessionData { .. }
+                | Tag::ExtXSessionKey { .. }
+        )
+    }
+
+    /// Returns `true` if the tag may only appear in a media playlist.
+    pub fn is_media_tag(&self) -> bool {
+        matches!(
+            self,
+            Tag::ExtInf(..)
+                | Tag::ExtXTargetDuration(_)
+                | Tag::ExtXMediaSequence(_)
+                | Tag::ExtXDiscontinuitySequence(_)
+                | Tag::ExtXEndList
+                | Tag::ExtXPlaylistType(_)
+                | Tag::ExtXKey { .. }
+                | Tag::ExtXMap { .. }
+                | Tag::ExtXProgramDateTime(_)
+                | Tag::ExtXByteRange(_)
+                | Tag::MalformedByteRange { .. }
+                | Tag::ExtXDiscontinuity
+                | Tag::ExtXGap
+                | Tag::ExtXBitrate(_)
+                | Tag::ExtXPart { .. }
+                | Tag::ExtXPartInf { .. }
+                | Tag::ExtXPreloadHint { .. }
+                | Tag::ExtXRenditionReport { .. }
+                | Tag::ExtXSkip { .. }
+                | Tag::ExtXServerControl { .. }
+        )
+    }
+}
+
 impl std::fmt::Display for Tag {
     /// Formats the tag as a string for output.
     ///
@@ -162,12 +284,13 @@ impl std::fmt::Display for Tag {
             Tag::ExtM3U => write!(f, "#EXTM3U"),
             Tag::ExtXVersion(version) => write!(f, "#EXT-X-VERSION:{}", version),
             Tag::ExtInf(url, duration, title) => {
-                if let Some(title) = title {
-                    // Format with 3 decimal places
-                    write!(f, "#EXTINF:{:.4},{}\n {}", duration, title, url)
-                } else {
-                    // Format with 3 decimal places
-                    write!(f, "#EXTINF:{:.4},\n{}", duration, url)
+                // Durations keep the precision they were written with
+                match title {
+                    Some(title) if has_leading_attributes(title) => {
+                        write!(f, "#EXTINF:{} {}\n{}", duration, title, url)
+                    }
+                    Some(title) => write!(f, "#EXTINF:{},{}\n{}", duration, title, url),
+                    None => write!(f, "#EXTINF:{},\n{}", duration, url),
                 }
             }
             Tag::ExtXTargetDuration(duration) => {
@@ -205,7 +328,7 @@ impl std::fmt::Display for Tag {
             Tag::ExtXMap { uri, byterange } => {
                 write!(f, "#EXT-X-MAP:URI=\"{}\"", uri)?;
                 if let Some(byterange) = byterange {
-                    write!(f, ",BYTERANGE={}", byterange)?;
+                    write!(f, ",BYTERANGE=\"{}\"", byterange)?;
                 }
                 Ok(())
             }
@@ -215,6 +338,7 @@ impl std::fmt::Display for Tag {
             Tag::ExtXByteRange(byterange) => {
                 write!(f, "#EXT-X-BYTERANGE:{}", byterange)
             }
+            Tag::MalformedByteRange { line, .. } => write!(f, "#{}", line),
             Tag::ExtXDefine(value) => {
                 write!(f, "#EXT-X-DEFINE:{}", value)
             }
@@ -249,9 +373,9 @@ impl std::fmt::Display for Tag {
                     write!(f, ",DEFAULT={}", if *default { "YES" } else { "NO" })?;
                 }
 
-                // Optional autoplay field
+                // Optional autoplay field, written as AUTOSELECT
                 if let Some(autoplay) = autoplay {
-                    write!(f, ",AUTOPLAY={}", if *autoplay { "YES" } else { "NO" })?;
+                    write!(f, ",AUTOSELECT={}", if *autoplay { "YES" } else { "NO" })?;
                 }
 
                 // Optional forced field
@@ -266,7 +390,7 @@ impl std::fmt::Display for Tag {
 
                 // Optional characteristics field
                 if let Some(characteristics) = characteristics {
-                    write!(f, ",CHARACTERISTICS={}", characteristics)?;
+                    write!(f, ",CHARACTERISTICS=\"{}\"", characteristics)?;
                 }
 
                 // Optional language field
@@ -282,7 +406,9 @@ impl std::fmt::Display for Tag {
                 Ok(())
             }
             Tag::ExtXStreamInf {
+                uri,
                 bandwidth,
+                average_bandwidth,
                 codecs,
                 resolution,
                 frame_rate,
@@ -292,6 +418,9 @@ impl std::fmt::Display for Tag {
                 closed_captions,
             } => {
                 write!(f, "#EXT-X-STREAM-INF:BANDWIDTH={}", bandwidth)?;
+                if let Some(average_bandwidth) = average_bandwidth {
+                    write!(f, ",AVERAGE-BANDWIDTH={}", average_bandwidth)?;
+                }
                 if let Some(codecs) = codecs {
                     write!(f, ",CODECS=\"{}\"", codecs)?;
                 }
@@ -310,10 +439,13 @@ impl std::fmt::Display for Tag {
                 if let Some(subtitle) = subtitle {
                     write!(f, ",SUBTITLES=\"{}\"", subtitle)?;
                 }
-                if let Some(closed_captions) = closed_captions {
-                    write!(f, ",CLOSED-CAPTIONS=\"{}\"", closed_captions)?;
+                match closed_captions.as_deref() {
+                    // NONE is an enumerated-string rather than a quoted GROUP-ID
+                    Some("NONE") => write!(f, ",CLOSED-CAPTIONS=NONE")?,
+                    Some(closed_captions) => write!(f, ",CLOSED-CAPTIONS=\"{}\"", closed_captions)?,
+                    None => {}
                 }
-                Ok(())
+                write!(f, "\n{}", uri)
             }
             Tag::ExtXIFrameStreamInf {
                 bandwidth,
@@ -351,25 +483,33 @@ impl std::fmt::Display for Tag {
                 Ok(())
             }
             Tag::ExtXServerControl {
-                can_play,
-                can_seek,
-                can_pause,
-                min_buffer_time,
+                can_skip_until,
+                can_skip_dateranges,
+                hold_back,
+                part_hold_back,
+                can_block_reload,
             } => {
-                write!(f, "#EXT-X-SERVER-CONTROL")?;
-                if let Some(can_play) = can_play {
-                    write!(f, ",CAN-PLAY={}", if *can_play { "YES" } else { "NO" })?;
+                let yes_no = |value: bool| if value { "YES" } else { "NO" };
+                let mut attributes = Vec::new();
+                if let Some(can_skip_until) = can_skip_until {
+                    attributes.push(format!("CAN-SKIP-UNTIL={}", can_skip_until));
                 }
-                if let Some(can_seek) = can_seek {
-                    write!(f, ",CAN-SEEK={}", if *can_seek { "YES" } else { "NO" })?;
+                if let Some(can_skip_dateranges) = can_skip_dateranges {
+                    attributes.push(format!(
+                        "CAN-SKIP-DATERANGES={}",
+                        yes_no(*can_skip_dateranges)
+                    ));
                 }
-                if let Some(can_pause) = can_pause {
-                    write!(f, ",CAN-PAUSE={}", if *can_pause { "YES" } else { "NO" })?;
+                if let Some(hold_back) = hold_back {
+                    attributes.push(format!("HOLD-BACK={}", hold_back));
                 }
-                if let Some(min_buffer_time) = min_buffer_time {
-                    write!(f, ",MIN-BUFFER-TIME={}", min_buffer_time)?;
+                if let Some(part_hold_back) = part_hold_back {
+                    attributes.push(format!("PART-HOLD-BACK={}", part_hold_back));
                 }
-                Ok(())
+                if let Some(can_block_reload) = can_block_reload {
+                    attributes.push(format!("CAN-BLOCK-RELOAD={}", yes_no(*can_block_reload)));
+                }
+                write!(f, "#EXT-X-SERVER-CONTROL:{}", attributes.join(","))
             }
             Tag::ExtXPartInf {
                 part_target_duration,
@@ -385,19 +525,34 @@ impl std::fmt::Display for Tag {
                 }
                 Ok(())
             }
-            Tag::ExtXPreloadHint { uri, byterange } => {
-                let mut result = format!("#EXT-X-PRELOAD-HINT:URI=\"{}\"", uri);
-                if let Some(byterange) = byterange {
-                    result.push_str(&format!(",BYTERANGE={}", byterange));
+            Tag::ExtXPreloadHint {
+                type_,
+                uri,
+                byterange_start,
+                byterange_length,
+            } => {
+                write!(f, "#EXT-X-PRELOAD-HINT:TYPE={},URI=\"{}\"", type_, uri)?;
+                if let Some(byterange_start) = byterange_start {
+                    write!(f, ",BYTERANGE-START={}", byterange_start)?;
                 }
-                write!(f, "{}", result)
+                if let Some(byterange_length) = byterange_length {
+                    write!(f, ",BYTERANGE-LENGTH={}", byterange_length)?;
+                }
+                Ok(())
             }
-            Tag::ExtXRenditionReport { uri, bandwidth } => {
-                write!(
-                    f,
-                    "#EXT-X-RENDITION-REPORT:URI=\"{}\",BANDWIDTH={}",
-                    uri, bandwidth
-                )
+            Tag::ExtXRenditionReport {
+                uri,
+                last_msn,
+                last_part,
+            } => {
+                write!(f, "#EXT-X-RENDITION-REPORT:URI=\"{}\"", uri)?;
+                if let Some(last_msn) = last_msn {
+                    write!(f, ",LAST-MSN={}", last_msn)?;
+                }
+                if let Some(last_part) = last_part {
+                    write!(f, ",LAST-PART={}", last_part)?;
+                }
+                Ok(())
             }
             Tag::ExtXPart { uri, duration } => {
                 write!(f, "#EXT-X-PART:URI=\"{}\"", uri)?;
@@ -407,33 +562,29 @@ impl std::fmt::Display for Tag {
                 Ok(())
             }
             Tag::ExtXSkip {
-                uri,
-                duration,
                 skipped_segments,
-                reason,
+                recently_removed_dateranges,
             } => {
-                let mut output = format!(
-                    "#EXT-X-SKIP:URI=\"{}\",SKIPPED-SEGMENTS={}",
-                    uri, skipped_segments
-                );
-
-                if let Some(duration) = duration {
-                    output.push_str(&format!(",DURATION={}", duration));
-                }
-
-                if let Some(reason) = reason {
-                    output.push_str(&format!(",REASON=\"{}\"", reason));
+                write!(f, "#EXT-X-SKIP:SKIPPED-SEGMENTS={}", skipped_segments)?;
+                if let Some(ids) = recently_removed_dateranges {
+                    write!(f, ",RECENTLY-REMOVED-DATERANGES=\"{}\"", ids.join("\t"))?;
                 }
-
-                write!(f, "{}", output)
+                Ok(())
             }
             Tag::ExtXDiscontinuity => write!(f, "#EXT-X-DISCONTINUITY"),
             Tag::ExtXSessionData {
                 id,
                 value,
+                uri,
                 language,
             } => {
-                write!(f, "#EXT-X-SESSION-DATA:ID=\"{}\",VALUE=\"{}\"", id, value)?;
+                write!(f, "#EXT-X-SESSION-DATA:ID=\"{}\"", id)?;
+                if let Some(value) = value {
+                    write!(f, ",VALUE=\"{}\"", value)?;
+                }
+                if let Some(uri) = uri {
+                    write!(f, ",URI=\"{}\"", uri)?;
+                }
                 if let Some(language) = language {
                     write!(f, ",LANGUAGE=\"{}\"", language)?;
                 }
@@ -0,0 +1,135 @@
+#[cfg(test)]
+mod tests {
+    use crate::m3u8::playlist::Playlist;
+    use crate::m3u8::tags::Tag;
+
+    fn parse_segment(text: &str) -> Tag {
+        let playlist = Playlist::from_reader(format!("#EXTM3U\n{}\n", text).as_bytes()).unwrap();
+        assert_eq!(playlist.tags.len(), 2, "{}", text);
+        playlist.tags[1].clone()
+    }
+
+    fn extinf(uri: &str, duration: &str, title: Option<&str>) -> Tag {
+        Tag::ExtInf(
+            uri.to_string(),
+            duration.parse().unwrap(),
+            title.map(|t| t.to_string()),
+        )
+    }
+
+    #[test]
+    fn test_parse_extinf_titles() {
+        let cases = [
+            ("#EXTINF:10,\nfirst.ts", extinf("first.ts", "10", None)),
+            ("#EXTINF:10\nfirst.ts", extinf("first.ts", "10", None)),
+            (
+                "#EXTINF:10.0,Title\nfirst.ts",
+                extinf("first.ts", "10.0", Some("Title")),
+            ),
+            (
+                "#EXTINF:10.0,Hello, World, again\nfirst.ts",
+                extinf("first.ts", "10.0", Some("Hello, World, again")),
+            ),
+            (
+                "#EXTINF:10.0,Episode #1\nhttps://media.example.com/first.ts#t=10",
+                extinf(
+                    "https://media.example.com/first.ts#t=10",
+                    "10.0",
+                    Some("Episode #1"),
+                ),
+            ),
+            (
+                "#EXTINF:-1 tvg-id=\"news.us\" tvg-name=\"News, US\" group-title=\"News\",US News\nhttp://iptv.example.com/news",
+                extinf(
+                    "http://iptv.example.com/news",
+                    "-1",
+                    Some("tvg-id=\"news.us\" tvg-name=\"News, US\" group-title=\"News\",US News"),
+                ),
+            ),
+            (
+                "#EXTINF:5.005,  padded title  \r\n  first.ts  ",
+                extinf("first.ts", "5.005", Some("padded title")),
+            ),
+        ];
+
+        for (text, expected) in cases {
+            assert_eq!(parse_segment(text), expected, "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_write_extinf_titles() {
+        let cases = [
+            (extinf("first.ts", "10", None), "#EXTINF:10,\nfirst.ts"),
+            (
+                extinf("first.ts", "10.0", Some("Hello, World")),
+                "#EXTINF:10.0,Hello, World\nfirst.ts",
+            ),
+            (
+                extinf(
+                    "news",
+                    "-1",
+                    Some("tvg-id=\"news\" group-title=\"News\",News"),
+                ),
+                "#EXTINF:-1 tvg-id=\"news\" group-title=\"News\",News\nnews",
+            ),
+            (
+                extinf("first.ts", "4", Some("tvg-id=\"unterminated,Title")),
+                "#EXTINF:4,tvg-id=\"unterminated,Title\nfirst.ts",
+            ),
+        ];
+
+        for (tag, expected) in cases {
+            assert_eq!(tag.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_extinf_round_trip() {
+        let titles = [
+            None,
+            Some("Title"),
+            Some("Title, with, commas"),
+            Some(",leading comma"),
+            Some("trailing comma,"),
+            Some("Episode #1: \"Quoted\""),
+            Some("tvg-id=\"a\",Name"),
+            Some("tvg-id=\"a\" tvg-logo=\"http://logo.example.com/a,b.png\",Name, Extra"),
+            Some("key=\"value\" not an attribute,Name"),
+            Some("émission spéciale"),
+        ];
+        let durations = ["10", "9.97663", "0.5", "-1"];
+
+        for title in titles {
+            for duration in durations {
+                let tag = extinf("https://media.example.com/segment.ts", duration, title);
+                let text = tag.to_string();
+                let parsed = parse_segment(&text);
+                assert_eq!(parsed, tag, "{}", text);
+                assert_eq!(parsed.to_string(), text);
+            }
+        }
+    }
+
+    #[test]
+    fn test_extinf_playlist_text_round_trip() {
+        let data = "#EXTM3U
+#EXTINF:-1 tvg-id=\"one\" group-title=\"Movies\",Movie, The
+http://iptv.example.com/one
+#EXTINF:-1,
+http://iptv.example.com/two
+#EXTINF:120.5,Live: Part #2
+http://iptv.example.com/three
+";
+
+        let playlist = Playlist::from_reader(data.as_bytes()).unwrap();
+        assert_eq!(playlist.tags.len(), 4);
+        assert_eq!(playlist.to_string(), data);
+    }
+
+    #[test]
+    fn test_extinf_without_uri_is_an_error() {
+        assert!(Playlist::from_reader("#EXTM3U\n#EXTINF:10,Title\n".as_bytes()).is_err());
+        assert!(Playlist::from_reader("#EXTM3U\n#EXTINF:ten,\nfirst.ts\n".as_bytes()).is_err());
+    }
+}
@@ -1,9 +1,15 @@
 #[cfg(test)]
 mod tests {
     use crate::m3u8::playlist::builder::PlaylistBuilder;
+    use crate::m3u8::playlist::diff::{Change, SegmentSummary};
+    use crate::m3u8::playlist::live::{LivePlaylistWindow, LiveSegment, WindowLimit};
+    use crate::m3u8::playlist::slice::SliceMode;
+    use crate::m3u8::playlist::uri::{resolve, UriKind};
+    use crate::m3u8::playlist::writer::{LineEnding, PlaylistWriter};
     use crate::m3u8::playlist::Playlist;
     use crate::m3u8::tags::Tag;
-    use crate::m3u8::validation::ValidationError;
+    use crate::m3u8::types::{ByteRange, DateTime, DecimalDuration, Resolution};
+    use crate::m3u8::validation::{Profile, ValidationError, Validator};
     use std::io::Write;
 
     #[test]
@@ -30,17 +36,17 @@ https://media.example.com/third.ts
                 Tag::ExtXTargetDuration(10),
                 Tag::ExtInf(
                     "https://media.example.com/first.ts".to_string(),
-                    5.005,
+                    "5.005".parse().unwrap(),
                     None
                 ),
                 Tag::ExtInf(
                     "https://media.example.com/second.ts".to_string(),
-                    5.005,
+                    "5.005".parse().unwrap(),
                     None
                 ),
                 Tag::ExtInf(
                     "https://media.example.com/third.ts".to_string(),
-                    3.003,
+                    "3.003".parse().unwrap(),
                     None
                 ),
                 Tag::ExtXEndList,
@@ -57,17 +63,17 @@ https://media.example.com/third.ts
                 Tag::ExtXTargetDuration(10),
                 Tag::ExtInf(
                     "https://media.example.com/first.ts".to_string(),
-                    5.005,
+                    "5.005".parse().unwrap(),
                     None,
                 ),
                 Tag::ExtInf(
                     "https://media.example.com/second.ts".to_string(),
-                    5.005,
+                    "5.005".parse().unwrap(),
                     None,
                 ),
                 Tag::ExtInf(
                     "https://media.example.com/third.ts".to_string(),
-                    3.003,
+                    "3.003".parse().unwrap(),
                     None,
                 ),
                 Tag::ExtXEndList,
@@ -83,11 +89,11 @@ https://media.example.com/third.ts
         let expected = r#"#EXTM3U
 #EXT-X-VERSION:7
 #EXT-X-TARGETDURATION:10
-#EXTINF:5.0050,
+#EXTINF:5.005,
 https://media.example.com/first.ts
-#EXTINF:5.0050,
+#EXTINF:5.005,
 https://media.example.com/second.ts
-#EXTINF:3.0030,
+#EXTINF:3.003,
 https://media.example.com/third.ts
 #EXT-X-ENDLIST
 "#;
@@ -127,17 +133,17 @@ https://media.example.com/third.ts
                 },
                 Tag::ExtInf(
                     "https://media.example.com/first.ts".to_string(),
-                    5.005,
+                    "5.005".parse().unwrap(),
                     None
                 ),
                 Tag::ExtInf(
                     "https://media.example.com/second.ts".to_string(),
-                    5.005,
+                    "5.005".parse().unwrap(),
                     None
                 ),
                 Tag::ExtInf(
                     "https://media.example.com/third.ts".to_string(),
-                    3.003,
+                    "3.003".parse().unwrap(),
                     None
                 ),
                 Tag::ExtXEndList,
@@ -161,17 +167,17 @@ https://media.example.com/third.ts
                 },
                 Tag::ExtInf(
                     "https://media.example.com/first.ts".to_string(),
-                    5.005,
+                    "5.005".parse().unwrap(),
                     None,
                 ),
                 Tag::ExtInf(
                     "https://media.example.com/second.ts".to_string(),
-                    5.005,
+                    "5.005".parse().unwrap(),
                     None,
                 ),
                 Tag::ExtInf(
                     "https://media.example.com/third.ts".to_string(),
-                    3.003,
+                    "3.003".parse().unwrap(),
                     None,
                 ),
                 Tag::ExtXEndList,
@@ -188,11 +194,11 @@ https://media.example.com/third.ts
 #EXT-X-VERSION:7
 #EXT-X-TARGETDURATION:10
 #EXT-X-KEY:METHOD=AES-128,URI="https://priv.example.com/key.php?r=52"
-#EXTINF:5.0050,
+#EXTINF:5.005,
 https://media.example.com/first.ts
-#EXTINF:5.0050,
+#EXTINF:5.005,
 https://media.example.com/second.ts
-#EXTINF:3.0030,
+#EXTINF:3.003,
 https://media.example.com/third.ts
 #EXT-X-ENDLIST
 "#;
@@ -229,17 +235,17 @@ https://media.example.com/third.ts
                 },
                 Tag::ExtInf(
                     "https://media.example.com/first.ts".to_string(),
-                    5.005,
+                    "5.005".parse().unwrap(),
                     None
                 ),
                 Tag::ExtInf(
                     "https://media.example.com/second.ts".to_string(),
-                    5.005,
+                    "5.005".parse().unwrap(),
                     None
                 ),
                 Tag::ExtInf(
                     "https://media.example.com/third.ts".to_string(),
-                    3.003,
+                    "3.003".parse().unwrap(),
                     None
                 ),
                 Tag::ExtXEndList,
@@ -260,17 +266,17 @@ https://media.example.com/third.ts
                 },
                 Tag::ExtInf(
                     "https://media.example.com/first.ts".to_string(),
-                    5.005,
+                    "5.005".parse().unwrap(),
                     None,
                 ),
                 Tag::ExtInf(
                     "https://media.example.com/second.ts".to_string(),
-                    5.005,
+                    "5.005".parse().unwrap(),
                     None,
                 ),
                 Tag::ExtInf(
                     "https://media.example.com/third.ts".to_string(),
-                    3.003,
+                    "3.003".parse().unwrap(),
                     None,
                 ),
                 Tag::ExtXEndList,
@@ -287,11 +293,11 @@ https://media.example.com/third.ts
 #EXT-X-VERSION:6
 #EXT-X-TARGETDURATION:10
 #EXT-X-MAP:URI="init.mp4"
-#EXTINF:5.0050,
+#EXTINF:5.005,
 https://media.example.com/first.ts
-#EXTINF:5.0050,
+#EXTINF:5.005,
 https://media.example.com/second.ts
-#EXTINF:3.0030,
+#EXTINF:3.003,
 https://media.example.com/third.ts
 #EXT-X-ENDLIST
 "#;
@@ -325,17 +331,17 @@ https://media.example.com/third.ts
                 Tag::ExtXProgramDateTime("2020-01-01T00:00:00Z".to_string()),
                 Tag::ExtInf(
                     "https://media.example.com/first.ts".to_string(),
-                    5.005,
+                    "5.005".parse().unwrap(),
                     None
                 ),
                 Tag::ExtInf(
                     "https://media.example.com/second.ts".to_string(),
-                    5.005,
+                    "5.005".parse().unwrap(),
                     None
                 ),
                 Tag::ExtInf(
                     "https://media.example.com/third.ts".to_string(),
-                    3.003,
+                    "3.003".parse().unwrap(),
                     None
                 ),
                 Tag::ExtXEndList,
@@ -353,17 +359,17 @@ https://media.example.com/third.ts
                 Tag::ExtXProgramDateTime("2020-01-01T00:00:00Z".to_string()),
                 Tag::ExtInf(
                     "https://media.example.com/first.ts".to_string(),
-                    5.005,
+                    "5.005".parse().unwrap(),
                     None,
                 ),
                 Tag::ExtInf(
                     "https://media.example.com/second.ts".to_string(),
-                    5.005,
+                    "5.005".parse().unwrap(),
                     None,
                 ),
                 Tag::ExtInf(
                     "https://media.example.com/third.ts".to_string(),
-                    3.003,
+                    "3.003".parse().unwrap(),
                     None,
                 ),
                 Tag::ExtXEndList,
@@ -380,11 +386,11 @@ https://media.example.com/third.ts
 #EXT-X-VERSION:7
 #EXT-X-TARGETDURATION:10
 #EXT-X-PROGRAM-DATE-TIME:2020-01-01T00:00:00Z
-#EXTINF:5.0050,
+#EXTINF:5.005,
 https://media.example.com/first.ts
-#EXTINF:5.0050,
+#EXTINF:5.005,
 https://media.example.com/second.ts
-#EXTINF:3.0030,
+#EXTINF:3.003,
 https://media.example.com/third.ts
 #EXT-X-ENDLIST
 "#;
@@ -398,11 +404,11 @@ https://media.example.com/third.ts
 #EXTM3U
 #EXT-X-VERSION:7
 #EXT-X-TARGETDURATION:10
-#EXTINF:5.0050,
+#EXTINF:5.005,
 https://media.example.com/first.ts
-#EXTINF:5.0050,
+#EXTINF:5.005,
 https://media.example.com/second.ts
-#EXTINF:3.0030,
+#EXTINF:3.003,
 https://media.example.com/third.ts
 #EXT-X-ENDLIST
 "#;
@@ -416,17 +422,17 @@ https://media.example.com/third.ts
                 Tag::ExtXTargetDuration(10),
                 Tag::ExtInf(
                     "https://media.example.com/first.ts".to_string(),
-                    5.005,
+                    "5.005".parse().unwrap(),
                     None
                 ),
                 Tag::ExtInf(
                     "https://media.example.com/second.ts".to_string(),
-                    5.005,
+                    "5.005".parse().unwrap(),
                     None
                 ),
                 Tag::ExtInf(
                     "https://media.example.com/third.ts".to_string(),
-                    3.003,
+                    "3.003".parse().unwrap(),
                     None
                 ),
                 Tag::ExtXEndList,
@@ -455,17 +461,17 @@ https://media.example.com/third.ts
                 Tag::ExtXTargetDuration(10),
                 Tag::ExtInf(
                     "https://media.example.com/first.ts".to_string(),
-                    5.005,
+                    "5.005".parse().unwrap(),
                     None
                 ),
                 Tag::ExtInf(
                     "https://media.example.com/second.ts".to_string(),
-                    5.005,
+                    "5.005".parse().unwrap(),
                     None
                 ),
                 Tag::ExtInf(
                     "https://media.example.com/third.ts".to_string(),
-                    3.003,
+                    "3.003".parse().unwrap(),
                     None
                 ),
                 Tag::ExtXEndList,
@@ -481,11 +487,11 @@ https://media.example.com/third.ts
         let expected = "#EXTM3U
 #EXT-X-VERSION:7
 #EXT-X-TARGETDURATION:10
-#EXTINF:5.0050,
+#EXTINF:5.005,
 https://media.example.com/first.ts
-#EXTINF:5.0050,
+#EXTINF:5.005,
 https://media.example.com/second.ts
-#EXTINF:3.0030,
+#EXTINF:3.003,
 https://media.example.com/third.ts
 #EXT-X-ENDLIST
 ";
@@ -526,7 +532,7 @@ https://media.example.com/third.ts
     fn test_validate_playlist_invalid_version() {
         let playlist = PlaylistBuilder::new()
             .extm3u()
-            .version(8) // Invalid version
+            .version(13) // Invalid version
             .target_duration(10)
             .extinf("https://media.example.com/first.ts", 5.005, None)
             .extinf("https://media.example.com/second.ts", 5.005, None)
@@ -534,7 +540,16 @@ https://media.example.com/third.ts
             .end_list()
             .build();
 
-        assert_eq!(playlist, Err(vec![ValidationError::InvalidVersion(8)]));
+        assert_eq!(playlist, Err(vec![ValidationError::InvalidVersion(13)]));
+
+        let playlist = PlaylistBuilder::new()
+            .extm3u()
+            .version(12)
+            .target_duration(10)
+            .extinf("https://media.example.com/first.ts", 5.005, None)
+            .end_list()
+            .build();
+        assert!(playlist.is_ok());
     }
 
     #[test]
@@ -551,7 +566,7 @@ https://media.example.com/third.ts
 
         assert_eq!(
             playlist,
-            Err(vec![ValidationError::InvalidDuration(-5.005)])
+            Err(vec![ValidationError::InvalidDuration((-5.005).into())])
         );
     }
 
@@ -604,7 +619,7 @@ https://media.example.com/third.ts
     fn test_validate_playlist_invalid_map_uri() {
         let playlist = PlaylistBuilder::new()
             .extm3u()
-            .version(3)
+            .version(6)
             .target_duration(10)
             .map("", None) // Invalid map URI
             .extinf("https://media.example.com/first.ts", 5.005, None)
@@ -631,4 +646,1190 @@ https://media.example.com/third.ts
 
         assert_eq!(playlist, Err(vec![ValidationError::InvalidProgramDateTime]));
     }
+
+    #[test]
+    fn test_validate_playlist_malformed_program_date_time() {
+        let playlist = PlaylistBuilder::new()
+            .extm3u()
+            .version(3)
+            .target_duration(10)
+            .program_date_time("2020-01-01 00:00:00") // Not ISO 8601
+            .extinf("https://media.example.com/first.ts", 5.005, None)
+            .end_list()
+            .build();
+
+        assert_eq!(playlist, Err(vec![ValidationError::InvalidProgramDateTime]));
+    }
+
+    #[test]
+    fn test_program_date_times() {
+        let data = r#"
+#EXTM3U
+#EXT-X-VERSION:3
+#EXT-X-TARGETDURATION:10
+#EXTINF:4.0,
+https://media.example.com/zero.ts
+#EXT-X-PROGRAM-DATE-TIME:2020-01-01T00:00:00.000+01:00
+#EXTINF:5.005,
+https://media.example.com/first.ts
+#EXTINF:5.005,
+https://media.example.com/second.ts
+#EXT-X-DISCONTINUITY
+#EXTINF:3.003,
+https://media.example.com/third.ts
+#EXT-X-DISCONTINUITY
+#EXT-X-PROGRAM-DATE-TIME:2020-01-01T00:10:00Z
+#EXTINF:3.003,
+https://media.example.com/fourth.ts
+#EXTINF:3.003,
+https://media.example.com/fifth.ts
+#EXT-X-ENDLIST
+"#;
+
+        let playlist = Playlist::from_reader(data.as_bytes()).unwrap();
+        let date_times: Vec<Option<String>> = playlist
+            .program_date_times()
+            .iter()
+            .map(|date_time| date_time.map(|d| d.to_string()))
+            .collect();
+
+        assert_eq!(
+            date_times,
+            vec![
+                None,
+                Some("2020-01-01T00:00:00.000+01:00".to_string()),
+                Some("2020-01-01T00:00:05.005+01:00".to_string()),
+                None,
+                Some("2020-01-01T00:10:00Z".to_string()),
+                Some("2020-01-01T00:10:03.003Z".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_write_playlist_to_writer_and_string() {
+        let data = "#EXTM3U
+#EXT-X-VERSION:3
+#EXT-X-TARGETDURATION:10
+#EXTINF:5.005,
+https://media.example.com/first.ts
+#EXTINF:3.003,
+https://media.example.com/second.ts
+#EXT-X-ENDLIST
+";
+        let playlist = Playlist::from_reader(data.as_bytes()).unwrap();
+
+        let mut output = Vec::new();
+        playlist.write_to(&mut output).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), data);
+        assert_eq!(playlist.to_string(), data);
+    }
+
+    #[test]
+    fn test_playlist_writer_options() {
+        let playlist = PlaylistBuilder::new()
+            .extm3u()
+            .version(3)
+            .target_duration(10)
+            .extinf("https://media.example.com/first.ts", 5.005, None)
+            .extinf("https://media.example.com/second.ts", 3.5, None)
+            .build()
+            .unwrap();
+
+        let output = PlaylistWriter::new()
+            .line_ending(LineEnding::CrLf)
+            .float_precision(2)
+            .trailing_newline(false)
+            .write_to_string(&playlist);
+
+        assert_eq!(
+            output,
+            "#EXTM3U\r\n#EXT-X-VERSION:3\r\n#EXT-X-TARGETDURATION:10\r\n#EXTINF:5.01,\r\nhttps://media.example.com/first.ts\r\n#EXTINF:3.50,\r\nhttps://media.example.com/second.ts"
+        );
+
+        let low_latency = Playlist::from_reader(
+            "#EXTM3U\n#EXT-X-TARGETDURATION:4\n\
+             #EXT-X-SERVER-CONTROL:CAN-SKIP-UNTIL=24,HOLD-BACK=12.5,PART-HOLD-BACK=3.012\n\
+             #EXT-X-PART-INF:PART-TARGET=1.004\n"
+                .as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(
+            PlaylistWriter::new()
+                .float_precision(1)
+                .write_to_string(&low_latency),
+            "#EXTM3U\n#EXT-X-TARGETDURATION:4\n\
+             #EXT-X-SERVER-CONTROL:CAN-SKIP-UNTIL=24.0,HOLD-BACK=12.5,PART-HOLD-BACK=3.0\n\
+             #EXT-X-PART-INF:PART-TARGET=1.0\n"
+        );
+        assert_eq!(
+            PlaylistWriter::new().write_to_string(&Playlist::new(vec![])),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_repair_playlist() {
+        let data = "\
+#EXT-X-VERSION:2
+#EXT-X-TARGETDURATION:4
+#EXT-X-MEDIA-SEQUENCE:0
+#EXT-X-MAP:URI=init.mp4
+#EXTINF:4.5,
+segment0.m4s
+#EXTINF:4,
+segment1.m4s
+#EXT-X-ENDLIST
+#EXT-X-ENDLIST
+";
+        let (playlist, report) = Playlist::from_reader_repaired(data.as_bytes()).unwrap();
+
+        let errors: Vec<&ValidationError> =
+            report.repairs.iter().map(|repair| &repair.error).collect();
+        assert_eq!(
+            errors,
+            vec![
+                &ValidationError::UnquotedAttribute {
+                    tag: "EXT-X-MAP".to_string(),
+                    attribute: "URI".to_string(),
+                },
+                &ValidationError::MissingExtM3U,
+                &ValidationError::VersionTooLow {
+                    declared: 2,
+                    required: 6,
+                    feature: "EXT-X-MAP".to_string(),
+                },
+                &ValidationError::DuplicateTag("EXT-X-ENDLIST".to_string()),
+                &ValidationError::SegmentDurationExceedsTarget {
+                    uri: "segment0.m4s".to_string(),
+                    duration: "4.5".parse().unwrap(),
+                    target_duration: 4,
+                },
+            ]
+        );
+        assert_eq!(report.repairs[0].line, Some(4));
+        assert_eq!(report.repairs[2].line, Some(4));
+        assert_eq!(report.repairs[3].line, Some(10));
+        assert_eq!(report.repairs[4].line, Some(5));
+        assert_eq!(
+            report.repairs[4].to_string(),
+            "segment segment0.m4s lasts 4.5s, longer than the target duration of 4s: \
+             set EXT-X-TARGETDURATION to 5 (line 5)"
+        );
+
+        assert_eq!(playlist.validate(), Ok(()));
+        assert_eq!(
+            playlist.to_string(),
+            "#EXTM3U\n#EXT-X-VERSION:6\n#EXT-X-TARGETDURATION:5\n#EXT-X-MEDIA-SEQUENCE:0\n\
+             #EXT-X-MAP:URI=\"init.mp4\"\n#EXTINF:4.5,\nsegment0.m4s\n#EXTINF:4,\nsegment1.m4s\n\
+             #EXT-X-ENDLIST\n"
+        );
+    }
+
+    #[test]
+    fn test_repair_raises_target_duration_once() {
+        let mut playlist = Playlist::new(vec![
+            Tag::ExtM3U,
+            Tag::ExtXTargetDuration(4),
+            Tag::ExtInf("a.ts".to_string(), "5".parse().unwrap(), None),
+            Tag::ExtInf("b.ts".to_string(), "6".parse().unwrap(), None),
+            Tag::ExtInf("c.ts".to_string(), "5".parse().unwrap(), None),
+        ]);
+        let repairs = playlist.repair().repairs;
+        assert_eq!(repairs.len(), 1);
+        assert_eq!(
+            repairs[0].action,
+            "set EXT-X-TARGETDURATION to 6".to_string()
+        );
+        assert_eq!(playlist.tags[1], Tag::ExtXTargetDuration(6));
+        assert_eq!(playlist.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_repair_leaves_unfixable_defects() {
+        let mut playlist = Playlist::new(vec![
+            Tag::ExtM3U,
+            Tag::ExtXTargetDuration(4),
+            Tag::ExtXTargetDuration(6),
+            Tag::ExtInf("a.ts".to_string(), "4".parse().unwrap(), None),
+        ]);
+        assert!(playlist.repair().is_empty());
+        assert_eq!(
+            playlist.validate(),
+            Err(vec![ValidationError::DuplicateTag(
+                "EXT-X-TARGETDURATION".to_string()
+            )])
+        );
+
+        let (_, report) = Playlist::from_reader_repaired(
+            "#EXTM3U\n#EXT-X-STREAM-INF:BANDWIDTH=1000,CODECS=avc1.64001f,mp4a.40.2,CLOSED-CAPTIONS=NONE\nlow.m3u8\n"
+                .as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(report.repairs.len(), 1);
+    }
+
+    #[test]
+    fn test_timeline() {
+        let data = "\
+#EXTM3U
+#EXT-X-VERSION:3
+#EXT-X-TARGETDURATION:6
+#EXT-X-PROGRAM-DATE-TIME:2024-11-05T12:00:00Z
+#EXTINF:6,
+a.ts
+#EXTINF:4.5,
+b.ts
+#EXT-X-DISCONTINUITY
+#EXT-X-PROGRAM-DATE-TIME:2024-11-05T11:00:00Z
+#EXTINF:6,
+c.ts
+#EXT-X-DISCONTINUITY
+#EXTINF:2,
+d.ts
+";
+        let playlist = Playlist::from_reader(data.as_bytes()).unwrap();
+        let timeline = playlist.timeline();
+        let secs = |s: &str| s.parse::<DecimalDuration>().unwrap();
+        let date_time = |s: &str| s.parse::<DateTime>().unwrap();
+
+        let starts: Vec<_> = timeline.entries().iter().map(|entry| entry.start).collect();
+        assert_eq!(
+            starts,
+            vec![secs("0"), secs("6"), secs("10.5"), secs("16.5")]
+        );
+        assert_eq!(timeline.entries()[2].tag_index, 8);
+        assert_eq!(timeline.total_duration(), secs("18.5"));
+
+        assert_eq!(timeline.segment_at(secs("0")), Some(0));
+        assert_eq!(timeline.segment_at(secs("5.999")), Some(0));
+        assert_eq!(timeline.segment_at(secs("6")), Some(1));
+        assert_eq!(timeline.segment_at(secs("18.4")), Some(3));
+        assert_eq!(timeline.segment_at(secs("18.5")), None);
+        assert_eq!(timeline.segment_at(secs("-1")), None);
+
+        assert_eq!(
+            timeline.date_time_at(secs("7")),
+            Some(date_time("2024-11-05T12:00:07Z"))
+        );
+        assert_eq!(
+            timeline.date_time_at(secs("11")),
+            Some(date_time("2024-11-05T11:00:00.5Z"))
+        );
+        // The wall clock is unknown after a discontinuity without a date-time.
+        assert_eq!(timeline.date_time_at(secs("17")), None);
+
+        assert_eq!(
+            timeline.media_time_at(&date_time("2024-11-05T11:00:01Z")),
+            Some(secs("11.5"))
+        );
+        assert_eq!(
+            timeline.media_time_at(&date_time("2024-11-05T12:00:10.5Z")),
+            None
+        );
+        assert_eq!(Playlist::default().timeline().total_duration(), secs("0"));
+    }
+
+    #[test]
+    fn test_slice_playlist() {
+        let data = "\
+#EXTM3U
+#EXT-X-VERSION:6
+#EXT-X-TARGETDURATION:4
+#EXT-X-MEDIA-SEQUENCE:100
+#EXT-X-DISCONTINUITY-SEQUENCE:2
+#EXT-X-PLAYLIST-TYPE:VOD
+#EXT-X-MAP:URI=\"init.mp4\"
+#EXT-X-KEY:METHOD=AES-128,URI=\"key1\"
+#EXT-X-PROGRAM-DATE-TIME:2024-11-05T12:00:00Z
+#EXTINF:4,
+#EXT-X-BYTERANGE:1000@0
+main.mp4
+#EXTINF:4,
+#EXT-X-BYTERANGE:1000
+main.mp4
+#EXT-X-DISCONTINUITY
+#EXTINF:4,
+#EXT-X-BYTERANGE:1000
+main.mp4
+#EXTINF:4,
+#EXT-X-BYTERANGE:1000
+main.mp4
+#EXT-X-ENDLIST
+";
+        let playlist = Playlist::from_reader(data.as_bytes()).unwrap();
+        let secs = |s: &str| s.parse::<DecimalDuration>().unwrap();
+
+        let clip = playlist
+            .slice(secs("5.5")..secs("10"), SliceMode::Precise)
+            .unwrap();
+        assert_eq!(
+            clip.to_string(),
+            "\
+#EXTM3U
+#EXT-X-VERSION:6
+#EXT-X-TARGETDURATION:4
+#EXT-X-PLAYLIST-TYPE:VOD
+#EXT-X-START:TIME-OFFSET=1.5,PRECISE=YES
+#EXT-X-MEDIA-SEQUENCE:101
+#EXT-X-DISCONTINUITY-SEQUENCE:2
+#EXT-X-KEY:METHOD=AES-128,URI=\"key1\"
+#EXT-X-MAP:URI=\"init.mp4\"
+#EXT-X-PROGRAM-DATE-TIME:2024-11-05T12:00:04Z
+#EXT-X-BYTERANGE:1000@1000
+#EXTINF:4,
+main.mp4
+#EXT-X-DISCONTINUITY
+#EXT-X-BYTERANGE:1000
+#EXTINF:4,
+main.mp4
+#EXT-X-ENDLIST
+"
+        );
+        assert_eq!(clip.validate(), Ok(()));
+        assert_eq!(
+            clip.byte_ranges()[1],
+            Some(ByteRange::new(1000, Some(2000)))
+        );
+
+        // Removing a discontinuity increments the discontinuity sequence.
+        let clip = playlist
+            .slice(secs("13")..secs("16"), SliceMode::Snap)
+            .unwrap();
+        assert_eq!(clip.media_sequence(), 103);
+        assert_eq!(clip.discontinuity_sequence(), 3);
+        assert_eq!(clip.segments().len(), 1);
+        assert!(!clip
+            .tags
+            .iter()
+            .any(|tag| matches!(tag, Tag::ExtXStart { .. })));
+
+        let date_time = |s: &str| s.parse::<DateTime>().unwrap();
+        let clip = playlist
+            .slice(
+                date_time("2024-11-05T12:00:00Z")..date_time("2024-11-05T12:00:08Z"),
+                SliceMode::Precise,
+            )
+            .unwrap();
+        assert_eq!(clip.segments().len(), 2);
+        // Segments after a discontinuity without a date-time cannot be addressed
+        // by date-time.
+        assert!(playlist
+            .slice(
+                date_time("2024-11-05T12:00:09Z")..date_time("2024-11-05T12:00:16Z"),
+                SliceMode::Snap
+            )
+            .is_err());
+        assert!(playlist
+            .slice(secs("16")..secs("20"), SliceMode::Snap)
+            .is_err());
+        assert!(playlist
+            .slice(secs("4")..secs("4"), SliceMode::Snap)
+            .is_err());
+    }
+
+    #[test]
+    fn test_concat_playlists() {
+        let parse = |data: &str| Playlist::from_reader(data.as_bytes()).unwrap();
+        let pre_roll = || {
+            parse(
+                "#EXTM3U\n#EXT-X-TARGETDURATION:4\n#EXT-X-INDEPENDENT-SEGMENTS\n\
+                 #EXTINF:4,\npre.ts\n#EXT-X-ENDLIST\n",
+            )
+        };
+        let content = parse(
+            "#EXTM3U\n#EXT-X-VERSION:6\n#EXT-X-TARGETDURATION:6\n#EXT-X-MEDIA-SEQUENCE:7\n\
+             #EXT-X-INDEPENDENT-SEGMENTS\n#EXT-X-MAP:URI=\"init.mp4\"\n\
+             #EXT-X-KEY:METHOD=AES-128,URI=\"key\"\n#EXTINF:6,\nmain0.m4s\n#EXTINF:5.5,\nmain1.m4s\n",
+        );
+        let post_roll = parse(
+            "#EXTM3U\n#EXT-X-TARGETDURATION:4\n#EXT-X-MAP:URI=\"post.mp4\"\n\
+             #EXTINF:4,\npost.m4s\n#EXT-X-ENDLIST\n",
+        );
+
+        let stitched =
+            Playlist::concat(&[pre_roll(), Playlist::default(), content, post_roll]).unwrap();
+        assert_eq!(
+            stitched.to_string(),
+            "\
+#EXTM3U
+#EXT-X-VERSION:6
+#EXT-X-TARGETDURATION:6
+#EXT-X-PLAYLIST-TYPE:VOD
+#EXTINF:4,
+pre.ts
+#EXT-X-DISCONTINUITY
+#EXT-X-MAP:URI=\"init.mp4\"
+#EXT-X-KEY:METHOD=AES-128,URI=\"key\"
+#EXTINF:6,
+main0.m4s
+#EXTINF:5.5,
+main1.m4s
+#EXT-X-DISCONTINUITY
+#EXT-X-KEY:METHOD=NONE
+#EXT-X-MAP:URI=\"post.mp4\"
+#EXTINF:4,
+post.m4s
+#EXT-X-ENDLIST
+"
+        );
+        assert_eq!(stitched.validate(), Ok(()));
+        let reparsed = parse(&stitched.to_string());
+        assert_eq!(reparsed, stitched);
+        assert_eq!(reparsed.to_string(), stitched.to_string());
+
+        let twice = Playlist::concat(&[pre_roll(), pre_roll()]).unwrap();
+        assert!(twice
+            .tags
+            .iter()
+            .any(|tag| matches!(tag, Tag::ExtXIndependentSegments)));
+
+        let fmp4 = parse("#EXTM3U\n#EXT-X-MAP:URI=\"init.mp4\"\n#EXTINF:4,\na.m4s\n");
+        assert!(Playlist::concat(&[fmp4, pre_roll()]).is_err());
+    }
+
+    #[test]
+    fn test_live_playlist_window() {
+        let secs = |s: &str| s.parse::<DecimalDuration>().unwrap();
+        let key = Tag::ExtXKey {
+            method: "AES-128".to_string(),
+            uri: Some("key1".to_string()),
+            iv: None,
+            keyformat: None,
+            keyformatversions: None,
+        };
+        let map = Tag::ExtXMap {
+            uri: "init.mp4".to_string(),
+            byterange: None,
+        };
+
+        let mut window = LivePlaylistWindow::new(4, WindowLimit::Duration(secs("12")));
+        let mut previous = window
+            .push_segment(
+                LiveSegment::new("s0.m4s", secs("4"))
+                    .key(key.clone())
+                    .map(map.clone())
+                    .program_date_time("2024-11-05T12:00:00Z".parse().unwrap()),
+            )
+            .unwrap();
+        for index in 1..6 {
+            let mut segment = LiveSegment::new(&format!("s{}.m4s", index), secs("4"));
+            if index == 2 {
+                segment = segment.discontinuity();
+            }
+            let next = window.push_segment(segment).unwrap();
+            assert_eq!(Playlist::validate_update(&previous, &next), Ok(()));
+            assert_eq!(next.validate(), Ok(()));
+            previous = next;
+        }
+
+        assert_eq!(window.media_sequence(), 3);
+        assert_eq!(window.discontinuity_sequence(), 1);
+        assert_eq!(
+            previous.to_string(),
+            "\
+#EXTM3U
+#EXT-X-VERSION:6
+#EXT-X-TARGETDURATION:4
+#EXT-X-MEDIA-SEQUENCE:3
+#EXT-X-DISCONTINUITY-SEQUENCE:1
+#EXT-X-KEY:METHOD=AES-128,URI=\"key1\"
+#EXT-X-MAP:URI=\"init.mp4\"
+#EXTINF:4,
+s3.m4s
+#EXTINF:4,
+s4.m4s
+#EXTINF:4,
+s5.m4s
+"
+        );
+
+        let finished = window.finish().unwrap();
+        assert!(matches!(finished.tags.last(), Some(Tag::ExtXEndList)));
+        assert!(window
+            .push_segment(LiveSegment::new("s6.m4s", secs("4")))
+            .is_err());
+        assert!(window.playlist().unwrap().tags.contains(&Tag::ExtXEndList));
+    }
+
+    #[test]
+    fn test_live_playlist_window_event_and_parts() {
+        let secs = |s: &str| s.parse::<DecimalDuration>().unwrap();
+        let mut window = LivePlaylistWindow::event(2).part_target(secs("0.5"));
+        for index in 0..8 {
+            for part in 0..4 {
+                window
+                    .push_part(&format!("s{}.{}.m4s", index, part), secs("0.5"))
+                    .unwrap();
+            }
+            window
+                .push_segment(LiveSegment::new(&format!("s{}.m4s", index), secs("2")))
+                .unwrap();
+        }
+        let playlist = window.push_part("s8.0.m4s", secs("0.5")).unwrap();
+
+        assert_eq!(playlist.media_sequence(), 0);
+        assert_eq!(playlist.segments().len(), 8);
+        assert!(playlist
+            .tags
+            .contains(&Tag::ExtXPlaylistType("EVENT".to_string())));
+        assert_eq!(playlist.validate(), Ok(()));
+
+        // Parts are listed for the segments of the last three target durations
+        // and for the segment in progress.
+        let parts = playlist
+            .tags
+            .iter()
+            .filter(|tag| matches!(tag, Tag::ExtXPart { .. }))
+            .count();
+        assert_eq!(parts, 3 * 4 + 1);
+
+        assert!(window.push_part("s8.1.m4s", secs("0.6")).is_err());
+        assert!(window
+            .push_segment(LiveSegment::new("long.m4s", secs("3")))
+            .is_err());
+    }
+
+    #[test]
+    fn test_delta_update_round_trip() {
+        let data = "#EXTM3U\n#EXT-X-VERSION:6\n#EXT-X-TARGETDURATION:4\n\
+                    #EXT-X-SERVER-CONTROL:CAN-SKIP-UNTIL=8,CAN-SKIP-DATERANGES=YES\n\
+                    #EXT-X-MEDIA-SEQUENCE:20\n#EXT-X-KEY:METHOD=AES-128,URI=\"k1\"\n\
+                    #EXTINF:4,\na.ts\n#EXTINF:4,\nb.ts\n#EXT-X-KEY:METHOD=AES-128,URI=\"k2\"\n\
+                    #EXTINF:4,\nc.ts\n#EXTINF:4,\nd.ts\n#EXTINF:4,\ne.ts\n";
+        let playlist = Playlist::from_reader(data.as_bytes()).unwrap();
+
+        let delta = playlist
+            .delta_update(DecimalDuration::from_secs(8), &["ad-1".to_string()])
+            .unwrap();
+        assert_eq!(
+            delta.to_string(),
+            "#EXTM3U\n#EXT-X-VERSION:9\n#EXT-X-TARGETDURATION:4\n\
+             #EXT-X-SERVER-CONTROL:CAN-SKIP-UNTIL=8,CAN-SKIP-DATERANGES=YES\n\
+             #EXT-X-MEDIA-SEQUENCE:20\n\
+             #EXT-X-SKIP:SKIPPED-SEGMENTS=3,RECENTLY-REMOVED-DATERANGES=\"ad-1\"\n\
+             #EXTINF:4,\nd.ts\n#EXTINF:4,\ne.ts\n"
+        );
+        assert!(delta.validate().is_ok());
+        let bis = Validator::new(Profile::Rfc8216bis);
+        assert_eq!(bis.validate(&delta).into_result(), Ok(()));
+
+        let reparsed = Playlist::from_reader(delta.to_string().as_bytes()).unwrap();
+        assert_eq!(reparsed, delta);
+        let rebuilt = playlist.apply_delta_update(&reparsed).unwrap();
+        assert_eq!(rebuilt.tags[2..], playlist.tags[2..]);
+
+        // A previous playlist that starts earlier still covers the skip; the
+        // key in effect for the first skipped segment is carried over.
+        let previous = Playlist::from_reader(
+            "#EXTM3U\n#EXT-X-TARGETDURATION:4\n#EXT-X-MEDIA-SEQUENCE:19\n\
+             #EXT-X-KEY:METHOD=AES-128,URI=\"k1\"\n#EXTINF:4,\nz.ts\n#EXTINF:4,\na.ts\n\
+             #EXTINF:4,\nb.ts\n#EXT-X-KEY:METHOD=AES-128,URI=\"k2\"\n#EXTINF:4,\nc.ts\n"
+                .as_bytes(),
+        )
+        .unwrap();
+        let rebuilt = previous.apply_delta_update(&delta).unwrap();
+        assert_eq!(rebuilt.tags[2..], playlist.tags[2..]);
+
+        // Nothing to skip and no removed date ranges: the playlist is returned.
+        let full = playlist
+            .delta_update(DecimalDuration::from_secs(20), &[])
+            .unwrap();
+        assert_eq!(full, playlist);
+        assert_eq!(playlist.apply_delta_update(&full).unwrap(), playlist);
+    }
+
+    #[test]
+    fn test_delta_update_errors() {
+        let delta = Playlist::from_reader(
+            "#EXTM3U\n#EXT-X-VERSION:9\n#EXT-X-TARGETDURATION:4\n\
+             #EXT-X-SERVER-CONTROL:CAN-SKIP-UNTIL=8\n#EXT-X-MEDIA-SEQUENCE:20\n\
+             #EXT-X-SKIP:SKIPPED-SEGMENTS=3\n#EXTINF:4,\nd.ts\n"
+                .as_bytes(),
+        )
+        .unwrap();
+
+        // The previous playlist ends before the last skipped segment.
+        let stale = Playlist::from_reader(
+            "#EXTM3U\n#EXT-X-TARGETDURATION:4\n#EXT-X-MEDIA-SEQUENCE:18\n\
+             #EXTINF:4,\ny.ts\n#EXTINF:4,\nz.ts\n#EXTINF:4,\na.ts\n#EXTINF:4,\nb.ts\n"
+                .as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(
+            stale.apply_delta_update(&delta),
+            Err(
+                "Skipped segments 20..23 are not in the previous playlist, which has 18..22"
+                    .to_string()
+            )
+        );
+
+        // The previous playlist starts after the first skipped segment.
+        let ahead = Playlist::from_reader(
+            "#EXTM3U\n#EXT-X-TARGETDURATION:4\n#EXT-X-MEDIA-SEQUENCE:21\n\
+             #EXTINF:4,\nb.ts\n#EXTINF:4,\nc.ts\n#EXTINF:4,\nd.ts\n"
+                .as_bytes(),
+        )
+        .unwrap();
+        assert!(ahead.apply_delta_update(&delta).is_err());
+
+        let master = Playlist::from_reader(
+            "#EXTM3U\n#EXT-X-STREAM-INF:BANDWIDTH=1000000\nlow.m3u8\n".as_bytes(),
+        )
+        .unwrap();
+        assert!(master
+            .delta_update(DecimalDuration::from_secs(8), &[])
+            .is_err());
+    }
+
+    #[test]
+    fn test_validate_skip_tag() {
+        let playlist = Playlist::from_reader(
+            "#EXTM3U\n#EXT-X-VERSION:9\n#EXT-X-TARGETDURATION:4\n#EXTINF:4,\na.ts\n\
+             #EXT-X-SKIP:SKIPPED-SEGMENTS=1,RECENTLY-REMOVED-DATERANGES=\"ad-1\tad-2\"\n\
+             #EXTINF:4,\nb.ts\n"
+                .as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(
+            playlist.tags[4],
+            Tag::ExtXSkip {
+                skipped_segments: 1,
+                recently_removed_dateranges: Some(vec!["ad-1".to_string(), "ad-2".to_string()]),
+            }
+        );
+        assert_eq!(
+            Validator::new(Profile::Rfc8216bis)
+                .validate(&playlist)
+                .into_result(),
+            Err(vec![
+                ValidationError::InvalidSkipTag(
+                    "EXT-X-SKIP must precede the first media segment".to_string()
+                ),
+                ValidationError::InvalidSkipTag(
+                    "EXT-X-SERVER-CONTROL does not declare CAN-SKIP-UNTIL".to_string()
+                ),
+                ValidationError::InvalidSkipTag(
+                    "RECENTLY-REMOVED-DATERANGES requires CAN-SKIP-DATERANGES=YES".to_string()
+                ),
+            ])
+        );
+        assert!(Playlist::from_reader("#EXTM3U\n#EXT-X-SKIP:URI=\"a.ts\"\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_resolve_uri_reference() {
+        // The examples of RFC 3986 sections 5.4.1 and 5.4.2.
+        let base = "http://a/b/c/d;p?q";
+        let examples = [
+            ("g:h", "g:h"),
+            ("g", "http://a/b/c/g"),
+            ("./g", "http://a/b/c/g"),
+            ("g/", "http://a/b/c/g/"),
+            ("/g", "http://a/g"),
+            ("//g", "http://g"),
+            ("?y", "http://a/b/c/d;p?y"),
+            ("g?y", "http://a/b/c/g?y"),
+            ("#s", "http://a/b/c/d;p?q#s"),
+            ("g#s", "http://a/b/c/g#s"),
+            ("g?y#s", "http://a/b/c/g?y#s"),
+            (";x", "http://a/b/c/;x"),
+            ("g;x?y#s", "http://a/b/c/g;x?y#s"),
+            ("", "http://a/b/c/d;p?q"),
+            (".", "http://a/b/c/"),
+            ("./", "http://a/b/c/"),
+            ("..", "http://a/b/"),
+            ("../", "http://a/b/"),
+            ("../g", "http://a/b/g"),
+            ("../..", "http://a/"),
+            ("../../g", "http://a/g"),
+            ("../../../g", "http://a/g"),
+            ("../../../../g", "http://a/g"),
+            ("/./g", "http://a/g"),
+            ("/../g", "http://a/g"),
+            ("g.", "http://a/b/c/g."),
+            (".g", "http://a/b/c/.g"),
+            ("g..", "http://a/b/c/g.."),
+            ("..g", "http://a/b/c/..g"),
+            ("./../g", "http://a/b/g"),
+            ("./g/.", "http://a/b/c/g/"),
+            ("g/./h", "http://a/b/c/g/h"),
+            ("g/../h", "http://a/b/c/h"),
+            ("g;x=1/./y", "http://a/b/c/g;x=1/y"),
+            ("g;x=1/../y", "http://a/b/c/y"),
+            ("g?y/./x", "http://a/b/c/g?y/./x"),
+            ("g#s/../x", "http://a/b/c/g#s/../x"),
+            ("http:g", "http:g"),
+        ];
+        for (reference, expected) in examples {
+            assert_eq!(resolve(base, reference), expected, "{}", reference);
+        }
+
+        assert_eq!(resolve("http://a", "g"), "http://a/g");
+        assert_eq!(
+            resolve("videos/main.m3u8", "720p/index.m3u8"),
+            "videos/720p/index.m3u8"
+        );
+
+        // A relative base yields a path relative to the same location.
+        let relative = [
+            ("dir/index.m3u8", "../x.ts", "x.ts"),
+            ("a/b/index.m3u8", "../x.ts", "a/x.ts"),
+            ("a/b/index.m3u8", "./../../x.ts", "x.ts"),
+            ("a/index.m3u8", "../../x.ts", "../x.ts"),
+            ("index.m3u8", "../x.ts", "../x.ts"),
+            ("../index.m3u8", "../x.ts", "../../x.ts"),
+            ("a/b/index.m3u8", "..", "a/"),
+            ("a/index.m3u8", "..", "./"),
+            ("a/index.m3u8", "/x.ts", "/x.ts"),
+        ];
+        for (base, reference, expected) in relative {
+            assert_eq!(resolve(base, reference), expected, "{} {}", base, reference);
+        }
+    }
+
+    #[test]
+    fn test_resolve_playlist_uris() {
+        let data = r#"#EXTM3U
+#EXT-X-VERSION:9
+#EXT-X-TARGETDURATION:4
+#EXT-X-PART-INF:PART-TARGET=1.0
+#EXT-X-KEY:METHOD=AES-128,URI="/keys/k1"
+#EXT-X-MAP:URI="../init.mp4"
+#EXTINF:4,
+seg1.m4s
+#EXT-X-PART:DURATION=1.0,URI="seg2.0.m4s"
+#EXT-X-PRELOAD-HINT:TYPE=PART,URI="seg2.1.m4s"
+#EXT-X-RENDITION-REPORT:URI="../1080p/index.m3u8",LAST-MSN=1
+"#;
+        let (mut playlist, mut source) =
+            Playlist::from_reader_with_source(data.as_bytes()).unwrap();
+        assert_eq!(source.url(), None);
+        assert_eq!(source.absolute_uri("seg1.m4s"), "seg1.m4s");
+
+        let base = "https://cdn.example.com/live/720p/index.m3u8?token=abc";
+        source.set_url(base);
+        assert_eq!(source.url(), Some(base));
+        assert_eq!(
+            source.absolute_uri("seg1.m4s"),
+            "https://cdn.example.com/live/720p/seg1.m4s"
+        );
+
+        playlist.resolve_uris(base);
+        let uris: Vec<String> = playlist
+            .tags
+            .iter()
+            .filter_map(|tag| match tag {
+                Tag::ExtXKey { uri, .. } => uri.clone(),
+                Tag::ExtXMap { uri, .. }
+                | Tag::ExtInf(uri, ..)
+                | Tag::ExtXPart { uri, .. }
+                | Tag::ExtXPreloadHint { uri, .. }
+                | Tag::ExtXRenditionReport { uri, .. } => Some(uri.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            uris,
+            [
+                "https://cdn.example.com/keys/k1",
+                "https://cdn.example.com/live/init.mp4",
+                "https://cdn.example.com/live/720p/seg1.m4s",
+                "https://cdn.example.com/live/720p/seg2.0.m4s",
+                "https://cdn.example.com/live/720p/seg2.1.m4s",
+                "https://cdn.example.com/live/1080p/index.m3u8",
+            ]
+        );
+
+        let mut master = Playlist::from_reader(
+            "#EXTM3U\n#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"en\",URI=\"audio/en.m3u8\"\n\
+             #EXT-X-STREAM-INF:BANDWIDTH=1000000,AUDIO=\"aac\"\nhttp://other.example.com/low.m3u8\n\
+             #EXT-X-I-FRAME-STREAM-INF:BANDWIDTH=200000,URI=\"iframes.m3u8\"\n"
+                .as_bytes(),
+        )
+        .unwrap();
+        master.resolve_uris("https://example.com/hls/main.m3u8");
+        assert_eq!(
+            master.to_string(),
+            "#EXTM3U\n#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",\
+             URI=\"https://example.com/hls/audio/en.m3u8\",NAME=\"en\"\n\
+             #EXT-X-STREAM-INF:BANDWIDTH=1000000,AUDIO=\"aac\"\nhttp://other.example.com/low.m3u8\n\
+             #EXT-X-I-FRAME-STREAM-INF:BANDWIDTH=200000,URI=\"https://example.com/hls/iframes.m3u8\"\n"
+        );
+    }
+
+    #[test]
+    fn test_map_uris_visits_every_kind() {
+        let master = r#"#EXTM3U
+#EXT-X-SESSION-DATA:ID="com.example.lyrics",URI="lyrics.json"
+#EXT-X-SESSION-DATA:ID="com.example.title",VALUE="Title",LANGUAGE="en"
+#EXT-X-SESSION-KEY:METHOD=AES-128,URI="session.key",IV="0x1"
+#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID="aac",NAME="en",URI="en.m3u8"
+#EXT-X-STREAM-INF:BANDWIDTH=1000000,AUDIO="aac"
+low.m3u8
+#EXT-X-I-FRAME-STREAM-INF:BANDWIDTH=200000,URI="iframes.m3u8"
+"#;
+        let media = r#"#EXTM3U
+#EXT-X-VERSION:9
+#EXT-X-TARGETDURATION:4
+#EXT-X-PART-INF:PART-TARGET=1.0
+#EXT-X-KEY:METHOD=AES-128,URI="k1"
+#EXT-X-MAP:URI="init.mp4"
+#EXTINF:4,
+seg1.m4s
+#EXT-X-PART:DURATION=1.0,URI="seg2.0.m4s"
+#EXT-X-PRELOAD-HINT:TYPE=PART,URI="seg2.1.m4s"
+#EXT-X-RENDITION-REPORT:URI="../high/index.m3u8",LAST-MSN=1
+"#;
+
+        let mut visited = Vec::new();
+        for data in [master, media] {
+            let mut playlist = Playlist::from_reader(data.as_bytes()).unwrap();
+            playlist.map_uris(|kind, uri| {
+                visited.push((kind, uri.to_string()));
+                format!("https://cdn.example.com/{}", uri)
+            });
+            assert!(playlist
+                .to_string()
+                .lines()
+                .filter(|line| !line.starts_with('#') || line.contains("URI="))
+                .all(|line| line.contains("https://cdn.example.com/")));
+        }
+        assert_eq!(
+            visited,
+            [
+                (UriKind::SessionData, "lyrics.json".to_string()),
+                (UriKind::SessionKey, "session.key".to_string()),
+                (UriKind::Rendition, "en.m3u8".to_string()),
+                (UriKind::Variant, "low.m3u8".to_string()),
+                (UriKind::IFrameVariant, "iframes.m3u8".to_string()),
+                (UriKind::Key, "k1".to_string()),
+                (UriKind::Map, "init.mp4".to_string()),
+                (UriKind::Segment, "seg1.m4s".to_string()),
+                (UriKind::Part, "seg2.0.m4s".to_string()),
+                (UriKind::PreloadHint, "seg2.1.m4s".to_string()),
+                (UriKind::RenditionReport, "../high/index.m3u8".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_filter_master_playlist() {
+        let data = r#"#EXTM3U
+#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID="aac",NAME="English",LANGUAGE="en",DEFAULT=YES,AUTOSELECT=YES,URI="aac/en.m3u8"
+#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID="aac",NAME="Deutsch",LANGUAGE="de",URI="aac/de.m3u8"
+#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID="ec3",NAME="English",LANGUAGE="en",URI="ec3/en.m3u8"
+#EXT-X-MEDIA:TYPE=SUBTITLES,GROUP-ID="subs",NAME="Deutsch",LANGUAGE="de",URI="subs/de.m3u8"
+#EXT-X-STREAM-INF:BANDWIDTH=6000000,CODECS="hvc1.2.4.L153.B0,ec-3",RESOLUTION=3840x2160,AUDIO="ec3"
+hevc.m3u8
+#EXT-X-STREAM-INF:BANDWIDTH=3000000,CODECS="avc1.64001f,mp4a.40.2",RESOLUTION=1920x1080,AUDIO="aac",SUBTITLES="subs"
+high.m3u8
+#EXT-X-STREAM-INF:BANDWIDTH=1000000,CODECS="avc1.4d401f,mp4a.40.2",RESOLUTION=640x360,AUDIO="aac",SUBTITLES="subs"
+low.m3u8
+#EXT-X-I-FRAME-STREAM-INF:BANDWIDTH=600000,CODECS="hvc1.2.4.L153.B0",RESOLUTION=3840x2160,URI="hevc-iframes.m3u8"
+#EXT-X-I-FRAME-STREAM-INF:BANDWIDTH=100000,CODECS="avc1.4d401f",RESOLUTION=640x360,URI="low-iframes.m3u8"
+"#;
+        let uris = |playlist: &Playlist| -> Vec<String> {
+            playlist
+                .tags
+                .iter()
+                .filter_map(|tag| match tag {
+                    Tag::ExtXStreamInf { uri, .. } | Tag::ExtXIFrameStreamInf { uri, .. } => {
+                        Some(uri.clone())
+                    }
+                    Tag::ExtXMedia { uri, .. } => uri.clone(),
+                    _ => None,
+                })
+                .collect()
+        };
+
+        // Removing HEVC drops its I-frame variant and the orphaned EC-3 group.
+        let mut playlist = Playlist::from_reader(data.as_bytes()).unwrap();
+        playlist.remove_codecs(&["hvc1", "hev1"]).unwrap();
+        assert_eq!(
+            uris(&playlist),
+            [
+                "aac/en.m3u8",
+                "aac/de.m3u8",
+                "subs/de.m3u8",
+                "high.m3u8",
+                "low.m3u8",
+                "low-iframes.m3u8"
+            ]
+        );
+        assert_eq!(playlist.validate(), Ok(()));
+
+        // A resolution cap removes the variants and I-frame variants above it.
+        let mut playlist = Playlist::from_reader(data.as_bytes()).unwrap();
+        playlist
+            .limit_resolution(Resolution::new(1920, 1080))
+            .unwrap();
+        assert_eq!(uris(&playlist).len(), 6);
+        playlist.limit_bandwidth(2_000_000).unwrap();
+        assert_eq!(
+            uris(&playlist),
+            [
+                "aac/en.m3u8",
+                "aac/de.m3u8",
+                "subs/de.m3u8",
+                "low.m3u8",
+                "low-iframes.m3u8"
+            ]
+        );
+        assert!(playlist.limit_bandwidth(500_000).is_err());
+        assert_eq!(uris(&playlist).len(), 5);
+
+        // Removing a language promotes a new default and drops emptied
+        // subtitle references; removing the AAC group removes its variants.
+        let mut playlist = Playlist::from_reader(data.as_bytes()).unwrap();
+        playlist.remove_language("AUDIO", "EN").unwrap();
+        playlist.remove_language("SUBTITLES", "de").unwrap();
+        assert_eq!(
+            playlist.tags[1],
+            Tag::ExtXMedia {
+                type_: "AUDIO".to_string(),
+                group_id: "aac".to_string(),
+                name: Some("Deutsch".to_string()),
+                uri: Some("aac/de.m3u8".to_string()),
+                default: Some(true),
+                autoplay: Some(true),
+                characteristics: None,
+                language: Some("de".to_string()),
+                instream_id: None,
+                language_codec: None,
+                forced: None,
+            }
+        );
+        assert_eq!(
+            uris(&playlist),
+            ["aac/de.m3u8", "high.m3u8", "low.m3u8", "low-iframes.m3u8"]
+        );
+        assert!(playlist.tags.iter().all(|tag| !matches!(
+            tag,
+            Tag::ExtXStreamInf {
+                subtitle: Some(_),
+                ..
+            }
+        )));
+        assert_eq!(playlist.validate(), Ok(()));
+        assert!(playlist.remove_media_group("AUDIO", "aac").is_err());
+
+        let mut playlist = Playlist::from_reader(data.as_bytes()).unwrap();
+        playlist.remove_media_group("AUDIO", "aac").unwrap();
+        assert_eq!(
+            uris(&playlist),
+            ["ec3/en.m3u8", "hevc.m3u8", "hevc-iframes.m3u8"]
+        );
+
+        let mut playlist = Playlist::from_reader(data.as_bytes()).unwrap();
+        playlist.sort_variants_by_bandwidth(false);
+        assert_eq!(
+            uris(&playlist)[4..7],
+            ["low.m3u8", "high.m3u8", "hevc.m3u8"]
+        );
+        playlist.sort_variants_by_bandwidth(true);
+        assert_eq!(
+            uris(&playlist)[4..7],
+            ["hevc.m3u8", "high.m3u8", "low.m3u8"]
+        );
+
+        let mut media = Playlist::from_reader("#EXTM3U\n#EXTINF:4,\na.ts\n".as_bytes()).unwrap();
+        assert!(media.limit_bandwidth(1).is_err());
+    }
+
+    #[test]
+    fn test_diff_media_playlists() {
+        let before = Playlist::from_reader(
+            "#EXTM3U\n#EXT-X-TARGETDURATION:4\n#EXT-X-MEDIA-SEQUENCE:10\n\
+             #EXT-X-KEY:METHOD=AES-128,URI=\"k1\"\n#EXTINF:4,\na.ts\n#EXTINF:4,\nb.ts\n\
+             #EXTINF:4,\nc.ts\n"
+                .as_bytes(),
+        )
+        .unwrap();
+        let after = Playlist::from_reader(
+            "#EXTM3U\n#EXT-X-TARGETDURATION:6\n#EXT-X-MEDIA-SEQUENCE:11\n\
+             #EXT-X-KEY:METHOD=AES-128,URI=\"k1\"\n#EXTINF:4,\nb.ts\n#EXTINF:3.9,\nc2.ts\n\
+             #EXT-X-KEY:METHOD=AES-128,URI=\"k2\"\n#EXTINF:6,\nd.ts\n#EXT-X-ENDLIST\n"
+                .as_bytes(),
+        )
+        .unwrap();
+
+        let diff = before.diff(&after);
+        assert_eq!(
+            diff.changes[0],
+            Change::HeaderChanged {
+                name: "EXT-X-TARGETDURATION",
+                old: Some("4".to_string()),
+                new: Some("6".to_string()),
+            }
+        );
+        assert_eq!(
+            diff.changes[4],
+            Change::SegmentChanged {
+                media_sequence: 12,
+                old: SegmentSummary {
+                    uri: "c.ts".to_string(),
+                    duration: DecimalDuration::from_secs(4),
+                },
+                new: SegmentSummary {
+                    uri: "c2.ts".to_string(),
+                    duration: "3.9".parse().unwrap(),
+                },
+            }
+        );
+        assert_eq!(
+            diff.to_string(),
+            "EXT-X-TARGETDURATION changed from 4 to 6\n\
+             EXT-X-MEDIA-SEQUENCE changed from 10 to 11\n\
+             EXT-X-ENDLIST added\n\
+             segment 10 removed: a.ts (4s)\n\
+             segment 12 changed from c.ts (4s) to c2.ts (3.9s)\n\
+             segment 13 added: d.ts (6s)\n\
+             key rotated at segment 13: METHOD=AES-128,URI=\"k2\"\n"
+        );
+
+        assert!(before.diff(&before).is_empty());
+        let reversed = after.diff(&before);
+        assert!(reversed.changes.contains(&Change::HeaderChanged {
+            name: "EXT-X-ENDLIST",
+            old: Some(String::new()),
+            new: None,
+        }));
+
+        // A delta update skips segments and their keys but still has them.
+        let playlist = Playlist::from_reader(
+            "#EXTM3U\n#EXT-X-VERSION:6\n#EXT-X-TARGETDURATION:4\n\
+             #EXT-X-SERVER-CONTROL:CAN-SKIP-UNTIL=8\n#EXT-X-MEDIA-SEQUENCE:20\n\
+             #EXT-X-KEY:METHOD=AES-128,URI=\"k1\"\n#EXTINF:4,\na.ts\n#EXTINF:4,\nb.ts\n\
+             #EXT-X-KEY:METHOD=AES-128,URI=\"k2\"\n#EXTINF:4,\nc.ts\n#EXTINF:4,\nd.ts\n\
+             #EXTINF:4,\ne.ts\n"
+                .as_bytes(),
+        )
+        .unwrap();
+        let delta = playlist
+            .delta_update(DecimalDuration::from_secs(8), &[])
+            .unwrap();
+        assert_eq!(
+            playlist.diff(&delta).to_string(),
+            "EXT-X-VERSION changed from 6 to 9\n\
+             EXT-X-SKIP added: SKIPPED-SEGMENTS=3\n"
+        );
+        assert_eq!(
+            delta.diff(&playlist).to_string(),
+            "EXT-X-VERSION changed from 9 to 6\n\
+             EXT-X-SKIP removed\n"
+        );
+    }
+
+    #[test]
+    fn test_diff_master_playlists() {
+        let before = Playlist::from_reader(
+            "#EXTM3U\n\
+             #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"English\",LANGUAGE=\"en\",URI=\"en.m3u8\"\n\
+             #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"Deutsch\",LANGUAGE=\"de\",URI=\"de.m3u8\"\n\
+             #EXT-X-STREAM-INF:BANDWIDTH=1000000,AUDIO=\"aac\"\nlow.m3u8\n\
+             #EXT-X-STREAM-INF:BANDWIDTH=3000000,AUDIO=\"aac\"\nhigh.m3u8\n"
+                .as_bytes(),
+        )
+        .unwrap();
+        let after = Playlist::from_reader(
+            "#EXTM3U\n\
+             #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"English\",LANGUAGE=\"en\",DEFAULT=YES,URI=\"en2.m3u8\"\n\
+             #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"Français\",LANGUAGE=\"fr\",URI=\"fr.m3u8\"\n\
+             #EXT-X-STREAM-INF:BANDWIDTH=1200000,AUDIO=\"aac\"\nlow.m3u8\n\
+             #EXT-X-I-FRAME-STREAM-INF:BANDWIDTH=100000,URI=\"iframes.m3u8\"\n"
+                .as_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            before.diff(&after).to_string(),
+            "variant low.m3u8 BANDWIDTH changed from 1000000 to 1200000\n\
+             variant high.m3u8 removed\n\
+             variant iframes.m3u8 added\n\
+             rendition AUDIO/aac/English DEFAULT added: YES\n\
+             rendition AUDIO/aac/English URI changed from en.m3u8 to en2.m3u8\n\
+             rendition AUDIO/aac/Deutsch removed\n\
+             rendition AUDIO/aac/Français added\n"
+        );
+
+        // The same variant URI listed once per audio group.
+        let shared = Playlist::from_reader(
+            "#EXTM3U\n\
+             #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"English\",URI=\"aac.m3u8\"\n\
+             #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"ec3\",NAME=\"English\",URI=\"ec3.m3u8\"\n\
+             #EXT-X-STREAM-INF:BANDWIDTH=1000000,AUDIO=\"aac\"\nvideo.m3u8\n\
+             #EXT-X-STREAM-INF:BANDWIDTH=1400000,AUDIO=\"ec3\"\nvideo.m3u8\n"
+                .as_bytes(),
+        )
+        .unwrap();
+        assert!(shared.diff(&shared).is_empty());
+        let mut without_ec3 = Playlist::from_reader(shared.to_string().as_bytes()).unwrap();
+        without_ec3.remove_media_group("AUDIO", "ec3").unwrap();
+        assert_eq!(
+            shared.diff(&without_ec3).to_string(),
+            "variant video.m3u8 removed\n\
+             rendition AUDIO/ec3/English removed\n"
+        );
+    }
+
+    #[test]
+    fn test_event_playlist_to_vod() {
+        let data = r#"#EXTM3U
+#EXT-X-VERSION:9
+#EXT-X-TARGETDURATION:2
+#EXT-X-PLAYLIST-TYPE:EVENT
+#EXT-X-INDEPENDENT-SEGMENTS
+#EXT-X-SERVER-CONTROL:CAN-BLOCK-RELOAD=YES,PART-HOLD-BACK=3.0
+#EXT-X-PART-INF:PART-TARGET=1.0
+#EXT-X-MAP:URI="init.mp4"
+#EXTINF:2,
+a.m4s
+#EXTINF:2,
+b.m4s
+#EXT-X-PART:DURATION=1.0,URI="c.0.m4s"
+#EXT-X-PART:DURATION=1.0,URI="c.1.m4s"
+#EXTINF:2.4,
+c.m4s
+#EXT-X-PRELOAD-HINT:TYPE=PART,URI="d.0.m4s"
+#EXT-X-RENDITION-REPORT:URI="../1080p/index.m3u8",LAST-MSN=2
+"#;
+        let event = Playlist::from_reader(data.as_bytes()).unwrap();
+
+        let vod = event.to_vod().unwrap();
+        assert_eq!(
+            vod.to_string(),
+            "#EXTM3U\n#EXT-X-VERSION:6\n#EXT-X-TARGETDURATION:2\n#EXT-X-PLAYLIST-TYPE:VOD\n\
+             #EXT-X-INDEPENDENT-SEGMENTS\n#EXT-X-MAP:URI=\"init.mp4\"\n#EXTINF:2,\na.m4s\n\
+             #EXTINF:2,\nb.m4s\n#EXTINF:2.4,\nc.m4s\n#EXT-X-ENDLIST\n"
+        );
+        assert_eq!(vod.validate(), Ok(()));
+        // Converting a finished playlist again changes nothing.
+        assert_eq!(vod.to_vod().unwrap(), vod);
+
+        let trimmed = event
+            .to_vod_range(
+                DecimalDuration::from_secs(2)..DecimalDuration::from_secs(6),
+                SliceMode::Snap,
+            )
+            .unwrap();
+        assert_eq!(
+            trimmed.to_string(),
+            "#EXTM3U\n#EXT-X-VERSION:6\n#EXT-X-TARGETDURATION:2\n#EXT-X-PLAYLIST-TYPE:VOD\n\
+             #EXT-X-INDEPENDENT-SEGMENTS\n#EXT-X-MEDIA-SEQUENCE:1\n#EXT-X-MAP:URI=\"init.mp4\"\n\
+             #EXTINF:2,\nb.m4s\n#EXTINF:2.4,\nc.m4s\n#EXT-X-ENDLIST\n"
+        );
+
+        let delta = Playlist::from_reader(
+            "#EXTM3U\n#EXT-X-TARGETDURATION:2\n#EXT-X-SKIP:SKIPPED-SEGMENTS=1\n#EXTINF:2,\nb.m4s\n"
+                .as_bytes(),
+        )
+        .unwrap();
+        assert!(delta.to_vod().is_err());
+        let master = Playlist::from_reader(
+            "#EXTM3U\n#EXT-X-STREAM-INF:BANDWIDTH=1000000\nlow.m3u8\n".as_bytes(),
+        )
+        .unwrap();
+        assert!(master.to_vod().is_err());
+    }
 }
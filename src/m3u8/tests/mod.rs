@@ -1 +1,4 @@
+mod extinf_tests;
 mod lib_tests;
+mod types_tests;
+mod validation_tests;
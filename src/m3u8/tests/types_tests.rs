@@ -0,0 +1,280 @@
+#[cfg(test)]
+mod tests {
+    use crate::m3u8::playlist::builder::PlaylistBuilder;
+    use crate::m3u8::playlist::Playlist;
+    use crate::m3u8::tags::Tag;
+    use crate::m3u8::types::{
+        ByteRange, Codec, CodecInfo, CodecType, Codecs, DateTime, DecimalDuration, Resolution,
+    };
+    use crate::m3u8::validation::ValidationError;
+
+    #[test]
+    fn test_parse_resolution() {
+        let resolution: Resolution = "1280x720".parse().unwrap();
+        assert_eq!(resolution, Resolution::new(1280, 720));
+        assert_eq!(resolution.to_string(), "1280x720");
+        assert!("1280".parse::<Resolution>().is_err());
+        assert!("widexhigh".parse::<Resolution>().is_err());
+    }
+
+    #[test]
+    fn test_parse_byte_range() {
+        assert_eq!(
+            "1000@200".parse::<ByteRange>().unwrap(),
+            ByteRange::new(1000, Some(200))
+        );
+        assert_eq!(
+            "1000".parse::<ByteRange>().unwrap(),
+            ByteRange::new(1000, None)
+        );
+        assert_eq!(ByteRange::new(1000, Some(200)).to_string(), "1000@200");
+        assert_eq!(ByteRange::new(1000, None).to_string(), "1000");
+        assert!("abc@1".parse::<ByteRange>().is_err());
+    }
+
+    #[test]
+    fn test_parse_codecs() {
+        let codecs: Codecs =
+            "avc1.64001F,hvc1.2.4.L153.B0,av01.0.04M.10,mp4a.40.2,ec-3,stpp.ttml.im1t"
+                .parse()
+                .unwrap();
+        let infos: Vec<&CodecInfo> = codecs.iter().map(Codec::info).collect();
+
+        assert_eq!(
+            infos,
+            vec![
+                &CodecInfo::Avc {
+                    profile: 0x64,
+                    constraints: 0x00,
+                    level: 0x1f
+                },
+                &CodecInfo::Hevc {
+                    profile_space: None,
+                    profile: 2,
+                    compatibility: 4,
+                    tier: 'L',
+                    level: 153
+                },
+                &CodecInfo::Av1 {
+                    profile: 0,
+                    level: 4,
+                    tier: 'M',
+                    bit_depth: 10
+                },
+                &CodecInfo::Mp4a {
+                    object_type: 0x40,
+                    audio_object_type: Some(2)
+                },
+                &CodecInfo::Ec3,
+                &CodecInfo::Stpp {
+                    profile: Some("ttml.im1t".to_string())
+                },
+            ]
+        );
+        assert!(codecs.contains("HVC1"));
+        assert_eq!(
+            codecs.iter().last().unwrap().codec_type(),
+            CodecType::Subtitles
+        );
+        // The original spelling is preserved when written back.
+        assert_eq!(
+            codecs.to_string(),
+            "avc1.64001F,hvc1.2.4.L153.B0,av01.0.04M.10,mp4a.40.2,ec-3,stpp.ttml.im1t"
+        );
+        // Unrecognised AVC parameters are kept undecoded rather than rejected.
+        for value in ["avc1.64zz1f", "avc1.77.30", "avc1.6\u{20ac}01", "avc1"] {
+            let codec: Codec = value.parse().unwrap();
+            assert_eq!(codec.info(), &CodecInfo::Other, "{}", value);
+            assert_eq!(codec.to_string(), value);
+        }
+        let master =
+            "#EXTM3U\n#EXT-X-STREAM-INF:BANDWIDTH=1000,CODECS=\"avc1.6\u{20ac}01\"\nlow.m3u8\n";
+        assert!(Playlist::from_reader(master.as_bytes()).is_ok());
+        assert!("mp4a".parse::<Codecs>().is_err());
+    }
+
+    #[test]
+    fn test_parse_master_playlist_with_typed_attributes() {
+        let data = r#"#EXTM3U
+#EXT-X-STREAM-INF:BANDWIDTH=1280000,AVERAGE-BANDWIDTH=1100000,CODECS="avc1.4d401f,mp4a.40.2",RESOLUTION=1280x720,FRAME-RATE=29.97
+https://media.example.com/720p.m3u8
+#EXT-X-I-FRAME-STREAM-INF:BANDWIDTH=86000,RESOLUTION=640x360,URI="https://media.example.com/iframe.m3u8"
+"#;
+
+        let playlist = Playlist::from_reader(data.as_bytes()).unwrap();
+        assert_eq!(
+            playlist.tags,
+            vec![
+                Tag::ExtM3U,
+                Tag::ExtXStreamInf {
+                    uri: "https://media.example.com/720p.m3u8".to_string(),
+                    bandwidth: 1280000,
+                    average_bandwidth: Some(1100000),
+                    codecs: Some("avc1.4d401f,mp4a.40.2".parse().unwrap()),
+                    resolution: Some(Resolution::new(1280, 720)),
+                    frame_rate: Some(29.97),
+                    audio: None,
+                    video: None,
+                    subtitle: None,
+                    closed_captions: None,
+                },
+                Tag::ExtXIFrameStreamInf {
+                    bandwidth: 86000,
+                    codecs: None,
+                    resolution: Some(Resolution::new(640, 360)),
+                    frame_rate: None,
+                    uri: "https://media.example.com/iframe.m3u8".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_playlist_with_byte_ranges() {
+        let data = r#"#EXTM3U
+#EXT-X-VERSION:4
+#EXT-X-TARGETDURATION:10
+#EXT-X-MAP:URI="main.mp4",BYTERANGE="720@0"
+#EXT-X-BYTERANGE:75232@720
+#EXTINF:10.0,
+main.mp4
+#EXT-X-ENDLIST
+"#;
+
+        let playlist = Playlist::from_reader(data.as_bytes()).unwrap();
+        assert_eq!(
+            playlist.tags[3],
+            Tag::ExtXMap {
+                uri: "main.mp4".to_string(),
+                byterange: Some(ByteRange::new(720, Some(0))),
+            }
+        );
+        assert_eq!(
+            playlist.tags[4],
+            Tag::ExtXByteRange(ByteRange::new(75232, Some(720)))
+        );
+        assert_eq!(
+            playlist.tags[3].to_string(),
+            r#"#EXT-X-MAP:URI="main.mp4",BYTERANGE="720@0""#
+        );
+    }
+
+    #[test]
+    fn test_validate_zero_length_byte_range() {
+        let playlist = PlaylistBuilder::new()
+            .extm3u()
+            .version(4)
+            .target_duration(10)
+            .byte_range(ByteRange::new(0, Some(0)))
+            .extinf("https://media.example.com/main.ts", 5.005, None)
+            .end_list()
+            .build();
+
+        assert_eq!(
+            playlist,
+            Err(vec![ValidationError::InvalidByteRange("0@0".to_string())])
+        );
+    }
+
+    #[test]
+    fn test_decimal_duration_preserves_precision() {
+        for text in ["10", "5.005", "5.0050", "3.000", "0.033367", "-1"] {
+            let duration: DecimalDuration = text.parse().unwrap();
+            assert_eq!(duration.to_string(), text);
+        }
+
+        let duration: DecimalDuration = "5.005".parse().unwrap();
+        assert_eq!(duration, "5.00500".parse().unwrap());
+        assert_eq!(format!("{:.4}", duration), "5.0050");
+        assert_eq!(format!("{:.2}", duration), "5.01");
+        assert_eq!(duration.with_precision(0).to_string(), "5");
+        assert_eq!(DecimalDuration::from(5.005f32).to_string(), "5.005");
+
+        // Digits beyond microseconds are rounded.
+        let duration: DecimalDuration = "1.0000005".parse().unwrap();
+        assert_eq!(duration.as_micros(), 1_000_001);
+
+        assert!("".parse::<DecimalDuration>().is_err());
+        assert!("1e3".parse::<DecimalDuration>().is_err());
+        assert!("1.2.3".parse::<DecimalDuration>().is_err());
+    }
+
+    #[test]
+    fn test_decimal_duration_sums_exactly() {
+        let segment: DecimalDuration = "0.1".parse().unwrap();
+        let total: DecimalDuration = std::iter::repeat_n(segment, 100_000).sum();
+        assert_eq!(total, DecimalDuration::from_secs(10_000));
+        assert_eq!(total.to_string(), "10000.0");
+
+        let total: DecimalDuration = ["5.005", "5.005", "3.003"]
+            .iter()
+            .map(|d| d.parse::<DecimalDuration>().unwrap())
+            .sum();
+        assert_eq!(total.to_string(), "13.013");
+        assert_eq!(total.round_secs(), 13);
+    }
+
+    #[test]
+    fn test_decimal_duration_saturates() {
+        let max: DecimalDuration = "9223372036854.775807".parse().unwrap();
+        assert_eq!(max.as_micros(), i64::MAX);
+        assert_eq!(max.to_string(), "9223372036854.775807");
+        assert_eq!(format!("{:.0}", max), "9223372036855");
+        assert_eq!(max.round_secs(), 9_223_372_036_855);
+        assert_eq!(max + max, max);
+        assert_eq!(DecimalDuration::from_secs(u64::MAX), max);
+        let min = DecimalDuration::from_micros(i64::MIN);
+        assert_eq!(min - max, min);
+        assert_eq!(min.to_string(), "-9223372036854.775808");
+
+        let data = "#EXTM3U\n#EXT-X-TARGETDURATION:10\n#EXTINF:9223372036854.775807,\nfirst.ts\n\
+                    #EXTINF:10,\nsecond.ts\n";
+        let playlist = Playlist::from_reader(data.as_bytes()).unwrap();
+        assert!(playlist.validate().is_err());
+    }
+
+    #[test]
+    fn test_extinf_round_trips_duration_text() {
+        let data = "#EXTM3U\n#EXT-X-TARGETDURATION:10\n#EXTINF:9.97663,\nfirst.ts\n#EXTINF:10,\nsecond.ts\n";
+        let playlist = Playlist::from_reader(data.as_bytes()).unwrap();
+
+        assert_eq!(playlist.tags[2].to_string(), "#EXTINF:9.97663,\nfirst.ts");
+        assert_eq!(playlist.tags[3].to_string(), "#EXTINF:10,\nsecond.ts");
+    }
+
+    #[test]
+    fn test_parse_date_time() {
+        let utc: DateTime = "2024-11-05T12:00:00Z".parse().unwrap();
+        assert_eq!(utc.unix_micros(), 1_730_808_000_000_000);
+        assert_eq!(utc.to_string(), "2024-11-05T12:00:00Z");
+
+        let offset: DateTime = "2024-11-05T14:30:00.250+02:30".parse().unwrap();
+        assert_eq!(offset.offset_minutes(), 150);
+        assert_eq!(offset.duration_since(&utc).to_string(), "0.25");
+        assert_eq!(offset.to_string(), "2024-11-05T14:30:00.250+02:30");
+
+        let compact: DateTime = "2024-11-05T07:00:00.123456789-0500".parse().unwrap();
+        assert_eq!(compact.unix_micros(), utc.unix_micros() + 123_456);
+        assert_eq!(compact.to_string(), "2024-11-05T07:00:00.123456-05:00");
+
+        let leap_day: DateTime = "2024-02-29T23:59:59.5Z".parse().unwrap();
+        assert_eq!(
+            leap_day.add_duration("0.5".parse().unwrap()).to_string(),
+            "2024-03-01T00:00:00.0Z"
+        );
+
+        for invalid in [
+            "",
+            "2024-11-05",
+            "2024-11-05T12:00:00",
+            "2024-13-05T12:00:00Z",
+            "2023-02-29T12:00:00Z",
+            "2024-11-05T24:00:00Z",
+            "2024-11-05T12:00:00.Z",
+            "2024-11-05T12:00:00+2",
+            "2024/11/05T12:00:00Z",
+        ] {
+            assert!(invalid.parse::<DateTime>().is_err(), "{}", invalid);
+        }
+    }
+}
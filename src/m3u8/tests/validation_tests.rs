@@ -0,0 +1,772 @@
+#[cfg(test)]
+mod tests {
+    use crate::m3u8::playlist::builder::PlaylistBuilder;
+    use crate::m3u8::playlist::Playlist;
+    use crate::m3u8::tags::Tag;
+    use crate::m3u8::types::ByteRange;
+    use crate::m3u8::validation::{Profile, Rule, Severity, ValidationError, Validator};
+
+    fn validate(data: &str) -> Result<(), Vec<ValidationError>> {
+        Playlist::from_reader(data.as_bytes()).unwrap().validate()
+    }
+
+    #[test]
+    fn test_validate_segment_duration_exceeds_target() {
+        let playlist = PlaylistBuilder::new()
+            .extm3u()
+            .version(3)
+            .target_duration(6)
+            .extinf("https://media.example.com/first.ts", 6.499, None)
+            .extinf("https://media.example.com/second.ts", 6.5, None)
+            .end_list()
+            .build();
+
+        assert_eq!(
+            playlist,
+            Err(vec![ValidationError::SegmentDurationExceedsTarget {
+                uri: "https://media.example.com/second.ts".to_string(),
+                duration: "6.5".parse().unwrap(),
+                target_duration: 6,
+            }])
+        );
+    }
+
+    #[test]
+    fn test_validate_target_duration_count() {
+        let missing = "#EXTM3U\n#EXTINF:5,\nfirst.ts\n#EXT-X-ENDLIST\n";
+        assert_eq!(
+            validate(missing),
+            Err(vec![ValidationError::MissingTargetDuration])
+        );
+
+        let duplicated =
+            "#EXTM3U\n#EXT-X-TARGETDURATION:10\n#EXT-X-TARGETDURATION:6\n#EXTINF:5,\nfirst.ts\n";
+        assert_eq!(
+            validate(duplicated),
+            Err(vec![ValidationError::DuplicateTag(
+                "EXT-X-TARGETDURATION".to_string()
+            )])
+        );
+    }
+
+    #[test]
+    fn test_validate_tag_placement() {
+        let data = r#"#EXTM3U
+#EXT-X-TARGETDURATION:10
+#EXTINF:5,
+first.ts
+#EXT-X-MEDIA-SEQUENCE:4
+#EXT-X-VERSION:3
+#EXTINF:5,
+second.ts
+#EXT-X-ENDLIST
+#EXTINF:5,
+third.ts
+"#;
+
+        assert_eq!(
+            validate(data),
+            Err(vec![
+                ValidationError::HeaderTagAfterSegment("EXT-X-MEDIA-SEQUENCE".to_string()),
+                ValidationError::HeaderTagAfterSegment("EXT-X-VERSION".to_string()),
+                ValidationError::EndListNotLast,
+            ])
+        );
+    }
+
+    #[test]
+    fn test_validate_mixed_playlist_types() {
+        let media = r#"#EXTM3U
+#EXT-X-TARGETDURATION:10
+#EXT-X-STREAM-INF:BANDWIDTH=1280000
+low.m3u8
+#EXTINF:5,
+first.ts
+"#;
+        assert_eq!(
+            validate(media),
+            Err(vec![ValidationError::MasterTagInMediaPlaylist(
+                "EXT-X-STREAM-INF".to_string()
+            )])
+        );
+
+        let master = r#"#EXTM3U
+#EXT-X-MEDIA-SEQUENCE:1
+#EXT-X-STREAM-INF:BANDWIDTH=1280000
+low.m3u8
+"#;
+        assert_eq!(
+            validate(master),
+            Err(vec![ValidationError::MediaTagInMasterPlaylist(
+                "EXT-X-MEDIA-SEQUENCE".to_string()
+            )])
+        );
+    }
+
+    #[test]
+    fn test_validate_valid_master_playlist() {
+        let master = r#"#EXTM3U
+#EXT-X-INDEPENDENT-SEGMENTS
+#EXT-X-STREAM-INF:BANDWIDTH=1280000,CODECS="avc1.4d401f,mp4a.40.2",RESOLUTION=1280x720
+mid.m3u8
+#EXT-X-STREAM-INF:BANDWIDTH=2560000,CODECS="avc1.4d401f,mp4a.40.2",RESOLUTION=1920x1080
+high.m3u8
+"#;
+        assert_eq!(validate(master), Ok(()));
+    }
+
+    #[test]
+    fn test_parse_media_renditions() {
+        let data = r#"#EXTM3U
+#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID="aac",NAME="English",LANGUAGE="en",DEFAULT=YES,AUTOSELECT=YES,URI="en.m3u8"
+#EXT-X-MEDIA:TYPE=CLOSED-CAPTIONS,GROUP-ID="cc",NAME="CC1",INSTREAM-ID="CC1"
+#EXT-X-STREAM-INF:BANDWIDTH=1280000,AUDIO="aac",CLOSED-CAPTIONS="cc"
+mid.m3u8
+"#;
+        let playlist = Playlist::from_reader(data.as_bytes()).unwrap();
+
+        assert_eq!(
+            playlist.tags[1],
+            Tag::ExtXMedia {
+                type_: "AUDIO".to_string(),
+                group_id: "aac".to_string(),
+                name: Some("English".to_string()),
+                uri: Some("en.m3u8".to_string()),
+                default: Some(true),
+                autoplay: Some(true),
+                characteristics: None,
+                language: Some("en".to_string()),
+                instream_id: None,
+                language_codec: None,
+                forced: None,
+            }
+        );
+        assert_eq!(
+            playlist.tags[1].to_string(),
+            r#"#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID="aac",URI="en.m3u8",NAME="English",DEFAULT=YES,AUTOSELECT=YES,LANGUAGE="en""#
+        );
+        assert_eq!(playlist.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_undefined_media_groups() {
+        let data = r#"#EXTM3U
+#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID="aac",NAME="English"
+#EXT-X-MEDIA:TYPE=SUBTITLES,GROUP-ID="subs",NAME="English",URI="subs.m3u8"
+#EXT-X-STREAM-INF:BANDWIDTH=1280000,AUDIO="subs",SUBTITLES="subs",CLOSED-CAPTIONS=NONE
+mid.m3u8
+#EXT-X-STREAM-INF:BANDWIDTH=2560000,AUDIO="aac",VIDEO="cameras"
+high.m3u8
+"#;
+
+        assert_eq!(
+            validate(data),
+            Err(vec![
+                ValidationError::UndefinedMediaGroup {
+                    attribute: "AUDIO".to_string(),
+                    group_id: "subs".to_string(),
+                },
+                ValidationError::UndefinedMediaGroup {
+                    attribute: "VIDEO".to_string(),
+                    group_id: "cameras".to_string(),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_validate_rendition_groups() {
+        let data = r#"#EXTM3U
+#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID="aac",NAME="English",DEFAULT=YES,URI="en.m3u8"
+#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID="aac",NAME="English",DEFAULT=YES,URI="en2.m3u8"
+#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID="ac3",NAME="English",DEFAULT=YES,URI="en-ac3.m3u8"
+#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID="ac3",DEFAULT=YES,URI="unnamed.m3u8"
+#EXT-X-MEDIA:TYPE=CLOSED-CAPTIONS,GROUP-ID="cc",NAME="CC1",URI="cc.m3u8"
+#EXT-X-MEDIA:TYPE=CLOSED-CAPTIONS,GROUP-ID="cc2",URI="cc2.m3u8"
+#EXT-X-STREAM-INF:BANDWIDTH=1280000,AUDIO="aac",CLOSED-CAPTIONS="cc"
+mid.m3u8
+"#;
+
+        assert_eq!(
+            validate(data),
+            Err(vec![
+                ValidationError::DuplicateRenditionName {
+                    group_id: "aac".to_string(),
+                    name: "English".to_string(),
+                },
+                ValidationError::MultipleDefaultRenditions("aac".to_string()),
+                ValidationError::MissingMediaFields,
+                ValidationError::MultipleDefaultRenditions("ac3".to_string()),
+                ValidationError::MissingInstreamId("cc".to_string()),
+                ValidationError::ClosedCaptionsWithUri("cc".to_string()),
+                // Renditions without a NAME are still checked otherwise.
+                ValidationError::MissingMediaFields,
+                ValidationError::MissingInstreamId("cc2".to_string()),
+                ValidationError::ClosedCaptionsWithUri("cc2".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_minimum_version() {
+        let data = r#"#EXTM3U
+#EXT-X-VERSION:3
+#EXT-X-TARGETDURATION:10
+#EXT-X-KEY:METHOD=AES-128,URI="key.bin",IV=0x1234
+#EXTINF:10,
+first.ts
+#EXT-X-BYTERANGE:1000@0
+#EXTINF:9.5,
+second.ts
+"#;
+        let playlist = Playlist::from_reader(data.as_bytes()).unwrap();
+
+        assert_eq!(playlist.declared_version(), Some(3));
+        assert_eq!(playlist.minimum_version(), 4);
+        assert_eq!(
+            playlist.validate(),
+            Err(vec![ValidationError::VersionTooLow {
+                declared: 3,
+                required: 4,
+                feature: "EXT-X-BYTERANGE".to_string(),
+            }])
+        );
+
+        let empty = Playlist::from_reader("#EXTM3U\n".as_bytes()).unwrap();
+        assert_eq!(empty.minimum_version(), 1);
+    }
+
+    #[test]
+    fn test_validate_version_missing() {
+        let data = r#"#EXTM3U
+#EXT-X-TARGETDURATION:10
+#EXT-X-MAP:URI="init.mp4"
+#EXTINF:9.5,
+first.ts
+"#;
+
+        assert_eq!(
+            validate(data),
+            Err(vec![
+                ValidationError::VersionTooLow {
+                    declared: 1,
+                    required: 6,
+                    feature: "EXT-X-MAP".to_string(),
+                },
+                ValidationError::VersionTooLow {
+                    declared: 1,
+                    required: 3,
+                    feature: "floating-point EXTINF duration".to_string(),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_builder_auto_version() {
+        let playlist = PlaylistBuilder::new()
+            .extm3u()
+            .version(2)
+            .target_duration(10)
+            .map("init.mp4", None)
+            .extinf("https://media.example.com/first.ts", 9.5, None)
+            .end_list()
+            .auto_version()
+            .build()
+            .unwrap();
+
+        assert_eq!(playlist.tags[1], Tag::ExtXVersion(6));
+        assert_eq!(
+            playlist
+                .tags
+                .iter()
+                .filter(|tag| matches!(tag, Tag::ExtXVersion(_)))
+                .count(),
+            1
+        );
+
+        let playlist = PlaylistBuilder::new()
+            .extm3u()
+            .target_duration(10)
+            .extinf("https://media.example.com/first.ts", 10, None)
+            .auto_version()
+            .build()
+            .unwrap();
+        assert_eq!(playlist.tags[1], Tag::ExtXVersion(1));
+    }
+
+    #[test]
+    fn test_validator_reports_version_zero() {
+        let playlist = Playlist::from_reader(
+            "#EXTM3U\n#EXT-X-VERSION:0\n#EXT-X-TARGETDURATION:4\n#EXTINF:4,\na.ts\n".as_bytes(),
+        )
+        .unwrap();
+
+        for profile in [Profile::Rfc8216, Profile::Rfc8216bis, Profile::Lenient] {
+            let report = Validator::new(profile).validate(&playlist);
+            assert!(report
+                .diagnostics
+                .iter()
+                .any(|diagnostic| diagnostic.error == ValidationError::InvalidVersion(0)));
+        }
+    }
+
+    #[test]
+    fn test_validator_profiles() {
+        let data = r#"#EXTM3U
+#EXT-X-VERSION:9
+#EXT-X-TARGETDURATION:4
+#EXTINF:5,
+first.ts
+#EXT-X-GAP
+#EXTINF:4,
+second.ts
+"#;
+        let playlist = Playlist::from_reader(data.as_bytes()).unwrap();
+
+        let report = Validator::new(Profile::Rfc8216).validate(&playlist);
+        let rules: Vec<(Rule, Severity)> = report
+            .diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.rule, diagnostic.severity))
+            .collect();
+        assert_eq!(
+            rules,
+            vec![
+                (Rule::InvalidVersion, Severity::Error),
+                (Rule::SegmentDurationExceedsTarget, Severity::Error),
+                (Rule::UnsupportedTag, Severity::Warning),
+            ]
+        );
+
+        let report = Validator::new(Profile::Rfc8216bis).validate(&playlist);
+        assert_eq!(report.diagnostics.len(), 1);
+        assert_eq!(
+            report.diagnostics[0].rule,
+            Rule::SegmentDurationExceedsTarget
+        );
+        assert!(report.has_errors());
+
+        let report = Validator::new(Profile::Lenient).validate(&playlist);
+        assert!(!report.has_errors());
+        assert_eq!(report.with_severity(Severity::Warning).count(), 1);
+        assert_eq!(report.into_result(), Ok(()));
+    }
+
+    #[test]
+    fn test_validator_rule_overrides() {
+        let data = r#"#EXTM3U
+#EXT-X-TARGETDURATION:4
+#EXTINF:5,
+first.ts
+#EXT-X-ENDLIST
+#EXT-X-ENDLIST
+"#;
+        let playlist = Playlist::from_reader(data.as_bytes()).unwrap();
+        let validator = Validator::default()
+            .disable(Rule::DuplicateTag)
+            .rule_severity(Rule::SegmentDurationExceedsTarget, Severity::Info);
+
+        assert_eq!(validator.severity(Rule::DuplicateTag), None);
+        assert_eq!(
+            validator.severity(Rule::EndListNotLast),
+            Some(Severity::Error)
+        );
+
+        let report = validator.validate(&playlist);
+        assert_eq!(
+            report.into_result(),
+            Err(vec![ValidationError::EndListNotLast])
+        );
+        assert_eq!(
+            Rule::SegmentDurationExceedsTarget.as_str(),
+            "segment-duration-exceeds-target"
+        );
+        assert_eq!(ValidationError::EndListNotLast.rule(), Rule::EndListNotLast);
+    }
+
+    #[test]
+    fn test_validator_authoring_rules() {
+        let data = r#"#EXTM3U
+#EXT-X-STREAM-INF:BANDWIDTH=800000,AVERAGE-BANDWIDTH=700000,CODECS="avc1.4d401f,mp4a.40.2",RESOLUTION=640x360,FRAME-RATE=30
+low.m3u8
+#EXT-X-STREAM-INF:BANDWIDTH=2000000,AVERAGE-BANDWIDTH=900000,CODECS="avc1.4d401f,mp4a.40.2",RESOLUTION=1280x720
+mid.m3u8
+#EXT-X-STREAM-INF:BANDWIDTH=3000000,RESOLUTION=1920x1080,FRAME-RATE=30
+high.m3u8
+"#;
+        let playlist = Playlist::from_reader(data.as_bytes()).unwrap();
+
+        assert_eq!(playlist.validate(), Ok(()));
+        assert!(Validator::new(Profile::Rfc8216bis)
+            .validate(&playlist)
+            .diagnostics
+            .is_empty());
+
+        let report = Validator::new(Profile::AppleHlsAuthoring).validate(&playlist);
+        assert!(!report.has_errors());
+        assert!(report
+            .diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.severity == Severity::Warning));
+        let errors: Vec<&ValidationError> = report
+            .diagnostics
+            .iter()
+            .map(|diagnostic| &diagnostic.error)
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                &ValidationError::PeakToAverageRatio {
+                    bandwidth: 2000000,
+                    average_bandwidth: 900000,
+                },
+                &ValidationError::MissingFrameRate(2000000),
+                &ValidationError::MissingAverageBandwidth(3000000),
+                &ValidationError::MissingCodecs(3000000),
+                &ValidationError::MissingIndependentSegments,
+                &ValidationError::MissingIFrameVariants,
+                &ValidationError::BitrateLadderGap {
+                    lower: 800000,
+                    higher: 2000000,
+                },
+            ]
+        );
+
+        let media = Playlist::from_reader(
+            "#EXTM3U\n#EXT-X-TARGETDURATION:10\n#EXTINF:10,\nfirst.ts\n".as_bytes(),
+        )
+        .unwrap();
+        let report = Validator::new(Profile::AppleHlsAuthoring).validate(&media);
+        assert_eq!(report.diagnostics.len(), 1);
+        assert_eq!(report.diagnostics[0].rule, Rule::AuthoringTargetDuration);
+
+        // Rungs closer than a factor of 1.5 are reported as well.
+        let dense = Playlist::from_reader(
+            "#EXTM3U\n#EXT-X-INDEPENDENT-SEGMENTS\n\
+             #EXT-X-STREAM-INF:BANDWIDTH=1000000,AVERAGE-BANDWIDTH=900000,CODECS=\"mp4a.40.2\"\nlow.m3u8\n\
+             #EXT-X-STREAM-INF:BANDWIDTH=1200000,AVERAGE-BANDWIDTH=1100000,CODECS=\"mp4a.40.2\"\nhigh.m3u8\n"
+                .as_bytes(),
+        )
+        .unwrap();
+        let report = Validator::new(Profile::AppleHlsAuthoring).validate(&dense);
+        assert_eq!(report.diagnostics.len(), 1);
+        assert_eq!(report.diagnostics[0].rule, Rule::AuthoringBitrateLadder);
+        assert_eq!(
+            report.diagnostics[0].error,
+            ValidationError::BitrateLadderStep {
+                lower: 1000000,
+                higher: 1200000,
+            }
+        );
+    }
+
+    #[test]
+    fn test_diagnostics_source_location() {
+        let source =
+            "#EXTM3U\n\n#EXT-X-VERSION:3\n#EXTINF:5,\nfirst.ts\n# comment\n#EXT-X-VERSION:3\n";
+        let (playlist, source_map) = Playlist::from_reader_with_source(source.as_bytes()).unwrap();
+        assert_eq!(source_map.line_number(2), Some(4));
+        assert_eq!(source_map.line_number(3), Some(7));
+        assert_eq!(Playlist::from_reader(source.as_bytes()).unwrap(), playlist);
+
+        let report = Validator::default().validate(&playlist).locate(&source_map);
+        let locations: Vec<(Rule, Option<usize>, Option<usize>)> = report
+            .diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.rule, diagnostic.tag_index, diagnostic.line))
+            .collect();
+        assert_eq!(
+            locations,
+            vec![
+                (Rule::DuplicateTag, Some(3), Some(7)),
+                (Rule::HeaderTagAfterSegment, Some(3), Some(7)),
+                (Rule::MissingTargetDuration, None, None),
+            ]
+        );
+        assert_eq!(
+            report.diagnostics[0].to_string(),
+            "error[duplicate-tag]: EXT-X-VERSION appears more than once (line 7)"
+        );
+        assert_eq!(
+            report.render(source),
+            "error[duplicate-tag]: EXT-X-VERSION appears more than once
+ --> line 7
+  |
+7 | #EXT-X-VERSION:3
+  | ^^^^^^^^^^^^^^^^
+  = note: this tag may appear at most once per playlist
+
+error[header-tag-after-segment]: EXT-X-VERSION appears after the first media segment
+ --> line 7
+  |
+7 | #EXT-X-VERSION:3
+  | ^^^^^^^^^^^^^^^^
+  = note: playlist tags must appear before the first media segment
+
+error[missing-target-duration]: media playlist has no EXT-X-TARGETDURATION
+ = note: media playlists must have an EXT-X-TARGETDURATION tag
+"
+        );
+    }
+
+    #[test]
+    fn test_validation_error_display() {
+        let error: Box<dyn std::error::Error> = Box::new(ValidationError::UndefinedMediaGroup {
+            attribute: "AUDIO".to_string(),
+            group_id: "aac".to_string(),
+        });
+        assert_eq!(
+            error.to_string(),
+            "AUDIO references undefined rendition group \"aac\""
+        );
+        assert_eq!(
+            ValidationError::InvalidDuration("-1.5".parse().unwrap()).to_string(),
+            "segment duration -1.5 is not positive"
+        );
+    }
+
+    #[test]
+    fn test_validate_low_latency_playlist() {
+        let data = r#"#EXTM3U
+#EXT-X-VERSION:6
+#EXT-X-TARGETDURATION:4
+#EXT-X-SERVER-CONTROL:HOLD-BACK=12,PART-HOLD-BACK=1.002,CAN-BLOCK-RELOAD=YES
+#EXT-X-PART-INF:PART-TARGET=0.5
+#EXT-X-PART:URI="part1.0.mp4",DURATION=0.5
+#EXT-X-PART:URI="part1.1.mp4",DURATION=0.5
+#EXTINF:1.0,
+segment1.mp4
+#EXT-X-PART:URI="part2.0.mp4",DURATION=0.5
+#EXT-X-PRELOAD-HINT:TYPE=PART,URI="part2.1.mp4"
+#EXT-X-RENDITION-REPORT:URI="../low/live.m3u8",LAST-MSN=2,LAST-PART=0
+"#;
+        let playlist = Playlist::from_reader(data.as_bytes()).unwrap();
+        assert_eq!(playlist.validate(), Ok(()));
+        assert_eq!(playlist.to_string(), data);
+    }
+
+    #[test]
+    fn test_validate_low_latency_rules() {
+        let data = r#"#EXTM3U
+#EXT-X-VERSION:6
+#EXT-X-TARGETDURATION:4
+#EXT-X-SERVER-CONTROL:PART-HOLD-BACK=0.9,HOLD-BACK=8
+#EXT-X-PART-INF:PART-TARGET=0.5
+#EXT-X-PART:DURATION=0.5,URI="part1.0.mp4"
+#EXT-X-PART:DURATION=0.6,URI="part1.1.mp4"
+#EXTINF:1.0,
+segment1.mp4
+#EXT-X-PRELOAD-HINT:TYPE=PART,URI="part2.0.mp4"
+#EXT-X-PART:DURATION=0.5,URI="part2.0.mp4"
+"#;
+
+        assert_eq!(
+            validate(data),
+            Err(vec![
+                ValidationError::PartExceedsPartTarget {
+                    uri: "part1.1.mp4".to_string(),
+                    duration: "0.6".parse().unwrap(),
+                    part_target: "0.5".parse().unwrap(),
+                },
+                ValidationError::PartDurationMismatch {
+                    uri: "segment1.mp4".to_string(),
+                    duration: "1.0".parse().unwrap(),
+                    parts: "1.1".parse().unwrap(),
+                },
+                ValidationError::PreloadHintNotLast,
+                ValidationError::HoldBackTooShort {
+                    hold_back: "8".parse().unwrap(),
+                    minimum: "12".parse().unwrap(),
+                },
+                ValidationError::MissingCanBlockReload,
+                ValidationError::PartHoldBackTooShort {
+                    part_hold_back: "0.9".parse().unwrap(),
+                    minimum: "1.0".parse().unwrap(),
+                },
+            ])
+        );
+
+        let missing = "#EXTM3U\n#EXT-X-TARGETDURATION:4\n#EXT-X-PART:DURATION=0.5,URI=\"p.mp4\"\n";
+        assert_eq!(
+            validate(missing),
+            Err(vec![ValidationError::MissingPartInf])
+        );
+    }
+
+    #[test]
+    fn test_validate_rendition_reports() {
+        let master = Playlist::from_reader(
+            r#"#EXTM3U
+#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID="aac",NAME="English",URI="audio/en.m3u8"
+#EXT-X-STREAM-INF:BANDWIDTH=1000000,AUDIO="aac"
+low/live.m3u8
+#EXT-X-STREAM-INF:BANDWIDTH=2000000,AUDIO="aac"
+high/live.m3u8?token=1
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+        let media = Playlist::from_reader(
+            r#"#EXTM3U
+#EXT-X-RENDITION-REPORT:URI="../high/live.m3u8",LAST-MSN=2
+#EXT-X-RENDITION-REPORT:URI="../audio/en.m3u8",LAST-MSN=2
+#EXT-X-RENDITION-REPORT:URI="live.m3u8",LAST-MSN=2
+#EXT-X-RENDITION-REPORT:URI="../medium/live.m3u8",LAST-MSN=2
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            media.validate_rendition_reports("low/live.m3u8", &master),
+            Err(vec![
+                ValidationError::UndefinedRenditionReport("live.m3u8".to_string()),
+                ValidationError::UndefinedRenditionReport("../medium/live.m3u8".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_validate_update() {
+        let parse = |data: &str| Playlist::from_reader(data.as_bytes()).unwrap();
+        let previous = parse(
+            "#EXTM3U\n#EXT-X-TARGETDURATION:4\n#EXT-X-MEDIA-SEQUENCE:10\n\
+             #EXT-X-DISCONTINUITY-SEQUENCE:2\n#EXT-X-DISCONTINUITY\n#EXTINF:4,\na.ts\n\
+             #EXTINF:4,\nb.ts\n#EXTINF:4,\nc.ts\n",
+        );
+
+        let next = parse(
+            "#EXTM3U\n#EXT-X-TARGETDURATION:4\n#EXT-X-MEDIA-SEQUENCE:11\n\
+             #EXT-X-DISCONTINUITY-SEQUENCE:3\n#EXTINF:4,\nb.ts\n#EXTINF:4,\nc.ts\n\
+             #EXTINF:4,\nd.ts\n#EXT-X-ENDLIST\n",
+        );
+        assert_eq!(Playlist::validate_update(&previous, &next), Ok(()));
+
+        let (changed, source) = Playlist::from_reader_with_source(
+            "#EXTM3U\n#EXT-X-TARGETDURATION:4\n#EXT-X-MEDIA-SEQUENCE:11\n\
+             #EXT-X-DISCONTINUITY-SEQUENCE:2\n#EXTINF:4,\nb.ts\n#EXTINF:3,\nc.ts\n"
+                .as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(
+            Playlist::validate_update(&previous, &changed),
+            Err(vec![
+                ValidationError::SegmentChanged {
+                    media_sequence: 12,
+                    previous: "c.ts (4s)".to_string(),
+                    next: "c.ts (3s)".to_string(),
+                },
+                ValidationError::DiscontinuitySequenceMismatch {
+                    expected: 3,
+                    found: 2,
+                },
+            ])
+        );
+
+        assert_eq!(
+            Playlist::validate_update(&next, &previous),
+            Err(vec![
+                ValidationError::EndListRemoved,
+                ValidationError::MediaSequenceDecreased {
+                    previous: 11,
+                    next: 10,
+                },
+            ])
+        );
+
+        let report = Validator::default().validate_update(&previous, &changed);
+        assert_eq!(report.diagnostics[0].line, None);
+        let report = report.locate(&source);
+        assert_eq!(report.diagnostics.len(), 2);
+        assert_eq!(report.diagnostics[0].rule, Rule::SegmentChanged);
+        assert_eq!(report.diagnostics[0].line, Some(7));
+        assert_eq!(report.diagnostics[1].line, Some(4));
+    }
+
+    #[test]
+    fn test_validate_byte_ranges() {
+        let data = "\
+#EXTM3U
+#EXT-X-VERSION:4
+#EXT-X-TARGETDURATION:4
+#EXTINF:4,
+#EXT-X-BYTERANGE:1000
+a.ts
+#EXTINF:4,
+#EXT-X-BYTERANGE:1000@0
+a.ts
+#EXTINF:4,
+#EXT-X-BYTERANGE:500
+a.ts
+#EXTINF:4,
+#EXT-X-BYTERANGE:500
+b.ts
+#EXTINF:4,
+#EXT-X-BYTERANGE:10@18446744073709551615
+b.ts
+";
+        let playlist = Playlist::from_reader(data.as_bytes()).unwrap();
+        assert_eq!(
+            playlist.byte_ranges(),
+            vec![
+                None,
+                Some(ByteRange::new(1000, Some(0))),
+                Some(ByteRange::new(500, Some(1000))),
+                None,
+                Some(ByteRange::new(10, Some(u64::MAX))),
+            ]
+        );
+        assert_eq!(
+            playlist.validate(),
+            Err(vec![
+                ValidationError::ImplicitByteRangeOffset("a.ts".to_string()),
+                ValidationError::ImplicitByteRangeOffset("b.ts".to_string()),
+                ValidationError::InvalidByteRange("10@18446744073709551615".to_string()),
+            ])
+        );
+
+        // The byte range is written before the EXTINF and read back unchanged.
+        let written = Playlist::from_reader(playlist.to_string().as_bytes()).unwrap();
+        assert_eq!(written, playlist);
+        assert_eq!(
+            written.segments()[1].byte_range,
+            Some(ByteRange::new(1000, Some(0)))
+        );
+    }
+
+    #[test]
+    fn test_validate_malformed_byte_ranges() {
+        let data = r#"#EXTM3U
+#EXT-X-VERSION:4
+#EXT-X-TARGETDURATION:4
+#EXT-X-MAP:URI="init.mp4",BYTERANGE="x@y"
+#EXTINF:4,
+#EXT-X-BYTERANGE:abc
+a.ts
+#EXTINF:4,
+#EXT-X-BYTERANGE:500@0
+b.ts
+"#;
+        let playlist = Playlist::from_reader(data.as_bytes()).unwrap();
+        assert_eq!(
+            playlist.tags[3],
+            Tag::MalformedByteRange {
+                line: r#"EXT-X-MAP:URI="init.mp4",BYTERANGE="x@y""#.to_string(),
+                byte_range: "x@y".to_string(),
+            }
+        );
+        assert_eq!(playlist.tags[4].name(), "EXT-X-BYTERANGE");
+        assert_eq!(
+            playlist.validate(),
+            Err(vec![
+                ValidationError::InvalidByteRange("x@y".to_string()),
+                ValidationError::InvalidByteRange("abc".to_string()),
+            ])
+        );
+
+        // Malformed tags are written back as they were read.
+        let written = Playlist::from_reader(playlist.to_string().as_bytes()).unwrap();
+        assert_eq!(written, playlist);
+    }
+}
@@ -0,0 +1,62 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// A sub-range of a resource, as used by `EXT-X-BYTERANGE` and the
+/// `BYTERANGE` attribute of `EXT-X-MAP`.
+///
+/// The textual form is `<length>[@<offset>]`. When the offset is omitted the
+/// sub-range begins at the next byte following the previous sub-range of the
+/// same resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ByteRange {
+    /// Length of the sub-range in bytes.
+    pub length: u64,
+    /// Start of the sub-range, as a byte offset from the beginning of the resource.
+    pub offset: Option<u64>,
+}
+
+impl ByteRange {
+    /// Creates a new `ByteRange`.
+    pub fn new(length: u64, offset: Option<u64>) -> Self {
+        Self { length, offset }
+    }
+}
+
+impl FromStr for ByteRange {
+    type Err = String;
+
+    /// Parses a byte range of the form `<length>[@<offset>]`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use m3u8_parser::m3u8::types::ByteRange;
+    /// let range: ByteRange = "500@1000".parse().unwrap();
+    /// assert_eq!(range, ByteRange::new(500, Some(1000)));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (length, offset) = match s.split_once('@') {
+            Some((length, offset)) => (length, Some(offset)),
+            None => (s, None),
+        };
+        let length = length
+            .parse()
+            .map_err(|_| format!("Invalid byte range length: {}", s))?;
+        let offset = offset
+            .map(|offset| offset.parse())
+            .transpose()
+            .map_err(|_| format!("Invalid byte range offset: {}", s))?;
+        Ok(ByteRange { length, offset })
+    }
+}
+
+impl fmt::Display for ByteRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.length)?;
+        if let Some(offset) = self.offset {
+            write!(f, "@{}", offset)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,304 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// The broad media type a codec belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CodecType {
+    Video,
+    Audio,
+    Subtitles,
+    Unknown,
+}
+
+/// Parameters decoded from an RFC 6381 codec identifier.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CodecInfo {
+    /// H.264 (`avc1`, `avc3`), e.g. `avc1.64001f`.
+    Avc {
+        profile: u8,
+        constraints: u8,
+        level: u8,
+    },
+    /// H.265 (`hvc1`, `hev1`), e.g. `hvc1.2.4.L153.B0`.
+    Hevc {
+        profile_space: Option<char>,
+        profile: u8,
+        compatibility: u32,
+        tier: char,
+        level: u8,
+    },
+    /// AV1 (`av01`), e.g. `av01.0.04M.08`.
+    Av1 {
+        profile: u8,
+        level: u8,
+        tier: char,
+        bit_depth: u8,
+    },
+    /// MPEG-4 audio (`mp4a`), e.g. `mp4a.40.2`.
+    Mp4a {
+        object_type: u8,
+        audio_object_type: Option<u8>,
+    },
+    /// AC-3 (`ac-3`).
+    Ac3,
+    /// Enhanced AC-3 (`ec-3`).
+    Ec3,
+    /// TTML subtitles carried in ISOBMFF (`stpp`), e.g. `stpp.ttml.im1t`.
+    Stpp { profile: Option<String> },
+    /// WebVTT subtitles carried in ISOBMFF (`wvtt`).
+    Wvtt,
+    /// A codec whose parameters are not decoded.
+    Other,
+}
+
+/// A single RFC 6381 codec identifier, such as `avc1.64001f` or `mp4a.40.2`.
+///
+/// The original text is kept so that the identifier is written back exactly as
+/// it was read.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Codec {
+    value: String,
+    info: CodecInfo,
+}
+
+impl Codec {
+    /// Returns the codec identifier as written in the playlist.
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns the sample entry (the part before the first `.`), e.g. `avc1`.
+    pub fn sample_entry(&self) -> &str {
+        self.value.split('.').next().unwrap_or_default()
+    }
+
+    /// Returns the decoded codec parameters.
+    pub fn info(&self) -> &CodecInfo {
+        &self.info
+    }
+
+    /// Returns the media type of the codec.
+    pub fn codec_type(&self) -> CodecType {
+        match self.sample_entry().to_ascii_lowercase().as_str() {
+            "avc1" | "avc3" | "hvc1" | "hev1" | "av01" | "vp09" | "vp08" | "dvh1" | "dvhe"
+            | "dva1" | "dvav" => CodecType::Video,
+            "mp4a" | "ac-3" | "ec-3" | "ac-4" | "opus" | "flac" | "alac" => CodecType::Audio,
+            "stpp" | "wvtt" => CodecType::Subtitles,
+            _ => CodecType::Unknown,
+        }
+    }
+
+    /// Returns `true` if the codec is a video codec.
+    pub fn is_video(&self) -> bool {
+        self.codec_type() == CodecType::Video
+    }
+
+    /// Returns `true` if the codec is an audio codec.
+    pub fn is_audio(&self) -> bool {
+        self.codec_type() == CodecType::Audio
+    }
+}
+
+impl FromStr for Codec {
+    type Err = String;
+
+    /// Parses a single codec identifier, decoding the parameters of the
+    /// well-known sample entries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use m3u8_parser::m3u8::types::{Codec, CodecInfo};
+    /// let codec: Codec = "mp4a.40.2".parse().unwrap();
+    /// assert_eq!(
+    ///     codec.info(),
+    ///     &CodecInfo::Mp4a { object_type: 0x40, audio_object_type: Some(2) }
+    /// );
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim();
+        if value.is_empty() {
+            return Err("Empty codec identifier".to_string());
+        }
+
+        let mut parts = value.split('.');
+        let sample_entry = parts.next().unwrap_or_default();
+        let params: Vec<&str> = parts.collect();
+        let invalid = || format!("Invalid codec identifier: {}", value);
+
+        let info = match sample_entry.to_ascii_lowercase().as_str() {
+            // Identifiers other than the RFC 6381 hexadecimal form, such as the
+            // deprecated decimal `avc1.77.30`, are kept undecoded.
+            "avc1" | "avc3" => {
+                let byte = |i: usize| {
+                    let params = params.first().filter(|params| params.len() == 6)?;
+                    u8::from_str_radix(params.get(i..i + 2)?, 16).ok()
+                };
+                match (byte(0), byte(2), byte(4)) {
+                    (Some(profile), Some(constraints), Some(level)) => CodecInfo::Avc {
+                        profile,
+                        constraints,
+                        level,
+                    },
+                    _ => CodecInfo::Other,
+                }
+            }
+            "hvc1" | "hev1" => {
+                if params.len() < 3 {
+                    return Err(invalid());
+                }
+                let (profile_space, profile) = match params[0].chars().next() {
+                    Some(c @ ('A' | 'B' | 'C')) => (Some(c), &params[0][1..]),
+                    _ => (None, params[0]),
+                };
+                let mut tier_level = params[2].chars();
+                let tier = tier_level.next().ok_or_else(invalid)?;
+                if tier != 'L' && tier != 'H' {
+                    return Err(invalid());
+                }
+                CodecInfo::Hevc {
+                    profile_space,
+                    profile: profile.parse().map_err(|_| invalid())?,
+                    compatibility: u32::from_str_radix(params[1], 16).map_err(|_| invalid())?,
+                    tier,
+                    level: tier_level.as_str().parse().map_err(|_| invalid())?,
+                }
+            }
+            "av01" => {
+                if params.len() < 3 || params[1].len() != 3 {
+                    return Err(invalid());
+                }
+                let tier = params[1].chars().nth(2).ok_or_else(invalid)?;
+                if tier != 'M' && tier != 'H' {
+                    return Err(invalid());
+                }
+                CodecInfo::Av1 {
+                    profile: params[0].parse().map_err(|_| invalid())?,
+                    level: params[1][..2].parse().map_err(|_| invalid())?,
+                    tier,
+                    bit_depth: params[2].parse().map_err(|_| invalid())?,
+                }
+            }
+            "mp4a" => {
+                let object_type = params.first().ok_or_else(invalid)?;
+                CodecInfo::Mp4a {
+                    object_type: u8::from_str_radix(object_type, 16).map_err(|_| invalid())?,
+                    audio_object_type: params
+                        .get(1)
+                        .map(|aot| aot.parse())
+                        .transpose()
+                        .map_err(|_| invalid())?,
+                }
+            }
+            "ac-3" => CodecInfo::Ac3,
+            "ec-3" => CodecInfo::Ec3,
+            "stpp" => CodecInfo::Stpp {
+                profile: if params.is_empty() {
+                    None
+                } else {
+                    Some(params.join("."))
+                },
+            },
+            "wvtt" => CodecInfo::Wvtt,
+            _ => CodecInfo::Other,
+        };
+
+        Ok(Codec {
+            value: value.to_string(),
+            info,
+        })
+    }
+}
+
+impl fmt::Display for Codec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+/// The `CODECS` attribute of a variant stream: a comma-separated list of
+/// RFC 6381 codec identifiers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Codecs(Vec<Codec>);
+
+impl Codecs {
+    /// Creates a new `Codecs` list.
+    pub fn new(codecs: Vec<Codec>) -> Self {
+        Self(codecs)
+    }
+
+    /// Returns an iterator over the codecs.
+    pub fn iter(&self) -> std::slice::Iter<'_, Codec> {
+        self.0.iter()
+    }
+
+    /// Returns the number of codecs in the list.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the list contains no codecs.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns `true` if any codec in the list uses the given sample entry
+    /// (compared case-insensitively), e.g. `"hvc1"`.
+    pub fn contains(&self, sample_entry: &str) -> bool {
+        self.0
+            .iter()
+            .any(|codec| codec.sample_entry().eq_ignore_ascii_case(sample_entry))
+    }
+
+    /// Returns `true` if the list contains a video codec.
+    pub fn has_video(&self) -> bool {
+        self.0.iter().any(Codec::is_video)
+    }
+
+    /// Returns `true` if the list contains an audio codec.
+    pub fn has_audio(&self) -> bool {
+        self.0.iter().any(Codec::is_audio)
+    }
+}
+
+impl<'a> IntoIterator for &'a Codecs {
+    type Item = &'a Codec;
+    type IntoIter = std::slice::Iter<'a, Codec>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl FromStr for Codecs {
+    type Err = String;
+
+    /// Parses a comma-separated list of codec identifiers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use m3u8_parser::m3u8::types::Codecs;
+    /// let codecs: Codecs = "avc1.64001f,mp4a.40.2".parse().unwrap();
+    /// assert_eq!(codecs.len(), 2);
+    /// assert!(codecs.has_video() && codecs.has_audio());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(str::parse)
+            .collect::<Result<Vec<_>, _>>()
+            .map(Codecs)
+    }
+}
+
+impl fmt::Display for Codecs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, codec) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}", codec)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,290 @@
+use crate::m3u8::types::DecimalDuration;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+const MICROS_PER_SECOND: i64 = 1_000_000;
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// A timezone-aware ISO 8601 timestamp, as used by `EXT-X-PROGRAM-DATE-TIME`.
+///
+/// The instant is stored as microseconds since the Unix epoch together with the
+/// UTC offset it was written in, so that it is displayed in the original
+/// timezone. Equality, ordering and hashing compare the instant only:
+/// `2024-01-01T01:00:00+01:00` and `2024-01-01T00:00:00Z` are equal.
+///
+/// # Example
+///
+/// ```
+/// use m3u8_parser::m3u8::types::DateTime;
+/// let date_time: DateTime = "2024-11-05T12:00:00.500+02:00".parse().unwrap();
+/// assert_eq!(date_time, "2024-11-05T10:00:00.5Z".parse().unwrap());
+/// assert_eq!(date_time.to_string(), "2024-11-05T12:00:00.500+02:00");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct DateTime {
+    unix_micros: i64,
+    offset_minutes: i16,
+    fraction_digits: u8,
+}
+
+impl DateTime {
+    /// Creates a `DateTime` from microseconds since the Unix epoch, displayed
+    /// with the given UTC offset in minutes.
+    pub fn from_unix_micros(unix_micros: i64, offset_minutes: i16) -> Self {
+        Self {
+            unix_micros,
+            offset_minutes,
+            fraction_digits: fraction_digits(unix_micros),
+        }
+    }
+
+    /// Returns the instant as microseconds since the Unix epoch.
+    pub fn unix_micros(&self) -> i64 {
+        self.unix_micros
+    }
+
+    /// Returns the UTC offset, in minutes, the timestamp is displayed with.
+    pub fn offset_minutes(&self) -> i16 {
+        self.offset_minutes
+    }
+
+    /// Returns the timestamp advanced by `duration`, keeping its UTC offset.
+    pub fn add_duration(&self, duration: DecimalDuration) -> Self {
+        let unix_micros = self.unix_micros + duration.as_micros();
+        Self {
+            unix_micros,
+            offset_minutes: self.offset_minutes,
+            fraction_digits: self.fraction_digits.max(fraction_digits(unix_micros)),
+        }
+    }
+
+    /// Returns the signed duration from `earlier` to `self`.
+    pub fn duration_since(&self, earlier: &DateTime) -> DecimalDuration {
+        DecimalDuration::from_micros(self.unix_micros - earlier.unix_micros)
+    }
+}
+
+/// Returns the fewest fractional second digits that represent `micros` exactly.
+fn fraction_digits(micros: i64) -> u8 {
+    let mut fraction = micros.rem_euclid(MICROS_PER_SECOND);
+    if fraction == 0 {
+        return 0;
+    }
+    let mut digits = 6;
+    while fraction % 10 == 0 {
+        fraction /= 10;
+        digits -= 1;
+    }
+    digits
+}
+
+/// Returns the number of days since 1970-01-01 for a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Returns the proleptic Gregorian `(year, month, day)` for days since 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl PartialEq for DateTime {
+    fn eq(&self, other: &Self) -> bool {
+        self.unix_micros == other.unix_micros
+    }
+}
+
+impl Eq for DateTime {}
+
+impl Hash for DateTime {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.unix_micros.hash(state);
+    }
+}
+
+impl PartialOrd for DateTime {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DateTime {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.unix_micros.cmp(&other.unix_micros)
+    }
+}
+
+impl FromStr for DateTime {
+    type Err = String;
+
+    /// Parses an ISO 8601 date-time of the form
+    /// `YYYY-MM-DDThh:mm:ss[.fraction](Z|±hh:mm|±hhmm|±hh)`.
+    ///
+    /// A time zone designator is required. Fractional seconds beyond
+    /// microseconds are truncated.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid ISO 8601 date-time: {}", s);
+        let text = s.trim();
+        let bytes = text.as_bytes();
+        let number = |range: std::ops::Range<usize>| -> Result<u32, String> {
+            let digits = text.get(range).ok_or_else(invalid)?;
+            if !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            digits.parse().map_err(|_| invalid())
+        };
+
+        if bytes.len() < 20
+            || bytes[4] != b'-'
+            || bytes[7] != b'-'
+            || !matches!(bytes[10], b'T' | b't')
+            || bytes[13] != b':'
+            || bytes[16] != b':'
+        {
+            return Err(invalid());
+        }
+
+        let year = i64::from(number(0..4)?);
+        let month = number(5..7)?;
+        let day = number(8..10)?;
+        let hour = number(11..13)?;
+        let minute = number(14..16)?;
+        let second = number(17..19)?;
+        if !(1..=12).contains(&month)
+            || day == 0
+            || day > days_in_month(year, month)
+            || hour > 23
+            || minute > 59
+            || second > 59
+        {
+            return Err(invalid());
+        }
+
+        let mut rest = &text[19..];
+        let mut micros = 0;
+        let mut digits = 0;
+        if let Some(fraction) = rest.strip_prefix('.') {
+            let end = fraction
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(fraction.len());
+            if end == 0 {
+                return Err(invalid());
+            }
+            for digit in fraction[..end].bytes().take(6) {
+                micros = micros * 10 + i64::from(digit - b'0');
+                digits += 1;
+            }
+            micros *= 10_i64.pow(6 - u32::from(digits));
+            rest = &fraction[end..];
+        }
+
+        let offset_minutes: i16 = match rest {
+            "Z" | "z" => 0,
+            _ => {
+                let sign = match rest.as_bytes().first() {
+                    Some(b'+') => 1,
+                    Some(b'-') => -1,
+                    _ => return Err(invalid()),
+                };
+                let offset = rest[1..].replace(':', "");
+                if !offset.bytes().all(|b| b.is_ascii_digit())
+                    || !(offset.len() == 2 || offset.len() == 4)
+                {
+                    return Err(invalid());
+                }
+                let hours: i16 = offset[..2].parse().map_err(|_| invalid())?;
+                let minutes: i16 = offset
+                    .get(2..)
+                    .filter(|m| !m.is_empty())
+                    .map(|m| m.parse())
+                    .transpose()
+                    .map_err(|_| invalid())?
+                    .unwrap_or(0);
+                if hours > 23 || minutes > 59 {
+                    return Err(invalid());
+                }
+                sign * (hours * 60 + minutes)
+            }
+        };
+
+        let local_seconds = days_from_civil(year, month, day) * SECONDS_PER_DAY
+            + i64::from(hour * 3600 + minute * 60 + second);
+        let utc_seconds = local_seconds - i64::from(offset_minutes) * 60;
+
+        Ok(DateTime {
+            unix_micros: utc_seconds * MICROS_PER_SECOND + micros,
+            offset_minutes,
+            fraction_digits: digits,
+        })
+    }
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let local_micros =
+            self.unix_micros + i64::from(self.offset_minutes) * 60 * MICROS_PER_SECOND;
+        let seconds = local_micros.div_euclid(MICROS_PER_SECOND);
+        let micros = local_micros.rem_euclid(MICROS_PER_SECOND);
+        let (year, month, day) = civil_from_days(seconds.div_euclid(SECONDS_PER_DAY));
+        let time = seconds.rem_euclid(SECONDS_PER_DAY);
+
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            year,
+            month,
+            day,
+            time / 3600,
+            time % 3600 / 60,
+            time % 60
+        )?;
+        if self.fraction_digits > 0 {
+            let digits = u32::from(self.fraction_digits);
+            write!(
+                f,
+                ".{:0width$}",
+                micros / 10_i64.pow(6 - digits),
+                width = digits as usize
+            )?;
+        }
+        if self.offset_minutes == 0 {
+            write!(f, "Z")
+        } else {
+            let sign = if self.offset_minutes < 0 { '-' } else { '+' };
+            let offset = self.offset_minutes.unsigned_abs();
+            write!(f, "{}{:02}:{:02}", sign, offset / 60, offset % 60)
+        }
+    }
+}
@@ -0,0 +1,295 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+use std::str::FromStr;
+
+const MICROS_PER_SECOND: i64 = 1_000_000;
+
+/// Maximum number of fractional digits that are kept exactly.
+pub const MAX_PRECISION: u8 = 6;
+
+/// A duration in seconds stored as an exact number of microseconds.
+///
+/// `DecimalDuration` is used for `EXTINF` and partial segment durations. Unlike
+/// a binary float it sums exactly, so adding up thousands of segment durations
+/// does not drift, and it remembers how many fractional digits it was written
+/// with so that `5.005` is written back as `5.005` rather than `5.0050`.
+///
+/// Equality, ordering and hashing only consider the numeric value: `5.0` and
+/// `5.000` are equal but are displayed differently. Arithmetic saturates at the
+/// bounds of the representable range instead of overflowing.
+///
+/// # Example
+///
+/// ```
+/// use m3u8_parser::m3u8::types::DecimalDuration;
+/// let duration: DecimalDuration = "5.005".parse().unwrap();
+/// assert_eq!(duration.as_micros(), 5_005_000);
+/// assert_eq!(duration.to_string(), "5.005");
+/// assert_eq!(format!("{:.1}", duration), "5.0");
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DecimalDuration {
+    micros: i64,
+    precision: u8,
+}
+
+impl DecimalDuration {
+    /// A duration of zero seconds.
+    pub const ZERO: DecimalDuration = DecimalDuration {
+        micros: 0,
+        precision: 0,
+    };
+
+    /// Creates a duration from a number of microseconds, using the fewest
+    /// fractional digits that represent it exactly.
+    pub fn from_micros(micros: i64) -> Self {
+        let mut precision = MAX_PRECISION;
+        let mut fraction = micros.rem_euclid(MICROS_PER_SECOND);
+        while precision > 0 && fraction % 10 == 0 {
+            fraction /= 10;
+            precision -= 1;
+        }
+        Self { micros, precision }
+    }
+
+    /// Creates a duration from a whole number of seconds, saturating at the
+    /// largest representable duration.
+    pub fn from_secs(secs: u64) -> Self {
+        Self {
+            micros: i64::try_from(secs)
+                .map_or(i64::MAX, |secs| secs.saturating_mul(MICROS_PER_SECOND)),
+            precision: 0,
+        }
+    }
+
+    /// Creates a duration from floating-point seconds, rounded to the nearest
+    /// microsecond.
+    pub fn from_secs_f64(secs: f64) -> Self {
+        Self::from_micros((secs * MICROS_PER_SECOND as f64).round() as i64)
+    }
+
+    /// Returns the duration in microseconds.
+    pub fn as_micros(&self) -> i64 {
+        self.micros
+    }
+
+    /// Returns the duration as floating-point seconds.
+    pub fn as_secs_f64(&self) -> f64 {
+        self.micros as f64 / MICROS_PER_SECOND as f64
+    }
+
+    /// Returns the number of fractional digits used when displaying the duration.
+    pub fn precision(&self) -> u8 {
+        self.precision
+    }
+
+    /// Returns the same duration displayed with `precision` fractional digits
+    /// (capped at six).
+    pub fn with_precision(self, precision: u8) -> Self {
+        Self {
+            micros: self.micros,
+            precision: precision.min(MAX_PRECISION),
+        }
+    }
+
+    /// Returns the duration rounded to the nearest whole second, with halves
+    /// rounded away from zero.
+    pub fn round_secs(&self) -> i64 {
+        let secs = self.micros / MICROS_PER_SECOND;
+        let remainder = self.micros % MICROS_PER_SECOND;
+        if remainder.abs() >= MICROS_PER_SECOND / 2 {
+            secs + remainder.signum()
+        } else {
+            secs
+        }
+    }
+
+    /// Returns `true` if the duration is a whole number of seconds written
+    /// without fractional digits.
+    pub fn is_integer(&self) -> bool {
+        self.precision == 0 && self.micros % MICROS_PER_SECOND == 0
+    }
+}
+
+impl PartialEq for DecimalDuration {
+    fn eq(&self, other: &Self) -> bool {
+        self.micros == other.micros
+    }
+}
+
+impl Eq for DecimalDuration {}
+
+impl Hash for DecimalDuration {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.micros.hash(state);
+    }
+}
+
+impl PartialOrd for DecimalDuration {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DecimalDuration {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.micros.cmp(&other.micros)
+    }
+}
+
+impl Add for DecimalDuration {
+    type Output = DecimalDuration;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            micros: self.micros.saturating_add(rhs.micros),
+            precision: self.precision.max(rhs.precision),
+        }
+    }
+}
+
+impl AddAssign for DecimalDuration {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for DecimalDuration {
+    type Output = DecimalDuration;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            micros: self.micros.saturating_sub(rhs.micros),
+            precision: self.precision.max(rhs.precision),
+        }
+    }
+}
+
+impl SubAssign for DecimalDuration {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sum for DecimalDuration {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(DecimalDuration::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a DecimalDuration> for DecimalDuration {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl From<f32> for DecimalDuration {
+    fn from(secs: f32) -> Self {
+        // Go through the shortest decimal representation of the `f32` so that
+        // `5.005f32` becomes exactly 5.005 seconds.
+        secs.to_string()
+            .parse()
+            .unwrap_or_else(|_| Self::from_secs_f64(f64::from(secs)))
+    }
+}
+
+impl From<f64> for DecimalDuration {
+    fn from(secs: f64) -> Self {
+        Self::from_secs_f64(secs)
+    }
+}
+
+impl From<u64> for DecimalDuration {
+    fn from(secs: u64) -> Self {
+        Self::from_secs(secs)
+    }
+}
+
+impl FromStr for DecimalDuration {
+    type Err = String;
+
+    /// Parses a decimal number of seconds such as `10`, `5.005` or `-1`.
+    ///
+    /// Digits beyond the sixth fractional digit are rounded to the nearest
+    /// microsecond.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid decimal duration: {}", s);
+        let s = s.trim();
+        let (negative, unsigned) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+        let (integer, fraction) = match unsigned.split_once('.') {
+            Some((integer, fraction)) => (integer, fraction),
+            None => (unsigned, ""),
+        };
+        if integer.is_empty()
+            || !integer.bytes().all(|b| b.is_ascii_digit())
+            || !fraction.bytes().all(|b| b.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+
+        let seconds: i64 = integer.parse().map_err(|_| invalid())?;
+        let precision = fraction.len().min(MAX_PRECISION as usize);
+        let mut fraction_micros: i64 = 0;
+        for digit in fraction.bytes().take(MAX_PRECISION as usize) {
+            fraction_micros = fraction_micros * 10 + i64::from(digit - b'0');
+        }
+        fraction_micros *= 10_i64.pow((MAX_PRECISION as usize - precision) as u32);
+        if fraction
+            .as_bytes()
+            .get(MAX_PRECISION as usize)
+            .is_some_and(|digit| *digit >= b'5')
+        {
+            fraction_micros += 1;
+        }
+
+        let micros = seconds
+            .checked_mul(MICROS_PER_SECOND)
+            .and_then(|micros| micros.checked_add(fraction_micros))
+            .ok_or_else(invalid)?;
+
+        Ok(Self {
+            micros: if negative { -micros } else { micros },
+            precision: precision as u8,
+        })
+    }
+}
+
+impl fmt::Display for DecimalDuration {
+    /// Writes the duration with its own precision, or with the precision given
+    /// in the format string (e.g. `{:.3}`).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = f
+            .precision()
+            .map(|p| p.min(MAX_PRECISION as usize) as u32)
+            .unwrap_or(u32::from(self.precision));
+
+        // Round half away from zero to the requested number of digits.
+        let unit = 10_u64.pow(MAX_PRECISION as u32 - precision);
+        let magnitude = (self.micros.unsigned_abs() + unit / 2) / unit;
+        let scale = 10_u64.pow(precision);
+        let sign = if self.micros < 0 && magnitude != 0 {
+            "-"
+        } else {
+            ""
+        };
+
+        if precision == 0 {
+            write!(f, "{}{}", sign, magnitude)
+        } else {
+            write!(
+                f,
+                "{}{}.{:0width$}",
+                sign,
+                magnitude / scale,
+                magnitude % scale,
+                width = precision as usize
+            )
+        }
+    }
+}
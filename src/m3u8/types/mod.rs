@@ -0,0 +1,23 @@
+//! Typed attribute values used by M3U8 tags.
+//!
+//! Several tag attributes carry structured values (a `RESOLUTION` is a pair of
+//! dimensions, a `BYTERANGE` is a length with an optional offset, `CODECS` is a
+//! list of RFC 6381 codec identifiers, a duration is an exact decimal number of
+//! seconds, a program date-time is an ISO 8601 timestamp). This module provides
+//! value types for them so consumers do not have to re-parse the raw attribute
+//! strings.
+//!
+//! Every type implements `FromStr` and `Display`, so values round-trip through
+//! their textual playlist representation.
+
+pub mod byte_range;
+pub mod codecs;
+pub mod date_time;
+pub mod decimal_duration;
+pub mod resolution;
+
+pub use byte_range::ByteRange;
+pub use codecs::{Codec, CodecInfo, CodecType, Codecs};
+pub use date_time::DateTime;
+pub use decimal_duration::DecimalDuration;
+pub use resolution::Resolution;
@@ -0,0 +1,56 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// The `RESOLUTION` attribute of a variant stream, e.g. `1280x720`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Resolution {
+    /// Horizontal pixel count.
+    pub width: u32,
+    /// Vertical pixel count.
+    pub height: u32,
+}
+
+impl Resolution {
+    /// Creates a new `Resolution`.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self { width, height }
+    }
+
+    /// Returns the total number of pixels.
+    pub fn pixels(&self) -> u64 {
+        u64::from(self.width) * u64::from(self.height)
+    }
+}
+
+impl FromStr for Resolution {
+    type Err = String;
+
+    /// Parses a decimal resolution of the form `<width>x<height>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use m3u8_parser::m3u8::types::Resolution;
+    /// let resolution: Resolution = "1920x1080".parse().unwrap();
+    /// assert_eq!(resolution, Resolution::new(1920, 1080));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (width, height) = s
+            .trim()
+            .split_once(['x', 'X'])
+            .ok_or_else(|| format!("Invalid resolution: {}", s))?;
+        let width = width
+            .parse()
+            .map_err(|_| format!("Invalid resolution width: {}", s))?;
+        let height = height
+            .parse()
+            .map_err(|_| format!("Invalid resolution height: {}", s))?;
+        Ok(Resolution { width, height })
+    }
+}
+
+impl fmt::Display for Resolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{}", self.width, self.height)
+    }
+}
@@ -0,0 +1,124 @@
+//! Recommendations of the Apple HLS Authoring Specification.
+//!
+//! These rules go beyond RFC 8216: playlists violating them are valid, but are
+//! commonly rejected by distribution partners. They are reported as warnings by
+//! `Profile::AppleHlsAuthoring` and are disabled in the other profiles.
+
+use crate::m3u8::playlist::Playlist;
+use crate::m3u8::tags::Tag;
+use crate::m3u8::validation::{Findings, ValidationError};
+
+/// The recommended `EXT-X-TARGETDURATION`, in seconds.
+const RECOMMENDED_TARGET_DURATION: u64 = 6;
+
+/// The smallest recommended factor between adjacent bitrates of the ladder.
+const MIN_LADDER_STEP: f64 = 1.5;
+
+/// The largest recommended factor between adjacent bitrates of the ladder.
+const MAX_LADDER_STEP: f64 = 2.0;
+
+/// The largest recommended factor between the peak and average bitrate of a
+/// variant.
+const MAX_PEAK_TO_AVERAGE: f64 = 2.0;
+
+/// Checks the playlist against the authoring recommendations.
+pub(crate) fn validate_authoring(playlist: &Playlist, errors: &mut Findings) {
+    for (index, tag) in playlist.tags.iter().enumerate() {
+        if let Tag::ExtXTargetDuration(target_duration) = tag {
+            if *target_duration != RECOMMENDED_TARGET_DURATION {
+                errors.push_at(
+                    index,
+                    ValidationError::TargetDurationNotRecommended(*target_duration),
+                );
+            }
+        }
+    }
+
+    validate_variants(playlist, errors);
+}
+
+/// Checks the variant streams of a master playlist.
+fn validate_variants(playlist: &Playlist, errors: &mut Findings) {
+    let mut bandwidths = Vec::new();
+    let mut has_video = false;
+
+    for (index, tag) in playlist.tags.iter().enumerate() {
+        if let Tag::ExtXStreamInf {
+            bandwidth,
+            average_bandwidth,
+            codecs,
+            resolution,
+            frame_rate,
+            ..
+        } = tag
+        {
+            bandwidths.push((*bandwidth, index));
+
+            match average_bandwidth {
+                None => errors.push_at(index, ValidationError::MissingAverageBandwidth(*bandwidth)),
+                Some(average_bandwidth)
+                    if f64::from(*bandwidth)
+                        > f64::from(*average_bandwidth) * MAX_PEAK_TO_AVERAGE =>
+                {
+                    errors.push_at(
+                        index,
+                        ValidationError::PeakToAverageRatio {
+                            bandwidth: *bandwidth,
+                            average_bandwidth: *average_bandwidth,
+                        },
+                    );
+                }
+                Some(_) => {}
+            }
+
+            if codecs.is_none() {
+                errors.push_at(index, ValidationError::MissingCodecs(*bandwidth));
+            }
+
+            let is_video = match codecs {
+                Some(codecs) => codecs.has_video(),
+                None => resolution.is_some(),
+            };
+            if is_video {
+                has_video = true;
+                if frame_rate.is_none() {
+                    errors.push_at(index, ValidationError::MissingFrameRate(*bandwidth));
+                }
+            }
+        }
+    }
+
+    if bandwidths.is_empty() {
+        return;
+    }
+
+    // Variants whose segments do not start with a key frame cannot be switched
+    // between at segment boundaries.
+    if !playlist
+        .tags
+        .iter()
+        .any(|tag| matches!(tag, Tag::ExtXIndependentSegments))
+    {
+        errors.push(ValidationError::MissingIndependentSegments);
+    }
+
+    if has_video
+        && !playlist
+            .tags
+            .iter()
+            .any(|tag| matches!(tag, Tag::ExtXIFrameStreamInf { .. }))
+    {
+        errors.push(ValidationError::MissingIFrameVariants);
+    }
+
+    bandwidths.sort_unstable();
+    bandwidths.dedup_by_key(|(bandwidth, _)| *bandwidth);
+    for pair in bandwidths.windows(2) {
+        let ((lower, _), (higher, index)) = (pair[0], pair[1]);
+        if f64::from(higher) > f64::from(lower) * MAX_LADDER_STEP {
+            errors.push_at(index, ValidationError::BitrateLadderGap { lower, higher });
+        } else if f64::from(higher) < f64::from(lower) * MIN_LADDER_STEP {
+            errors.push_at(index, ValidationError::BitrateLadderStep { lower, higher });
+        }
+    }
+}
@@ -0,0 +1,37 @@
+//! Byte range rules of RFC 8216 section 4.3.2.2.
+//!
+//! An `EXT-X-BYTERANGE` without an offset continues the sub-range of the
+//! previous media segment, so it is only meaningful when that segment is a
+//! sub-range of the same resource.
+
+use crate::m3u8::playlist::Playlist;
+use crate::m3u8::tags::Tag;
+use crate::m3u8::validation::{Findings, ValidationError};
+
+/// Checks that every byte range is well-formed and that every segment byte
+/// range can be resolved to an absolute range.
+pub(crate) fn validate_byte_ranges(playlist: &Playlist, errors: &mut Findings) {
+    for (index, tag) in playlist.tags.iter().enumerate() {
+        if let Tag::MalformedByteRange { byte_range, .. } = tag {
+            errors.push_at(index, ValidationError::InvalidByteRange(byte_range.clone()));
+        }
+    }
+
+    let resolved = playlist.byte_ranges();
+    for (segment, resolved) in playlist.segments().iter().zip(resolved) {
+        let Some(range) = segment.byte_range else {
+            continue;
+        };
+        match (range.offset, resolved) {
+            (None, None) => errors.push_at(
+                segment.tag_index,
+                ValidationError::ImplicitByteRangeOffset(segment.uri.to_string()),
+            ),
+            (Some(offset), _) if offset.checked_add(range.length).is_none() => errors.push_at(
+                segment.tag_index,
+                ValidationError::InvalidByteRange(range.to_string()),
+            ),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,307 @@
+//! Low-latency HLS rules (RFC 8216bis section 4.4.3 and 4.4.5).
+//!
+//! A low-latency media playlist divides its segments into partial segments
+//! (`EXT-X-PART`), advertises the next part with `EXT-X-PRELOAD-HINT` and
+//! controls playlist delivery with `EXT-X-SERVER-CONTROL`. These rules check
+//! that the parts agree with their segments and that the server control
+//! attributes give clients enough room to play at the live edge.
+
+use crate::m3u8::playlist::uri::resolve;
+use crate::m3u8::playlist::Playlist;
+use crate::m3u8::tags::Tag;
+use crate::m3u8::types::DecimalDuration;
+use crate::m3u8::validation::{Findings, ValidationError};
+
+/// The largest accepted difference, in microseconds, between a segment duration
+/// and the sum of its part durations. Encoders commonly round `EXTINF` and
+/// `DURATION` values to different precisions.
+const PART_SUM_TOLERANCE_MICROS: i64 = 1_000;
+
+/// Checks the partial segments, preload hints and server control attributes.
+pub(crate) fn validate_low_latency(playlist: &Playlist, errors: &mut Findings) {
+    let tags = &playlist.tags;
+    let part_target = tags.iter().find_map(|tag| match tag {
+        Tag::ExtXPartInf {
+            part_target_duration,
+            ..
+        } => Some(*part_target_duration),
+        _ => None,
+    });
+    let has_parts = tags.iter().any(|tag| matches!(tag, Tag::ExtXPart { .. }));
+
+    if has_parts && part_target.is_none() {
+        errors.push(ValidationError::MissingPartInf);
+    }
+
+    let mut parts = DecimalDuration::ZERO;
+    let mut segment_has_parts = false;
+    for (index, tag) in tags.iter().enumerate() {
+        match tag {
+            Tag::ExtXPart { uri, duration } => {
+                let Some(duration) = duration else {
+                    errors.push_at(
+                        index,
+                        ValidationError::InvalidPartInfo(format!(
+                            "EXT-X-PART {} is missing DURATION",
+                            uri
+                        )),
+                    );
+                    continue;
+                };
+                if let Some(part_target) = part_target {
+                    if *duration > part_target {
+                        errors.push_at(
+                            index,
+                            ValidationError::PartExceedsPartTarget {
+                                uri: uri.clone(),
+                                duration: *duration,
+                                part_target,
+                            },
+                        );
+                    }
+                }
+                parts += *duration;
+                segment_has_parts = true;
+            }
+            Tag::ExtInf(uri, duration, _) => {
+                if segment_has_parts
+                    && (*duration - parts).as_micros().abs() > PART_SUM_TOLERANCE_MICROS
+                {
+                    errors.push_at(
+                        index,
+                        ValidationError::PartDurationMismatch {
+                            uri: uri.clone(),
+                            duration: *duration,
+                            parts,
+                        },
+                    );
+                }
+                parts = DecimalDuration::ZERO;
+                segment_has_parts = false;
+            }
+            _ => {}
+        }
+    }
+
+    // The preload hint announces the part following the last one in the
+    // playlist, so no segment or part may come after it.
+    if let Some(hint) = tags
+        .iter()
+        .position(|tag| matches!(tag, Tag::ExtXPreloadHint { .. }))
+    {
+        if tags[hint..]
+            .iter()
+            .any(|tag| matches!(tag, Tag::ExtInf(..) | Tag::ExtXPart { .. }))
+        {
+            errors.push_at(hint, ValidationError::PreloadHintNotLast);
+        }
+    }
+
+    validate_server_control(playlist, part_target, errors);
+    validate_skip(playlist, errors);
+}
+
+/// Checks that `EXT-X-SKIP` opens the segment list of a delta update the
+/// server advertised with `CAN-SKIP-UNTIL`.
+fn validate_skip(playlist: &Playlist, errors: &mut Findings) {
+    let tags = &playlist.tags;
+    let Some(index) = tags
+        .iter()
+        .position(|tag| matches!(tag, Tag::ExtXSkip { .. }))
+    else {
+        return;
+    };
+
+    if tags[..index]
+        .iter()
+        .any(|tag| matches!(tag, Tag::ExtInf(..)))
+    {
+        errors.push_at(
+            index,
+            ValidationError::InvalidSkipTag(
+                "EXT-X-SKIP must precede the first media segment".to_string(),
+            ),
+        );
+    }
+
+    let (can_skip_until, can_skip_dateranges) = tags
+        .iter()
+        .find_map(|tag| match tag {
+            Tag::ExtXServerControl {
+                can_skip_until,
+                can_skip_dateranges,
+                ..
+            } => Some((*can_skip_until, *can_skip_dateranges)),
+            _ => None,
+        })
+        .unwrap_or_default();
+    if can_skip_until.is_none() {
+        errors.push_at(
+            index,
+            ValidationError::InvalidSkipTag(
+                "EXT-X-SERVER-CONTROL does not declare CAN-SKIP-UNTIL".to_string(),
+            ),
+        );
+    }
+    if matches!(
+        &tags[index],
+        Tag::ExtXSkip {
+            recently_removed_dateranges: Some(_),
+            ..
+        }
+    ) && can_skip_dateranges != Some(true)
+    {
+        errors.push_at(
+            index,
+            ValidationError::InvalidSkipTag(
+                "RECENTLY-REMOVED-DATERANGES requires CAN-SKIP-DATERANGES=YES".to_string(),
+            ),
+        );
+    }
+}
+
+/// Checks `EXT-X-SERVER-CONTROL` against the target durations.
+fn validate_server_control(
+    playlist: &Playlist,
+    part_target: Option<DecimalDuration>,
+    errors: &mut Findings,
+) {
+    let target_duration = playlist.tags.iter().find_map(|tag| match tag {
+        Tag::ExtXTargetDuration(duration) => Some(*duration),
+        _ => None,
+    });
+    let server_control = playlist
+        .tags
+        .iter()
+        .enumerate()
+        .find_map(|(index, tag)| match tag {
+            Tag::ExtXServerControl {
+                hold_back,
+                part_hold_back,
+                can_block_reload,
+                ..
+            } => Some((index, *hold_back, *part_hold_back, *can_block_reload)),
+            _ => None,
+        });
+
+    let Some((index, hold_back, part_hold_back, can_block_reload)) = server_control else {
+        if part_target.is_some() {
+            errors.push(ValidationError::MissingCanBlockReload);
+            errors.push(ValidationError::MissingPartHoldBack);
+        }
+        return;
+    };
+
+    if let (Some(hold_back), Some(target_duration)) = (hold_back, target_duration) {
+        let minimum = DecimalDuration::from_secs(target_duration * 3);
+        if hold_back < minimum {
+            errors.push_at(
+                index,
+                ValidationError::HoldBackTooShort { hold_back, minimum },
+            );
+        }
+    }
+
+    let Some(part_target) = part_target else {
+        return;
+    };
+    if can_block_reload != Some(true) {
+        errors.push_at(index, ValidationError::MissingCanBlockReload);
+    }
+    match part_hold_back {
+        None => errors.push_at(index, ValidationError::MissingPartHoldBack),
+        Some(part_hold_back) => {
+            let minimum = part_target + part_target;
+            if part_hold_back < minimum {
+                errors.push_at(
+                    index,
+                    ValidationError::PartHoldBackTooShort {
+                        part_hold_back,
+                        minimum,
+                    },
+                );
+            }
+        }
+    }
+}
+
+impl Playlist {
+    /// Checks that every `EXT-X-RENDITION-REPORT` of this media playlist refers
+    /// to a sibling rendition, i.e. another variant stream or rendition of the
+    /// master playlist.
+    ///
+    /// `uri` is the URI of this playlist as written in `master`; the URIs of
+    /// the reports are resolved relative to it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use m3u8_parser::m3u8::playlist::Playlist;
+    /// use m3u8_parser::m3u8::validation::ValidationError;
+    ///
+    /// let master = Playlist::from_reader(
+    ///     "#EXTM3U\n#EXT-X-STREAM-INF:BANDWIDTH=1000000\n1M/live.m3u8\n\
+    ///      #EXT-X-STREAM-INF:BANDWIDTH=2000000\n2M/live.m3u8\n"
+    ///         .as_bytes(),
+    /// )
+    /// .unwrap();
+    /// let media = Playlist::from_reader(
+    ///     "#EXTM3U\n#EXT-X-RENDITION-REPORT:URI=\"../2M/live.m3u8\"\n\
+    ///      #EXT-X-RENDITION-REPORT:URI=\"../4M/live.m3u8\"\n"
+    ///         .as_bytes(),
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(
+    ///     media.validate_rendition_reports("1M/live.m3u8", &master),
+    ///     Err(vec![ValidationError::UndefinedRenditionReport(
+    ///         "../4M/live.m3u8".to_string()
+    ///     )])
+    /// );
+    /// ```
+    pub fn validate_rendition_reports(
+        &self,
+        uri: &str,
+        master: &Playlist,
+    ) -> Result<(), Vec<ValidationError>> {
+        let own = playlist_uri("", uri);
+        let siblings: Vec<String> = master
+            .tags
+            .iter()
+            .filter_map(|tag| match tag {
+                Tag::ExtXStreamInf { uri, .. } => Some(uri.as_str()),
+                Tag::ExtXMedia { uri, .. } => uri.as_deref(),
+                _ => None,
+            })
+            .map(|sibling| playlist_uri("", sibling))
+            .filter(|sibling| *sibling != own)
+            .collect();
+
+        let errors: Vec<ValidationError> = self
+            .tags
+            .iter()
+            .filter_map(|tag| match tag {
+                Tag::ExtXRenditionReport { uri: report, .. }
+                    if !siblings.contains(&playlist_uri(uri, report)) =>
+                {
+                    Some(ValidationError::UndefinedRenditionReport(report.clone()))
+                }
+                _ => None,
+            })
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Resolves `reference` against `base` without the query and fragment, which
+/// do not identify another playlist.
+fn playlist_uri(base: &str, reference: &str) -> String {
+    let mut uri = resolve(base, reference);
+    uri.truncate(uri.find(['?', '#']).unwrap_or(uri.len()));
+    uri
+}
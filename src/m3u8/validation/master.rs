@@ -0,0 +1,97 @@
+//! Cross-tag rules for master playlists (RFC 8216 section 4.3.4).
+//!
+//! Variant streams refer to groups of alternative renditions by `GROUP-ID`.
+//! These rules check that every reference resolves to a group of the right
+//! `TYPE` and that the renditions inside each group are consistent.
+
+use crate::m3u8::playlist::Playlist;
+use crate::m3u8::tags::Tag;
+use crate::m3u8::validation::{Findings, ValidationError};
+use std::collections::HashSet;
+
+/// Checks rendition groups and the references to them from variant streams.
+pub(crate) fn validate_references(playlist: &Playlist, errors: &mut Findings) {
+    let mut groups: HashSet<(&str, &str)> = HashSet::new();
+    let mut defaults: HashSet<(&str, &str)> = HashSet::new();
+    let mut names: HashSet<(&str, &str, &str)> = HashSet::new();
+
+    for (index, tag) in playlist.tags.iter().enumerate() {
+        if let Tag::ExtXMedia {
+            type_,
+            group_id,
+            name,
+            uri,
+            default,
+            instream_id,
+            ..
+        } = tag
+        {
+            groups.insert((type_, group_id));
+
+            match name {
+                None => errors.push_at(index, ValidationError::MissingMediaFields),
+                Some(name) if !names.insert((type_, group_id, name)) => errors.push_at(
+                    index,
+                    ValidationError::DuplicateRenditionName {
+                        group_id: group_id.clone(),
+                        name: name.clone(),
+                    },
+                ),
+                Some(_) => {}
+            }
+            if *default == Some(true) && !defaults.insert((type_, group_id)) {
+                errors.push_at(
+                    index,
+                    ValidationError::MultipleDefaultRenditions(group_id.clone()),
+                );
+            }
+            if type_ == "CLOSED-CAPTIONS" {
+                if instream_id.is_none() {
+                    errors.push_at(index, ValidationError::MissingInstreamId(group_id.clone()));
+                }
+                if uri.is_some() {
+                    errors.push_at(
+                        index,
+                        ValidationError::ClosedCaptionsWithUri(group_id.clone()),
+                    );
+                }
+            }
+        }
+    }
+
+    for (index, tag) in playlist.tags.iter().enumerate() {
+        if let Tag::ExtXStreamInf {
+            audio,
+            video,
+            subtitle,
+            closed_captions,
+            ..
+        } = tag
+        {
+            let references = [
+                ("AUDIO", audio),
+                ("VIDEO", video),
+                ("SUBTITLES", subtitle),
+                ("CLOSED-CAPTIONS", closed_captions),
+            ];
+            for (type_, group_id) in references {
+                let Some(group_id) = group_id else {
+                    continue;
+                };
+                // `CLOSED-CAPTIONS=NONE` explicitly declares that there are none.
+                if type_ == "CLOSED-CAPTIONS" && group_id == "NONE" {
+                    continue;
+                }
+                if !groups.contains(&(type_, group_id.as_str())) {
+                    errors.push_at(
+                        index,
+                        ValidationError::UndefinedMediaGroup {
+                            attribute: type_.to_string(),
+                            group_id: group_id.clone(),
+                        },
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,140 @@
+//! Playlist-level rules of RFC 8216 section 6.2.1 and section 4.3.
+//!
+//! These rules look at the playlist as a whole rather than at individual tag
+//! values: where tags appear, how often, and how segment durations relate to
+//! the target duration.
+
+use crate::m3u8::playlist::Playlist;
+use crate::m3u8::tags::Tag;
+use crate::m3u8::validation::{Findings, ValidationError};
+
+/// Returns `true` for tags that describe the whole media playlist and so must
+/// appear before its first media segment.
+fn is_header_tag(tag: &Tag) -> bool {
+    matches!(
+        tag,
+        Tag::ExtXVersion(_)
+            | Tag::ExtXTargetDuration(_)
+            | Tag::ExtXMediaSequence(_)
+            | Tag::ExtXDiscontinuitySequence(_)
+            | Tag::ExtXPlaylistType(_)
+            | Tag::ExtXIndependentSegments
+            | Tag::ExtXStart { .. }
+            | Tag::ExtXPartInf { .. }
+            | Tag::ExtXServerControl { .. }
+    )
+}
+
+/// Returns `true` for tags that may appear at most once in a playlist.
+fn is_unique_tag(tag: &Tag) -> bool {
+    matches!(
+        tag,
+        Tag::ExtM3U
+            | Tag::ExtXVersion(_)
+            | Tag::ExtXTargetDuration(_)
+            | Tag::ExtXMediaSequence(_)
+            | Tag::ExtXDiscontinuitySequence(_)
+            | Tag::ExtXPlaylistType(_)
+            | Tag::ExtXEndList
+            | Tag::ExtXIndependentSegments
+            | Tag::ExtXStart { .. }
+            | Tag::ExtXPartInf { .. }
+            | Tag::ExtXServerControl { .. }
+    )
+}
+
+/// Checks the structure of the playlist: playlist type consistency, tag
+/// uniqueness and placement, and segment durations against the target duration.
+pub(crate) fn validate_structure(playlist: &Playlist, errors: &mut Findings) {
+    let tags = &playlist.tags;
+
+    let mut seen: Vec<&'static str> = Vec::new();
+    for (index, tag) in tags
+        .iter()
+        .enumerate()
+        .filter(|(_, tag)| is_unique_tag(tag))
+    {
+        if seen.contains(&tag.name()) {
+            errors.push_at(index, ValidationError::DuplicateTag(tag.name().to_string()));
+        } else {
+            seen.push(tag.name());
+        }
+    }
+
+    let is_media = tags
+        .iter()
+        .any(|tag| matches!(tag, Tag::ExtInf(..) | Tag::ExtXTargetDuration(_)));
+    let is_master = !is_media && tags.iter().any(Tag::is_master_tag);
+
+    if is_master {
+        for (index, tag) in tags
+            .iter()
+            .enumerate()
+            .filter(|(_, tag)| tag.is_media_tag())
+        {
+            errors.push_at(
+                index,
+                ValidationError::MediaTagInMasterPlaylist(tag.name().to_string()),
+            );
+        }
+        return;
+    }
+    if !is_media {
+        return;
+    }
+
+    for (index, tag) in tags
+        .iter()
+        .enumerate()
+        .filter(|(_, tag)| tag.is_master_tag())
+    {
+        errors.push_at(
+            index,
+            ValidationError::MasterTagInMediaPlaylist(tag.name().to_string()),
+        );
+    }
+
+    if let Some(first_segment) = tags.iter().position(|tag| matches!(tag, Tag::ExtInf(..))) {
+        for (index, tag) in tags
+            .iter()
+            .enumerate()
+            .skip(first_segment)
+            .filter(|(_, tag)| is_header_tag(tag))
+        {
+            errors.push_at(
+                index,
+                ValidationError::HeaderTagAfterSegment(tag.name().to_string()),
+            );
+        }
+    }
+
+    if let Some(end_list) = tags.iter().position(|tag| matches!(tag, Tag::ExtXEndList)) {
+        if end_list + 1 < tags.len() {
+            errors.push_at(end_list, ValidationError::EndListNotLast);
+        }
+    }
+
+    let target_duration = tags.iter().find_map(|tag| match tag {
+        Tag::ExtXTargetDuration(duration) => Some(*duration),
+        _ => None,
+    });
+    match target_duration {
+        None => errors.push(ValidationError::MissingTargetDuration),
+        // A zero target duration is already reported by `InvalidTargetDuration`.
+        Some(0) => {}
+        Some(target_duration) => {
+            for segment in playlist.segments() {
+                if segment.duration.round_secs() > target_duration as i64 {
+                    errors.push_at(
+                        segment.tag_index,
+                        ValidationError::SegmentDurationExceedsTarget {
+                            uri: segment.uri.to_string(),
+                            duration: segment.duration,
+                            target_duration,
+                        },
+                    );
+                }
+            }
+        }
+    }
+}
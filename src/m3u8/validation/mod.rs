@@ -0,0 +1,730 @@
+pub(crate) mod authoring;
+pub(crate) mod byte_range;
+pub(crate) mod ll_hls;
+pub(crate) mod master;
+pub(crate) mod media;
+pub mod render;
+pub(crate) mod update;
+pub mod validator;
+pub mod version;
+
+pub use validator::{Diagnostic, Profile, Rule, Severity, ValidationReport, Validator};
+
+use crate::m3u8::types::DecimalDuration;
+use std::error::Error;
+use std::fmt;
+
+/// Represents different types of validation errors that can occur when processing an M3U8 playlist.
+///
+/// This enum captures specific validation issues that may arise when checking the conformity
+/// of a playlist to the M3U8 specification. Each variant represents a distinct error, providing
+/// context for what went wrong during validation.
+#[derive(Debug, PartialEq)]
+pub enum ValidationError {
+    /// Error indicating that the #EXTM3U tag is missing from the playlist.
+    MissingExtM3U,
+
+    /// Error indicating that the specified version is invalid.
+    ///
+    /// # Arguments
+    ///
+    /// * `u8` - The invalid version number that was encountered.
+    InvalidVersion(u8),
+
+    /// Error indicating that the duration specified is invalid.
+    ///
+    /// # Arguments
+    ///
+    /// * `DecimalDuration` - The invalid duration value that was encountered.
+    InvalidDuration(DecimalDuration),
+
+    /// Error indicating that the target duration specified is invalid.
+    ///
+    /// # Arguments
+    ///
+    /// * `u32` - The invalid target duration value that was encountered.
+    InvalidTargetDuration(u64),
+
+    /// Error indicating that an invalid key method was specified.
+    ///
+    /// # Arguments
+    ///
+    /// * `String` - The invalid key method that was encountered.
+    InvalidKeyMethod(String),
+
+    /// Error indicating that the URI specified in a map tag is invalid.
+    InvalidMapUri,
+
+    /// Error indicating that the program date and time specified is invalid.
+    InvalidProgramDateTime,
+
+    /// Error indicating that the ID specified in a date range is invalid.
+    InvalidDateRangeId,
+
+    /// Error indicating that the start date specified in a date range is invalid.
+    InvalidDateRangeStartDate,
+
+    /// Error indicating that the end date specified in a date range is invalid.
+    InvalidDateRangeEndDate,
+
+    /// Error indicating that the planned duration specified in a date range is invalid.
+    ///
+    /// # Arguments
+    ///
+    /// * `f32` - The invalid planned duration value that was encountered in the date range.
+    InvalidDateRangePlannedDuration(f32),
+
+    /// Error indicating that the specified byte range is invalid.
+    ///
+    /// # Arguments
+    ///
+    /// * `String` - The invalid byte range that was encountered.
+    InvalidByteRange(String),
+
+    /// Error indicating that a media tag is missing required fields.
+    MissingMediaFields,
+
+    /// Error indicating that a stream information tag is invalid.
+    ///
+    /// # Arguments
+    ///
+    /// * `String` - The invalid stream information encountered.
+    InvalidStreamInf(String),
+
+    /// Error indicating that an I-frame stream information tag is invalid.
+    ///
+    /// # Arguments
+    ///
+    /// * `String` - The invalid I-frame stream information encountered.
+    InvalidIFrameStreamInf(String),
+
+    /// Error indicating that a part tag is invalid.
+    ///
+    /// # Arguments
+    ///
+    /// * `String` - The invalid part information encountered.
+    InvalidPartInfo(String),
+
+    /// Error indicating that a preload hint URI is invalid.
+    InvalidPreloadHintUri,
+
+    /// Error indicating that a rendition report URI is invalid.
+    InvalidRenditionReportUri,
+
+    /// Error indicating that the server control information is invalid.
+    InvalidServerControl,
+
+    /// Error indicating that the specified start time offset is invalid.
+    InvalidStartTimeOffset,
+
+    /// Error indicating that a skip tag is invalid.
+    ///
+    /// # Arguments
+    ///
+    /// * `String` - The invalid skip tag information encountered.
+    InvalidSkipTag(String),
+
+    /// Error indicating that the specified bitrate is invalid.
+    ///
+    /// # Arguments
+    ///
+    /// * `u32` - The invalid bitrate value that was encountered.
+    InvalidBitrate(u32),
+
+    /// Error indicating that the specified start offset is invalid.
+    InvalidStartOffset,
+
+    /// Error indicating that a segment duration, rounded to the nearest integer,
+    /// exceeds the target duration.
+    ///
+    /// # Arguments
+    ///
+    /// * `uri` - The URI of the offending segment.
+    /// * `duration` - The `EXTINF` duration of the segment.
+    /// * `target_duration` - The declared `EXT-X-TARGETDURATION`.
+    SegmentDurationExceedsTarget {
+        uri: String,
+        duration: DecimalDuration,
+        target_duration: u64,
+    },
+
+    /// Error indicating that a media playlist has no `EXT-X-TARGETDURATION` tag.
+    MissingTargetDuration,
+
+    /// Error indicating that a tag which may appear at most once appears more than once.
+    ///
+    /// # Arguments
+    ///
+    /// * `String` - The name of the duplicated tag.
+    DuplicateTag(String),
+
+    /// Error indicating that a playlist header tag appears after the first media segment.
+    ///
+    /// # Arguments
+    ///
+    /// * `String` - The name of the misplaced tag.
+    HeaderTagAfterSegment(String),
+
+    /// Error indicating that tags follow `EXT-X-ENDLIST`.
+    EndListNotLast,
+
+    /// Error indicating that a master playlist tag appears in a media playlist.
+    ///
+    /// # Arguments
+    ///
+    /// * `String` - The name of the misplaced tag.
+    MasterTagInMediaPlaylist(String),
+
+    /// Error indicating that a media playlist tag appears in a master playlist.
+    ///
+    /// # Arguments
+    ///
+    /// * `String` - The name of the misplaced tag.
+    MediaTagInMasterPlaylist(String),
+
+    /// Error indicating that a variant stream references a rendition group that
+    /// is not defined by an `EXT-X-MEDIA` tag of the matching type.
+    ///
+    /// # Arguments
+    ///
+    /// * `attribute` - The referencing `EXT-X-STREAM-INF` attribute, e.g. `AUDIO`.
+    /// * `group_id` - The referenced `GROUP-ID`.
+    UndefinedMediaGroup { attribute: String, group_id: String },
+
+    /// Error indicating that more than one rendition in a group has `DEFAULT=YES`.
+    ///
+    /// # Arguments
+    ///
+    /// * `String` - The `GROUP-ID` of the group.
+    MultipleDefaultRenditions(String),
+
+    /// Error indicating that two renditions in the same group share a `NAME`.
+    ///
+    /// # Arguments
+    ///
+    /// * `group_id` - The `GROUP-ID` of the group.
+    /// * `name` - The duplicated `NAME`.
+    DuplicateRenditionName { group_id: String, name: String },
+
+    /// Error indicating that a `CLOSED-CAPTIONS` rendition has no `INSTREAM-ID`.
+    ///
+    /// # Arguments
+    ///
+    /// * `String` - The `GROUP-ID` of the rendition.
+    MissingInstreamId(String),
+
+    /// Error indicating that a `CLOSED-CAPTIONS` rendition has a `URI`.
+    ///
+    /// # Arguments
+    ///
+    /// * `String` - The `GROUP-ID` of the rendition.
+    ClosedCaptionsWithUri(String),
+
+    /// Error indicating that the playlist uses a feature that requires a higher
+    /// protocol version than the one declared by `EXT-X-VERSION`.
+    ///
+    /// # Arguments
+    ///
+    /// * `declared` - The declared version, or 1 if there is no `EXT-X-VERSION` tag.
+    /// * `required` - The version required by `feature`.
+    /// * `feature` - A description of the feature requiring the version.
+    VersionTooLow {
+        declared: u8,
+        required: u8,
+        feature: String,
+    },
+
+    /// Error indicating that the playlist uses a tag the validation profile does
+    /// not define, e.g. a low-latency HLS tag under strict RFC 8216.
+    ///
+    /// # Arguments
+    ///
+    /// * `String` - The name of the unsupported tag.
+    UnsupportedTag(String),
+
+    /// Warning indicating that `EXT-X-TARGETDURATION` is not the recommended
+    /// 6 seconds (Apple HLS Authoring Specification).
+    ///
+    /// # Arguments
+    ///
+    /// * `u64` - The declared target duration.
+    TargetDurationNotRecommended(u64),
+
+    /// Warning indicating that a master playlist has no `EXT-X-INDEPENDENT-SEGMENTS`
+    /// tag declaring its variants key-frame aligned.
+    MissingIndependentSegments,
+
+    /// Warning indicating that a variant stream has no `AVERAGE-BANDWIDTH`.
+    ///
+    /// # Arguments
+    ///
+    /// * `u32` - The `BANDWIDTH` of the variant.
+    MissingAverageBandwidth(u32),
+
+    /// Warning indicating that a variant stream has no `CODECS`.
+    ///
+    /// # Arguments
+    ///
+    /// * `u32` - The `BANDWIDTH` of the variant.
+    MissingCodecs(u32),
+
+    /// Warning indicating that a video variant stream has no `FRAME-RATE`.
+    ///
+    /// # Arguments
+    ///
+    /// * `u32` - The `BANDWIDTH` of the variant.
+    MissingFrameRate(u32),
+
+    /// Warning indicating that a master playlist with video has no
+    /// `EXT-X-I-FRAME-STREAM-INF` variants.
+    MissingIFrameVariants,
+
+    /// Warning indicating that adjacent bitrates of the variant ladder are more
+    /// than a factor of 2 apart.
+    ///
+    /// # Arguments
+    ///
+    /// * `lower` - The lower `BANDWIDTH`.
+    /// * `higher` - The next higher `BANDWIDTH`.
+    BitrateLadderGap { lower: u32, higher: u32 },
+
+    /// Warning indicating that adjacent bitrates of the variant ladder are less
+    /// than a factor of 1.5 apart.
+    ///
+    /// # Arguments
+    ///
+    /// * `lower` - The lower `BANDWIDTH`.
+    /// * `higher` - The next higher `BANDWIDTH`.
+    BitrateLadderStep { lower: u32, higher: u32 },
+
+    /// Warning indicating that the peak bitrate of a variant exceeds twice its
+    /// average bitrate.
+    ///
+    /// # Arguments
+    ///
+    /// * `bandwidth` - The `BANDWIDTH` of the variant.
+    /// * `average_bandwidth` - The `AVERAGE-BANDWIDTH` of the variant.
+    PeakToAverageRatio {
+        bandwidth: u32,
+        average_bandwidth: u32,
+    },
+
+    /// Error indicating that the part durations of a segment do not add up to
+    /// its `EXTINF` duration.
+    ///
+    /// # Arguments
+    ///
+    /// * `uri` - The URI of the segment.
+    /// * `duration` - The `EXTINF` duration of the segment.
+    /// * `parts` - The sum of the durations of its parts.
+    PartDurationMismatch {
+        uri: String,
+        duration: DecimalDuration,
+        parts: DecimalDuration,
+    },
+
+    /// Error indicating that a part is longer than the `PART-TARGET` of
+    /// `EXT-X-PART-INF`.
+    ///
+    /// # Arguments
+    ///
+    /// * `uri` - The URI of the part.
+    /// * `duration` - The `DURATION` of the part.
+    /// * `part_target` - The declared part target duration.
+    PartExceedsPartTarget {
+        uri: String,
+        duration: DecimalDuration,
+        part_target: DecimalDuration,
+    },
+
+    /// Error indicating that a playlist with `EXT-X-PART` tags has no `EXT-X-PART-INF` tag.
+    MissingPartInf,
+
+    /// Error indicating that a segment or part follows `EXT-X-PRELOAD-HINT`.
+    PreloadHintNotLast,
+
+    /// Error indicating that `HOLD-BACK` is less than three target durations.
+    ///
+    /// # Arguments
+    ///
+    /// * `hold_back` - The declared `HOLD-BACK`.
+    /// * `minimum` - Three times the target duration.
+    HoldBackTooShort {
+        hold_back: DecimalDuration,
+        minimum: DecimalDuration,
+    },
+
+    /// Error indicating that `PART-HOLD-BACK` is less than two part target durations.
+    ///
+    /// # Arguments
+    ///
+    /// * `part_hold_back` - The declared `PART-HOLD-BACK`.
+    /// * `minimum` - Twice the part target duration.
+    PartHoldBackTooShort {
+        part_hold_back: DecimalDuration,
+        minimum: DecimalDuration,
+    },
+
+    /// Error indicating that a playlist with `EXT-X-PART-INF` does not declare
+    /// `PART-HOLD-BACK` in `EXT-X-SERVER-CONTROL`.
+    MissingPartHoldBack,
+
+    /// Error indicating that a playlist with `EXT-X-PART-INF` does not declare
+    /// `CAN-BLOCK-RELOAD=YES` in `EXT-X-SERVER-CONTROL`.
+    MissingCanBlockReload,
+
+    /// Error indicating that an `EXT-X-RENDITION-REPORT` does not refer to a
+    /// sibling rendition of the master playlist.
+    ///
+    /// # Arguments
+    ///
+    /// * `String` - The URI of the report.
+    UndefinedRenditionReport(String),
+
+    /// Error indicating that `EXT-X-MEDIA-SEQUENCE` decreased between two
+    /// snapshots of a live playlist.
+    ///
+    /// # Arguments
+    ///
+    /// * `previous` - The media sequence of the previous snapshot.
+    /// * `next` - The media sequence of the next snapshot.
+    MediaSequenceDecreased { previous: u64, next: u64 },
+
+    /// Error indicating that the segment with a given media sequence number has
+    /// a different URI or duration than in the previous snapshot.
+    ///
+    /// # Arguments
+    ///
+    /// * `media_sequence` - The media sequence number of the segment.
+    /// * `previous` - The URI and duration in the previous snapshot.
+    /// * `next` - The URI and duration in the next snapshot.
+    SegmentChanged {
+        media_sequence: u64,
+        previous: String,
+        next: String,
+    },
+
+    /// Error indicating that `EXT-X-DISCONTINUITY-SEQUENCE` does not account
+    /// for the discontinuities removed since the previous snapshot.
+    ///
+    /// # Arguments
+    ///
+    /// * `expected` - The discontinuity sequence implied by the previous snapshot.
+    /// * `found` - The declared discontinuity sequence.
+    DiscontinuitySequenceMismatch { expected: u64, found: u64 },
+
+    /// Error indicating that `EXT-X-ENDLIST` was removed from a playlist that had ended.
+    EndListRemoved,
+
+    /// Error indicating that a quoted-string attribute value is not enclosed in
+    /// double quotes. The parser cannot keep track of quoting, so this is only
+    /// reported by `Playlist::from_reader_repaired`.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The name of the tag.
+    /// * `attribute` - The name of the unquoted attribute.
+    UnquotedAttribute { tag: String, attribute: String },
+
+    /// Error indicating that an `EXT-X-BYTERANGE` without offset does not follow
+    /// a sub-range of the same resource.
+    ///
+    /// # Arguments
+    ///
+    /// * `String` - The URI of the segment.
+    ImplicitByteRangeOffset(String),
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::MissingExtM3U => write!(f, "playlist does not start with #EXTM3U"),
+            ValidationError::InvalidVersion(version) => {
+                write!(f, "unsupported protocol version {}", version)
+            }
+            ValidationError::InvalidDuration(duration) => {
+                write!(f, "segment duration {} is not positive", duration)
+            }
+            ValidationError::InvalidTargetDuration(duration) => {
+                write!(f, "target duration {} is not positive", duration)
+            }
+            ValidationError::InvalidKeyMethod(method) => {
+                write!(f, "unknown encryption method {}", method)
+            }
+            ValidationError::InvalidMapUri => write!(f, "EXT-X-MAP has an empty URI"),
+            ValidationError::InvalidProgramDateTime => {
+                write!(f, "EXT-X-PROGRAM-DATE-TIME is not an ISO 8601 date-time")
+            }
+            ValidationError::InvalidDateRangeId => write!(f, "EXT-X-DATERANGE has an invalid ID"),
+            ValidationError::InvalidDateRangeStartDate => {
+                write!(f, "EXT-X-DATERANGE has an invalid START-DATE")
+            }
+            ValidationError::InvalidDateRangeEndDate => {
+                write!(f, "EXT-X-DATERANGE has an invalid END-DATE")
+            }
+            ValidationError::InvalidDateRangePlannedDuration(duration) => {
+                write!(
+                    f,
+                    "EXT-X-DATERANGE has an invalid PLANNED-DURATION {}",
+                    duration
+                )
+            }
+            ValidationError::InvalidByteRange(byterange) => {
+                write!(f, "invalid byte range {}", byterange)
+            }
+            ValidationError::MissingMediaFields => {
+                write!(f, "EXT-X-MEDIA is missing required attributes")
+            }
+            ValidationError::InvalidStreamInf(reason) => {
+                write!(f, "invalid EXT-X-STREAM-INF: {}", reason)
+            }
+            ValidationError::InvalidIFrameStreamInf(reason) => {
+                write!(f, "invalid EXT-X-I-FRAME-STREAM-INF: {}", reason)
+            }
+            ValidationError::InvalidPartInfo(reason) => write!(f, "invalid part: {}", reason),
+            ValidationError::InvalidPreloadHintUri => {
+                write!(f, "EXT-X-PRELOAD-HINT has an empty URI")
+            }
+            ValidationError::InvalidRenditionReportUri => {
+                write!(f, "EXT-X-RENDITION-REPORT has an empty URI")
+            }
+            ValidationError::InvalidServerControl => write!(f, "invalid EXT-X-SERVER-CONTROL"),
+            ValidationError::InvalidStartTimeOffset | ValidationError::InvalidStartOffset => {
+                write!(f, "EXT-X-START has an invalid TIME-OFFSET")
+            }
+            ValidationError::InvalidSkipTag(reason) => {
+                write!(f, "invalid EXT-X-SKIP: {}", reason)
+            }
+            ValidationError::InvalidBitrate(bitrate) => write!(f, "invalid bitrate {}", bitrate),
+            ValidationError::SegmentDurationExceedsTarget {
+                uri,
+                duration,
+                target_duration,
+            } => write!(
+                f,
+                "segment {} lasts {}s, longer than the target duration of {}s",
+                uri, duration, target_duration
+            ),
+            ValidationError::MissingTargetDuration => {
+                write!(f, "media playlist has no EXT-X-TARGETDURATION")
+            }
+            ValidationError::DuplicateTag(name) => write!(f, "{} appears more than once", name),
+            ValidationError::HeaderTagAfterSegment(name) => {
+                write!(f, "{} appears after the first media segment", name)
+            }
+            ValidationError::EndListNotLast => write!(f, "tags follow EXT-X-ENDLIST"),
+            ValidationError::MasterTagInMediaPlaylist(name) => {
+                write!(f, "master playlist tag {} in a media playlist", name)
+            }
+            ValidationError::MediaTagInMasterPlaylist(name) => {
+                write!(f, "media playlist tag {} in a master playlist", name)
+            }
+            ValidationError::UndefinedMediaGroup {
+                attribute,
+                group_id,
+            } => write!(
+                f,
+                "{} references undefined rendition group \"{}\"",
+                attribute, group_id
+            ),
+            ValidationError::MultipleDefaultRenditions(group_id) => write!(
+                f,
+                "more than one rendition of group \"{}\" has DEFAULT=YES",
+                group_id
+            ),
+            ValidationError::DuplicateRenditionName { group_id, name } => write!(
+                f,
+                "rendition name \"{}\" is used twice in group \"{}\"",
+                name, group_id
+            ),
+            ValidationError::MissingInstreamId(group_id) => write!(
+                f,
+                "closed-captions rendition of group \"{}\" has no INSTREAM-ID",
+                group_id
+            ),
+            ValidationError::ClosedCaptionsWithUri(group_id) => write!(
+                f,
+                "closed-captions rendition of group \"{}\" has a URI",
+                group_id
+            ),
+            ValidationError::VersionTooLow {
+                declared,
+                required,
+                feature,
+            } => write!(
+                f,
+                "{} requires protocol version {}, but the playlist declares version {}",
+                feature, required, declared
+            ),
+            ValidationError::UnsupportedTag(name) => {
+                write!(f, "{} is not supported by the validation profile", name)
+            }
+            ValidationError::TargetDurationNotRecommended(duration) => write!(
+                f,
+                "target duration is {}s instead of the recommended 6s",
+                duration
+            ),
+            ValidationError::MissingIndependentSegments => {
+                write!(f, "master playlist has no EXT-X-INDEPENDENT-SEGMENTS")
+            }
+            ValidationError::MissingAverageBandwidth(bandwidth) => write!(
+                f,
+                "variant with BANDWIDTH={} has no AVERAGE-BANDWIDTH",
+                bandwidth
+            ),
+            ValidationError::MissingCodecs(bandwidth) => {
+                write!(f, "variant with BANDWIDTH={} has no CODECS", bandwidth)
+            }
+            ValidationError::MissingFrameRate(bandwidth) => write!(
+                f,
+                "video variant with BANDWIDTH={} has no FRAME-RATE",
+                bandwidth
+            ),
+            ValidationError::MissingIFrameVariants => {
+                write!(f, "master playlist has no I-frame variants")
+            }
+            ValidationError::BitrateLadderGap { lower, higher } => write!(
+                f,
+                "variant bitrates {} and {} are more than a factor of 2 apart",
+                lower, higher
+            ),
+            ValidationError::BitrateLadderStep { lower, higher } => write!(
+                f,
+                "variant bitrates {} and {} are less than a factor of 1.5 apart",
+                lower, higher
+            ),
+            ValidationError::PeakToAverageRatio {
+                bandwidth,
+                average_bandwidth,
+            } => write!(
+                f,
+                "peak bitrate {} is more than twice the average bitrate {}",
+                bandwidth, average_bandwidth
+            ),
+            ValidationError::PartDurationMismatch {
+                uri,
+                duration,
+                parts,
+            } => write!(
+                f,
+                "parts of segment {} last {}s, but the segment lasts {}s",
+                uri, parts, duration
+            ),
+            ValidationError::PartExceedsPartTarget {
+                uri,
+                duration,
+                part_target,
+            } => write!(
+                f,
+                "part {} lasts {}s, longer than the part target of {}s",
+                uri, duration, part_target
+            ),
+            ValidationError::MissingPartInf => {
+                write!(f, "playlist has parts but no EXT-X-PART-INF")
+            }
+            ValidationError::PreloadHintNotLast => {
+                write!(f, "segments or parts follow EXT-X-PRELOAD-HINT")
+            }
+            ValidationError::HoldBackTooShort { hold_back, minimum } => write!(
+                f,
+                "HOLD-BACK of {}s is less than three target durations ({}s)",
+                hold_back, minimum
+            ),
+            ValidationError::PartHoldBackTooShort {
+                part_hold_back,
+                minimum,
+            } => write!(
+                f,
+                "PART-HOLD-BACK of {}s is less than two part target durations ({}s)",
+                part_hold_back, minimum
+            ),
+            ValidationError::MissingPartHoldBack => {
+                write!(f, "playlist has parts but no PART-HOLD-BACK")
+            }
+            ValidationError::MissingCanBlockReload => {
+                write!(
+                    f,
+                    "playlist has parts but does not declare CAN-BLOCK-RELOAD=YES"
+                )
+            }
+            ValidationError::UndefinedRenditionReport(uri) => write!(
+                f,
+                "rendition report {} does not refer to a sibling rendition",
+                uri
+            ),
+            ValidationError::MediaSequenceDecreased { previous, next } => write!(
+                f,
+                "EXT-X-MEDIA-SEQUENCE decreased from {} to {}",
+                previous, next
+            ),
+            ValidationError::SegmentChanged {
+                media_sequence,
+                previous,
+                next,
+            } => write!(
+                f,
+                "segment {} changed from {} to {}",
+                media_sequence, previous, next
+            ),
+            ValidationError::DiscontinuitySequenceMismatch { expected, found } => write!(
+                f,
+                "EXT-X-DISCONTINUITY-SEQUENCE is {} instead of {}",
+                found, expected
+            ),
+            ValidationError::EndListRemoved => {
+                write!(f, "EXT-X-ENDLIST was removed from an ended playlist")
+            }
+            ValidationError::UnquotedAttribute { tag, attribute } => {
+                write!(f, "{} attribute {} is not quoted", tag, attribute)
+            }
+            ValidationError::ImplicitByteRangeOffset(uri) => write!(
+                f,
+                "byte range of segment {} has no offset and does not follow a sub-range of the same resource",
+                uri
+            ),
+        }
+    }
+}
+
+impl Error for ValidationError {}
+
+/// The validation errors found by the checks, in order, each with the index of
+/// the tag it was found at, if it concerns a single tag.
+#[derive(Debug, Default)]
+pub(crate) struct Findings(Vec<(Option<usize>, ValidationError)>);
+
+impl Findings {
+    /// Records an error about the playlist as a whole.
+    pub(crate) fn push(&mut self, error: ValidationError) {
+        self.0.push((None, error));
+    }
+
+    /// Records an error about the tag at `tag_index`.
+    pub(crate) fn push_at(&mut self, tag_index: usize, error: ValidationError) {
+        self.0.push((Some(tag_index), error));
+    }
+
+    /// Records the errors of `other` after these.
+    pub(crate) fn append(&mut self, mut other: Findings) {
+        self.0.append(&mut other.0);
+    }
+
+    /// Keeps only the errors for which `keep` returns `true`.
+    pub(crate) fn retain(&mut self, mut keep: impl FnMut(&ValidationError) -> bool) {
+        self.0.retain(|(_, error)| keep(error));
+    }
+
+    /// Returns the errors without their tag indices.
+    pub(crate) fn into_errors(self) -> Vec<ValidationError> {
+        self.0.into_iter().map(|(_, error)| error).collect()
+    }
+}
+
+impl IntoIterator for Findings {
+    type Item = (Option<usize>, ValidationError);
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
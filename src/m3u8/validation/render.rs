@@ -0,0 +1,83 @@
+//! Human-readable rendering of validation reports.
+//!
+//! Diagnostics are printed like compiler diagnostics: a header with the
+//! severity, rule ID and message, followed by the offending playlist line with
+//! carets under it and an explanation of the rule.
+//!
+//! ```text
+//! error[segment-duration-exceeds-target]: segment first.ts lasts 5s, longer than the target duration of 4s
+//!  --> line 3
+//!   |
+//! 3 | #EXTINF:5,
+//!   | ^^^^^^^^^^
+//!   = note: each EXTINF duration, rounded to the nearest integer, must not exceed EXT-X-TARGETDURATION
+//! ```
+
+use crate::m3u8::validation::{Diagnostic, ValidationReport};
+
+impl Diagnostic {
+    /// Renders the diagnostic, quoting its line from `source`, the text the
+    /// playlist was read from.
+    pub fn render(&self, source: &str) -> String {
+        let mut output = format!("{}[{}]: {}\n", self.severity, self.rule, self.error);
+
+        let quoted = self
+            .line
+            .and_then(|line| Some((line, source.lines().nth(line.checked_sub(1)?)?)));
+        let gutter = match quoted {
+            Some((line, text)) => {
+                let width = line.to_string().len();
+                let text = text.trim_end();
+                let pad = " ".repeat(width);
+                output.push_str(&format!("{}--> line {}\n", pad, line));
+                output.push_str(&format!("{} |\n", pad));
+                output.push_str(&format!("{} | {}\n", line, text));
+                let indent = text.len() - text.trim_start().len();
+                output.push_str(&format!(
+                    "{} | {}{}\n",
+                    pad,
+                    " ".repeat(indent),
+                    "^".repeat(text.trim_start().chars().count().max(1))
+                ));
+                pad
+            }
+            None => String::new(),
+        };
+
+        output.push_str(&format!("{} = note: {}\n", gutter, self.rule.description()));
+        output
+    }
+}
+
+impl ValidationReport {
+    /// Renders every diagnostic, separated by blank lines, quoting the
+    /// offending lines of `source` for the diagnostics located with
+    /// `ValidationReport::locate`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use m3u8_parser::m3u8::playlist::Playlist;
+    /// use m3u8_parser::m3u8::validation::Validator;
+    ///
+    /// let source = "#EXTM3U\n#EXT-X-TARGETDURATION:4\n#EXTINF:5,\nfirst.ts\n";
+    /// let (playlist, source_map) = Playlist::from_reader_with_source(source.as_bytes()).unwrap();
+    /// let report = Validator::default().validate(&playlist).locate(&source_map);
+    ///
+    /// assert_eq!(
+    ///     report.render(source),
+    ///     "error[segment-duration-exceeds-target]: segment first.ts lasts 5s, \
+    ///      longer than the target duration of 4s\n \
+    ///      --> line 3\n  |\n3 | #EXTINF:5,\n  | ^^^^^^^^^^\n  \
+    ///      = note: each EXTINF duration, rounded to the nearest integer, \
+    ///      must not exceed EXT-X-TARGETDURATION\n"
+    /// );
+    /// ```
+    pub fn render(&self, source: &str) -> String {
+        self.diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.render(source))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
@@ -0,0 +1,134 @@
+//! Reload consistency of live media playlists (RFC 8216 section 6.2.1).
+//!
+//! A server may only change a live media playlist by appending segments,
+//! removing segments from its start (incrementing `EXT-X-MEDIA-SEQUENCE` and,
+//! for removed discontinuities, `EXT-X-DISCONTINUITY-SEQUENCE`) and finally
+//! adding `EXT-X-ENDLIST`. These rules compare two successive snapshots of the
+//! same playlist.
+
+use crate::m3u8::playlist::Playlist;
+use crate::m3u8::tags::Tag;
+use crate::m3u8::validation::{Findings, ValidationError};
+
+/// Compares `next` with the `previous` snapshot of the same playlist.
+pub(crate) fn validate_update(previous: &Playlist, next: &Playlist, errors: &mut Findings) {
+    let has_end_list = |playlist: &Playlist| {
+        playlist
+            .tags
+            .iter()
+            .any(|tag| matches!(tag, Tag::ExtXEndList))
+    };
+    if has_end_list(previous) && !has_end_list(next) {
+        errors.push(ValidationError::EndListRemoved);
+    }
+
+    let previous_sequence = previous.media_sequence();
+    let next_sequence = next.media_sequence();
+    let media_sequence_index = next
+        .tags
+        .iter()
+        .position(|tag| matches!(tag, Tag::ExtXMediaSequence(_)));
+    if next_sequence < previous_sequence {
+        let error = ValidationError::MediaSequenceDecreased {
+            previous: previous_sequence,
+            next: next_sequence,
+        };
+        match media_sequence_index {
+            Some(index) => errors.push_at(index, error),
+            None => errors.push(error),
+        }
+        // Segments cannot be matched up when the sequence goes backwards.
+        return;
+    }
+
+    let previous_segments = previous.segments();
+    let next_segments = next.segments();
+    let removed = (next_sequence - previous_sequence) as usize;
+
+    for (offset, (before, after)) in previous_segments
+        .iter()
+        .skip(removed)
+        .zip(&next_segments)
+        .enumerate()
+    {
+        let sequence = next_sequence + offset as u64;
+        if before.uri != after.uri || before.duration != after.duration {
+            errors.push_at(
+                after.tag_index,
+                ValidationError::SegmentChanged {
+                    media_sequence: sequence,
+                    previous: format!("{} ({}s)", before.uri, before.duration),
+                    next: format!("{} ({}s)", after.uri, after.duration),
+                },
+            );
+        }
+    }
+
+    // The discontinuity sequence can only be checked when every removed
+    // segment is known from the previous snapshot.
+    if removed <= previous_segments.len() {
+        let removed_discontinuities = previous_segments[..removed]
+            .iter()
+            .filter(|segment| segment.discontinuity)
+            .count() as u64;
+        let expected = previous.discontinuity_sequence() + removed_discontinuities;
+        let found = next.discontinuity_sequence();
+        if found != expected {
+            let error = ValidationError::DiscontinuitySequenceMismatch { expected, found };
+            match next
+                .tags
+                .iter()
+                .position(|tag| matches!(tag, Tag::ExtXDiscontinuitySequence(_)))
+            {
+                Some(index) => errors.push_at(index, error),
+                None => errors.push(error),
+            }
+        }
+    }
+}
+
+impl Playlist {
+    /// Checks that `next` is a valid reload of the live media playlist
+    /// `previous`.
+    ///
+    /// `EXT-X-MEDIA-SEQUENCE` must not decrease, segments present in both
+    /// snapshots must keep their URI and duration, `EXT-X-DISCONTINUITY-SEQUENCE`
+    /// must grow by the number of discontinuities removed from the start of
+    /// the playlist, and `EXT-X-ENDLIST` must not disappear.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use m3u8_parser::m3u8::playlist::Playlist;
+    /// use m3u8_parser::m3u8::validation::ValidationError;
+    ///
+    /// let previous = Playlist::from_reader(
+    ///     "#EXTM3U\n#EXT-X-MEDIA-SEQUENCE:10\n#EXTINF:4,\na.ts\n#EXTINF:4,\nb.ts\n".as_bytes(),
+    /// )
+    /// .unwrap();
+    /// let next = Playlist::from_reader(
+    ///     "#EXTM3U\n#EXT-X-MEDIA-SEQUENCE:11\n#EXTINF:4,\nb.ts\n#EXTINF:4,\nc.ts\n".as_bytes(),
+    /// )
+    /// .unwrap();
+    /// assert_eq!(Playlist::validate_update(&previous, &next), Ok(()));
+    ///
+    /// let rewound = Playlist::from_reader("#EXTM3U\n#EXT-X-MEDIA-SEQUENCE:9\n".as_bytes()).unwrap();
+    /// assert_eq!(
+    ///     Playlist::validate_update(&next, &rewound),
+    ///     Err(vec![ValidationError::MediaSequenceDecreased { previous: 11, next: 9 }])
+    /// );
+    /// ```
+    pub fn validate_update(
+        previous: &Playlist,
+        next: &Playlist,
+    ) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Findings::default();
+        validate_update(previous, next, &mut errors);
+        let errors = errors.into_errors();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
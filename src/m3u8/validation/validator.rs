@@ -0,0 +1,640 @@
+//! Configurable validation with rule IDs, severities and profiles.
+//!
+//! `Playlist::validate` treats every problem as fatal. A `Validator` runs the
+//! same checks but classifies each finding by the `Rule` it violates and assigns
+//! it a `Severity` according to a `Profile`, so that callers can, for example,
+//! fail on errors while only logging warnings. The severity of individual rules
+//! can be overridden, and rules can be disabled.
+//!
+//! # Example
+//!
+//! ```
+//! use m3u8_parser::m3u8::playlist::Playlist;
+//! use m3u8_parser::m3u8::validation::{Profile, Rule, Severity, Validator};
+//!
+//! let playlist = Playlist::from_reader(
+//!     "#EXTM3U\n#EXT-X-TARGETDURATION:10\n#EXT-X-GAP\n#EXTINF:10,\nfirst.ts\n".as_bytes(),
+//! )
+//! .unwrap();
+//!
+//! let report = Validator::new(Profile::Rfc8216).validate(&playlist);
+//! assert!(!report.has_errors());
+//! assert_eq!(report.diagnostics[0].rule, Rule::UnsupportedTag);
+//! assert_eq!(report.diagnostics[0].severity, Severity::Warning);
+//!
+//! let report = Validator::new(Profile::Rfc8216)
+//!     .rule_severity(Rule::UnsupportedTag, Severity::Error)
+//!     .validate(&playlist);
+//! assert!(report.has_errors());
+//! ```
+
+use crate::m3u8::playlist::{Playlist, SourceMap};
+use crate::m3u8::tags::Tag;
+use crate::m3u8::validation::{authoring, update, Findings, ValidationError};
+use std::collections::HashMap;
+use std::fmt;
+
+/// How serious a validation finding is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// A remark that does not affect conformance.
+    Info,
+    /// A deviation from a recommendation, or a problem most clients tolerate.
+    Warning,
+    /// A violation of a requirement of the profile.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+/// The specification a `Validator` checks against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Profile {
+    /// RFC 8216. Tags introduced by later revisions are reported as
+    /// unsupported and protocol versions above 7 are errors.
+    Rfc8216,
+    /// The RFC 8216bis draft, which adds low-latency HLS, `EXT-X-GAP`,
+    /// `EXT-X-DEFINE` and protocol versions up to 12.
+    #[default]
+    Rfc8216bis,
+    /// RFC 8216bis, reporting the recommendations of the Apple HLS Authoring
+    /// Specification as warnings.
+    AppleHlsAuthoring,
+    /// RFC 8216bis, reporting only problems that prevent playback as errors.
+    Lenient,
+}
+
+impl Profile {
+    /// Returns the highest protocol version defined by the profile.
+    pub fn max_version(&self) -> u8 {
+        match self {
+            Profile::Rfc8216 => 7,
+            Profile::Rfc8216bis | Profile::AppleHlsAuthoring | Profile::Lenient => 12,
+        }
+    }
+
+    /// Returns the default severity of `rule`, or `None` if the profile does
+    /// not check it.
+    pub fn severity(&self, rule: Rule) -> Option<Severity> {
+        match (self, rule) {
+            (Profile::Rfc8216, Rule::UnsupportedTag) => Some(Severity::Warning),
+            (_, Rule::UnsupportedTag) => None,
+            (Profile::AppleHlsAuthoring, rule) if rule.is_authoring() => Some(Severity::Warning),
+            (_, rule) if rule.is_authoring() => None,
+            (Profile::Rfc8216, rule) if rule.is_low_latency() => None,
+            (
+                Profile::Lenient,
+                Rule::MissingExtM3U
+                | Rule::InvalidDuration
+                | Rule::InvalidTargetDuration
+                | Rule::MissingTargetDuration
+                | Rule::InvalidKeyMethod
+                | Rule::InvalidMapUri
+                | Rule::InvalidByteRange,
+            ) => Some(Severity::Error),
+            (Profile::Lenient, Rule::VersionTooLow) => Some(Severity::Info),
+            (Profile::Lenient, _) => Some(Severity::Warning),
+            _ => Some(Severity::Error),
+        }
+    }
+}
+
+/// A validation rule, identified by a stable ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rule {
+    /// The playlist starts with `#EXTM3U`.
+    MissingExtM3U,
+    /// `EXT-X-VERSION` is a version defined by the profile.
+    InvalidVersion,
+    /// `EXTINF` durations are positive.
+    InvalidDuration,
+    /// `EXT-X-TARGETDURATION` is positive.
+    InvalidTargetDuration,
+    /// `EXT-X-KEY` uses a known `METHOD`.
+    InvalidKeyMethod,
+    /// `EXT-X-MAP` has a non-empty `URI`.
+    InvalidMapUri,
+    /// `EXT-X-PROGRAM-DATE-TIME` is an ISO 8601 date-time.
+    InvalidProgramDateTime,
+    /// `EXT-X-DATERANGE` attributes are valid.
+    InvalidDateRange,
+    /// Byte ranges have a non-zero length.
+    InvalidByteRange,
+    /// `EXT-X-MEDIA` has its required attributes.
+    MissingMediaFields,
+    /// `EXT-X-STREAM-INF` attributes are valid.
+    InvalidStreamInf,
+    /// `EXT-X-I-FRAME-STREAM-INF` attributes are valid.
+    InvalidIFrameStreamInf,
+    /// `EXT-X-PART` and `EXT-X-PART-INF` attributes are valid.
+    InvalidPartInfo,
+    /// `EXT-X-PRELOAD-HINT` has a non-empty `URI`.
+    InvalidPreloadHint,
+    /// `EXT-X-RENDITION-REPORT` has a non-empty `URI`.
+    InvalidRenditionReport,
+    /// `EXT-X-SERVER-CONTROL` attributes are valid.
+    InvalidServerControl,
+    /// `EXT-X-START` has a `TIME-OFFSET`.
+    InvalidStartOffset,
+    /// `EXT-X-SKIP` attributes are valid.
+    InvalidSkipTag,
+    /// `EXT-X-BITRATE` is valid.
+    InvalidBitrate,
+    /// Rounded segment durations do not exceed the target duration.
+    SegmentDurationExceedsTarget,
+    /// Media playlists have an `EXT-X-TARGETDURATION` tag.
+    MissingTargetDuration,
+    /// Tags allowed once appear at most once.
+    DuplicateTag,
+    /// Playlist header tags precede the first segment.
+    HeaderTagAfterSegment,
+    /// `EXT-X-ENDLIST` is the last tag.
+    EndListNotLast,
+    /// Master and media playlist tags are not mixed.
+    MixedPlaylistTags,
+    /// Variant streams reference existing rendition groups.
+    UndefinedMediaGroup,
+    /// At most one rendition per group has `DEFAULT=YES`.
+    MultipleDefaultRenditions,
+    /// Rendition names are unique within a group.
+    DuplicateRenditionName,
+    /// `CLOSED-CAPTIONS` renditions have an `INSTREAM-ID` and no `URI`.
+    ClosedCaptionsAttributes,
+    /// `EXT-X-VERSION` supports every feature the playlist uses.
+    VersionTooLow,
+    /// The playlist only uses tags defined by the profile.
+    UnsupportedTag,
+    /// `EXT-X-TARGETDURATION` is 6 seconds.
+    AuthoringTargetDuration,
+    /// Master playlists declare `EXT-X-INDEPENDENT-SEGMENTS`.
+    AuthoringIndependentSegments,
+    /// Variant streams have an `AVERAGE-BANDWIDTH`.
+    AuthoringAverageBandwidth,
+    /// Variant streams have `CODECS`.
+    AuthoringCodecs,
+    /// Video variant streams have a `FRAME-RATE`.
+    AuthoringFrameRate,
+    /// Master playlists with video have I-frame variants.
+    AuthoringIFrameVariants,
+    /// Adjacent bitrates of the variant ladder are a factor of 1.5 to 2 apart.
+    AuthoringBitrateLadder,
+    /// Peak bitrates are at most twice the average bitrates.
+    AuthoringPeakToAverage,
+    /// The parts of a segment add up to its duration.
+    PartDurationSum,
+    /// Parts are at most as long as the part target duration.
+    PartTargetExceeded,
+    /// Playlists with parts have an `EXT-X-PART-INF` tag.
+    MissingPartInf,
+    /// `EXT-X-PRELOAD-HINT` follows every segment and part.
+    PreloadHintNotLast,
+    /// `HOLD-BACK` and `PART-HOLD-BACK` leave enough room at the live edge.
+    HoldBack,
+    /// Playlists with parts support blocking playlist reload.
+    MissingCanBlockReload,
+    /// Rendition reports refer to sibling renditions.
+    UndefinedRenditionReport,
+    /// `EXT-X-MEDIA-SEQUENCE` does not decrease between reloads.
+    MediaSequenceDecreased,
+    /// Segments keep their URI and duration between reloads.
+    SegmentChanged,
+    /// `EXT-X-DISCONTINUITY-SEQUENCE` counts the removed discontinuities.
+    DiscontinuitySequence,
+    /// `EXT-X-ENDLIST` is not removed between reloads.
+    EndListRemoved,
+    /// Quoted-string attribute values are enclosed in double quotes.
+    UnquotedAttribute,
+    /// Byte ranges without offset follow a sub-range of the same resource.
+    ImplicitByteRangeOffset,
+}
+
+impl Rule {
+    /// Returns the stable ID of the rule, e.g. `"segment-duration-exceeds-target"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Rule::MissingExtM3U => "missing-extm3u",
+            Rule::InvalidVersion => "invalid-version",
+            Rule::InvalidDuration => "invalid-duration",
+            Rule::InvalidTargetDuration => "invalid-target-duration",
+            Rule::InvalidKeyMethod => "invalid-key-method",
+            Rule::InvalidMapUri => "invalid-map-uri",
+            Rule::InvalidProgramDateTime => "invalid-program-date-time",
+            Rule::InvalidDateRange => "invalid-date-range",
+            Rule::InvalidByteRange => "invalid-byte-range",
+            Rule::MissingMediaFields => "missing-media-fields",
+            Rule::InvalidStreamInf => "invalid-stream-inf",
+            Rule::InvalidIFrameStreamInf => "invalid-i-frame-stream-inf",
+            Rule::InvalidPartInfo => "invalid-part-info",
+            Rule::InvalidPreloadHint => "invalid-preload-hint",
+            Rule::InvalidRenditionReport => "invalid-rendition-report",
+            Rule::InvalidServerControl => "invalid-server-control",
+            Rule::InvalidStartOffset => "invalid-start-offset",
+            Rule::InvalidSkipTag => "invalid-skip-tag",
+            Rule::InvalidBitrate => "invalid-bitrate",
+            Rule::SegmentDurationExceedsTarget => "segment-duration-exceeds-target",
+            Rule::MissingTargetDuration => "missing-target-duration",
+            Rule::DuplicateTag => "duplicate-tag",
+            Rule::HeaderTagAfterSegment => "header-tag-after-segment",
+            Rule::EndListNotLast => "endlist-not-last",
+            Rule::MixedPlaylistTags => "mixed-playlist-tags",
+            Rule::UndefinedMediaGroup => "undefined-media-group",
+            Rule::MultipleDefaultRenditions => "multiple-default-renditions",
+            Rule::DuplicateRenditionName => "duplicate-rendition-name",
+            Rule::ClosedCaptionsAttributes => "closed-captions-attributes",
+            Rule::VersionTooLow => "version-too-low",
+            Rule::UnsupportedTag => "unsupported-tag",
+            Rule::AuthoringTargetDuration => "authoring-target-duration",
+            Rule::AuthoringIndependentSegments => "authoring-independent-segments",
+            Rule::AuthoringAverageBandwidth => "authoring-average-bandwidth",
+            Rule::AuthoringCodecs => "authoring-codecs",
+            Rule::AuthoringFrameRate => "authoring-frame-rate",
+            Rule::AuthoringIFrameVariants => "authoring-i-frame-variants",
+            Rule::AuthoringBitrateLadder => "authoring-bitrate-ladder",
+            Rule::AuthoringPeakToAverage => "authoring-peak-to-average",
+            Rule::PartDurationSum => "part-duration-sum",
+            Rule::PartTargetExceeded => "part-target-exceeded",
+            Rule::MissingPartInf => "missing-part-inf",
+            Rule::PreloadHintNotLast => "preload-hint-not-last",
+            Rule::HoldBack => "hold-back",
+            Rule::MissingCanBlockReload => "missing-can-block-reload",
+            Rule::UndefinedRenditionReport => "undefined-rendition-report",
+            Rule::MediaSequenceDecreased => "media-sequence-decreased",
+            Rule::SegmentChanged => "segment-changed",
+            Rule::DiscontinuitySequence => "discontinuity-sequence",
+            Rule::EndListRemoved => "endlist-removed",
+            Rule::UnquotedAttribute => "unquoted-attribute",
+            Rule::ImplicitByteRangeOffset => "implicit-byte-range-offset",
+        }
+    }
+
+    /// Returns a short explanation of what the rule requires.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Rule::MissingExtM3U => "every playlist must start with the EXTM3U tag",
+            Rule::InvalidVersion => "EXT-X-VERSION must be a protocol version defined by the profile",
+            Rule::InvalidDuration => "EXTINF durations must be positive",
+            Rule::InvalidTargetDuration => "EXT-X-TARGETDURATION must be positive",
+            Rule::InvalidKeyMethod => "EXT-X-KEY METHOD must be NONE, AES-128 or SAMPLE-AES",
+            Rule::InvalidMapUri => "EXT-X-MAP must have a URI",
+            Rule::InvalidProgramDateTime => "EXT-X-PROGRAM-DATE-TIME must be an ISO 8601 date-time with a time zone",
+            Rule::InvalidDateRange => "EXT-X-DATERANGE attributes must be well-formed",
+            Rule::InvalidByteRange => "byte ranges must have a non-zero length",
+            Rule::MissingMediaFields => "EXT-X-MEDIA must have TYPE, GROUP-ID and NAME attributes",
+            Rule::InvalidStreamInf => "EXT-X-STREAM-INF attributes must be well-formed",
+            Rule::InvalidIFrameStreamInf => "EXT-X-I-FRAME-STREAM-INF attributes must be well-formed",
+            Rule::InvalidPartInfo => "EXT-X-PART and EXT-X-PART-INF attributes must be well-formed",
+            Rule::InvalidPreloadHint => "EXT-X-PRELOAD-HINT must have a URI",
+            Rule::InvalidRenditionReport => "EXT-X-RENDITION-REPORT must have a URI",
+            Rule::InvalidServerControl => "EXT-X-SERVER-CONTROL attributes must be well-formed",
+            Rule::InvalidStartOffset => "EXT-X-START must have a TIME-OFFSET",
+            Rule::InvalidSkipTag => "EXT-X-SKIP attributes must be well-formed",
+            Rule::InvalidBitrate => "EXT-X-BITRATE must be a valid bitrate",
+            Rule::SegmentDurationExceedsTarget => "each EXTINF duration, rounded to the nearest integer, must not exceed EXT-X-TARGETDURATION",
+            Rule::MissingTargetDuration => "media playlists must have an EXT-X-TARGETDURATION tag",
+            Rule::DuplicateTag => "this tag may appear at most once per playlist",
+            Rule::HeaderTagAfterSegment => "playlist tags must appear before the first media segment",
+            Rule::EndListNotLast => "EXT-X-ENDLIST must be the last tag of the playlist",
+            Rule::MixedPlaylistTags => "a playlist must be either a master playlist or a media playlist",
+            Rule::UndefinedMediaGroup => "group references must match the GROUP-ID of an EXT-X-MEDIA tag of the same TYPE",
+            Rule::MultipleDefaultRenditions => "at most one rendition per group may have DEFAULT=YES",
+            Rule::DuplicateRenditionName => "renditions of a group must have distinct NAMEs",
+            Rule::ClosedCaptionsAttributes => "CLOSED-CAPTIONS renditions must have an INSTREAM-ID and no URI",
+            Rule::VersionTooLow => "EXT-X-VERSION must be at least the version of every feature the playlist uses",
+            Rule::UnsupportedTag => "the profile does not define this tag",
+            Rule::AuthoringTargetDuration => "the target duration should be 6 seconds",
+            Rule::AuthoringIndependentSegments => "master playlists should declare EXT-X-INDEPENDENT-SEGMENTS so that variants can be switched at segment boundaries",
+            Rule::AuthoringAverageBandwidth => "every variant should have an AVERAGE-BANDWIDTH",
+            Rule::AuthoringCodecs => "every variant should have CODECS",
+            Rule::AuthoringFrameRate => "every video variant should have a FRAME-RATE",
+            Rule::AuthoringIFrameVariants => "master playlists with video should offer I-frame variants for trick play",
+            Rule::AuthoringBitrateLadder => "adjacent bitrates of the variant ladder should be a factor of 1.5 to 2 apart",
+            Rule::AuthoringPeakToAverage => "the peak bitrate of a variant should be at most twice its average bitrate",
+            Rule::PartDurationSum => "the durations of the parts of a segment must add up to its EXTINF duration",
+            Rule::PartTargetExceeded => "part durations must not exceed the PART-TARGET of EXT-X-PART-INF",
+            Rule::MissingPartInf => "playlists with EXT-X-PART tags must have an EXT-X-PART-INF tag",
+            Rule::PreloadHintNotLast => "EXT-X-PRELOAD-HINT must follow the last segment and part",
+            Rule::HoldBack => "HOLD-BACK must be at least three target durations and PART-HOLD-BACK, which is required with parts, at least two part target durations",
+            Rule::MissingCanBlockReload => "playlists with parts must declare CAN-BLOCK-RELOAD=YES in EXT-X-SERVER-CONTROL",
+            Rule::UndefinedRenditionReport => "rendition reports must refer to sibling renditions of the master playlist",
+            Rule::MediaSequenceDecreased => "EXT-X-MEDIA-SEQUENCE must never decrease between reloads of a live playlist",
+            Rule::SegmentChanged => "a segment must keep its URI and duration as long as it is in the playlist",
+            Rule::DiscontinuitySequence => "EXT-X-DISCONTINUITY-SEQUENCE must grow by the number of discontinuities removed from the playlist",
+            Rule::EndListRemoved => "once added, EXT-X-ENDLIST must remain in the playlist",
+            Rule::UnquotedAttribute => "quoted-string attribute values must be enclosed in double quotes",
+            Rule::ImplicitByteRangeOffset => "an EXT-X-BYTERANGE without offset must follow a sub-range of the same resource",
+        }
+    }
+
+    /// Returns `true` for the low-latency HLS rules of RFC 8216bis.
+    pub fn is_low_latency(&self) -> bool {
+        matches!(
+            self,
+            Rule::PartDurationSum
+                | Rule::PartTargetExceeded
+                | Rule::MissingPartInf
+                | Rule::PreloadHintNotLast
+                | Rule::HoldBack
+                | Rule::MissingCanBlockReload
+                | Rule::UndefinedRenditionReport
+        )
+    }
+
+    /// Returns `true` for the rules of the Apple HLS Authoring Specification.
+    pub fn is_authoring(&self) -> bool {
+        matches!(
+            self,
+            Rule::AuthoringTargetDuration
+                | Rule::AuthoringIndependentSegments
+                | Rule::AuthoringAverageBandwidth
+                | Rule::AuthoringCodecs
+                | Rule::AuthoringFrameRate
+                | Rule::AuthoringIFrameVariants
+                | Rule::AuthoringBitrateLadder
+                | Rule::AuthoringPeakToAverage
+        )
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl ValidationError {
+    /// Returns the rule violated by the error.
+    pub fn rule(&self) -> Rule {
+        match self {
+            ValidationError::MissingExtM3U => Rule::MissingExtM3U,
+            ValidationError::InvalidVersion(_) => Rule::InvalidVersion,
+            ValidationError::InvalidDuration(_) => Rule::InvalidDuration,
+            ValidationError::InvalidTargetDuration(_) => Rule::InvalidTargetDuration,
+            ValidationError::InvalidKeyMethod(_) => Rule::InvalidKeyMethod,
+            ValidationError::InvalidMapUri => Rule::InvalidMapUri,
+            ValidationError::InvalidProgramDateTime => Rule::InvalidProgramDateTime,
+            ValidationError::InvalidDateRangeId
+            | ValidationError::InvalidDateRangeStartDate
+            | ValidationError::InvalidDateRangeEndDate
+            | ValidationError::InvalidDateRangePlannedDuration(_) => Rule::InvalidDateRange,
+            ValidationError::InvalidByteRange(_) => Rule::InvalidByteRange,
+            ValidationError::MissingMediaFields => Rule::MissingMediaFields,
+            ValidationError::InvalidStreamInf(_) => Rule::InvalidStreamInf,
+            ValidationError::InvalidIFrameStreamInf(_) => Rule::InvalidIFrameStreamInf,
+            ValidationError::InvalidPartInfo(_) => Rule::InvalidPartInfo,
+            ValidationError::InvalidPreloadHintUri => Rule::InvalidPreloadHint,
+            ValidationError::InvalidRenditionReportUri => Rule::InvalidRenditionReport,
+            ValidationError::InvalidServerControl => Rule::InvalidServerControl,
+            ValidationError::InvalidStartTimeOffset | ValidationError::InvalidStartOffset => {
+                Rule::InvalidStartOffset
+            }
+            ValidationError::InvalidSkipTag(_) => Rule::InvalidSkipTag,
+            ValidationError::InvalidBitrate(_) => Rule::InvalidBitrate,
+            ValidationError::SegmentDurationExceedsTarget { .. } => {
+                Rule::SegmentDurationExceedsTarget
+            }
+            ValidationError::MissingTargetDuration => Rule::MissingTargetDuration,
+            ValidationError::DuplicateTag(_) => Rule::DuplicateTag,
+            ValidationError::HeaderTagAfterSegment(_) => Rule::HeaderTagAfterSegment,
+            ValidationError::EndListNotLast => Rule::EndListNotLast,
+            ValidationError::MasterTagInMediaPlaylist(_)
+            | ValidationError::MediaTagInMasterPlaylist(_) => Rule::MixedPlaylistTags,
+            ValidationError::UndefinedMediaGroup { .. } => Rule::UndefinedMediaGroup,
+            ValidationError::MultipleDefaultRenditions(_) => Rule::MultipleDefaultRenditions,
+            ValidationError::DuplicateRenditionName { .. } => Rule::DuplicateRenditionName,
+            ValidationError::MissingInstreamId(_) | ValidationError::ClosedCaptionsWithUri(_) => {
+                Rule::ClosedCaptionsAttributes
+            }
+            ValidationError::VersionTooLow { .. } => Rule::VersionTooLow,
+            ValidationError::UnsupportedTag(_) => Rule::UnsupportedTag,
+            ValidationError::TargetDurationNotRecommended(_) => Rule::AuthoringTargetDuration,
+            ValidationError::MissingIndependentSegments => Rule::AuthoringIndependentSegments,
+            ValidationError::MissingAverageBandwidth(_) => Rule::AuthoringAverageBandwidth,
+            ValidationError::MissingCodecs(_) => Rule::AuthoringCodecs,
+            ValidationError::MissingFrameRate(_) => Rule::AuthoringFrameRate,
+            ValidationError::MissingIFrameVariants => Rule::AuthoringIFrameVariants,
+            ValidationError::BitrateLadderGap { .. }
+            | ValidationError::BitrateLadderStep { .. } => Rule::AuthoringBitrateLadder,
+            ValidationError::PeakToAverageRatio { .. } => Rule::AuthoringPeakToAverage,
+            ValidationError::PartDurationMismatch { .. } => Rule::PartDurationSum,
+            ValidationError::PartExceedsPartTarget { .. } => Rule::PartTargetExceeded,
+            ValidationError::MissingPartInf => Rule::MissingPartInf,
+            ValidationError::PreloadHintNotLast => Rule::PreloadHintNotLast,
+            ValidationError::HoldBackTooShort { .. }
+            | ValidationError::PartHoldBackTooShort { .. }
+            | ValidationError::MissingPartHoldBack => Rule::HoldBack,
+            ValidationError::MissingCanBlockReload => Rule::MissingCanBlockReload,
+            ValidationError::UndefinedRenditionReport(_) => Rule::UndefinedRenditionReport,
+            ValidationError::MediaSequenceDecreased { .. } => Rule::MediaSequenceDecreased,
+            ValidationError::SegmentChanged { .. } => Rule::SegmentChanged,
+            ValidationError::DiscontinuitySequenceMismatch { .. } => Rule::DiscontinuitySequence,
+            ValidationError::EndListRemoved => Rule::EndListRemoved,
+            ValidationError::UnquotedAttribute { .. } => Rule::UnquotedAttribute,
+            ValidationError::ImplicitByteRangeOffset(_) => Rule::ImplicitByteRangeOffset,
+        }
+    }
+}
+
+/// A single validation finding.
+#[derive(Debug, PartialEq)]
+pub struct Diagnostic {
+    /// The violated rule.
+    pub rule: Rule,
+    /// The severity assigned to the rule by the validator.
+    pub severity: Severity,
+    /// The underlying error.
+    pub error: ValidationError,
+    /// Index of the offending tag in `Playlist::tags`, or `None` if the
+    /// finding concerns the playlist as a whole.
+    pub tag_index: Option<usize>,
+    /// The 1-based line of the offending tag in the source the playlist was
+    /// read from, once the report is located with `ValidationReport::locate`.
+    pub line: Option<usize>,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[{}]: {}", self.severity, self.rule, self.error)?;
+        if let Some(line) = self.line {
+            write!(f, " (line {})", line)?;
+        }
+        Ok(())
+    }
+}
+
+/// The findings of a `Validator`, in the order the checks produced them.
+#[derive(Debug, Default, PartialEq)]
+pub struct ValidationReport {
+    /// Every finding whose rule is enabled.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl ValidationReport {
+    /// Fills in the source line of every finding that concerns a tag, from
+    /// the `SourceMap` returned by `Playlist::from_reader_with_source`.
+    pub fn locate(mut self, source: &SourceMap) -> Self {
+        for diagnostic in &mut self.diagnostics {
+            diagnostic.line = diagnostic
+                .tag_index
+                .and_then(|index| source.line_number(index));
+        }
+        self
+    }
+
+    /// Returns `true` if any finding has `Severity::Error`.
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.severity == Severity::Error)
+    }
+
+    /// Returns the findings with the given severity.
+    pub fn with_severity(&self, severity: Severity) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(move |diagnostic| diagnostic.severity == severity)
+    }
+
+    /// Returns the errors as the result `Playlist::validate` would return,
+    /// dropping warnings and informational findings.
+    pub fn into_result(self) -> Result<(), Vec<ValidationError>> {
+        let errors: Vec<ValidationError> = self
+            .diagnostics
+            .into_iter()
+            .filter(|diagnostic| diagnostic.severity == Severity::Error)
+            .map(|diagnostic| diagnostic.error)
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Validates playlists against a `Profile`, with per-rule overrides.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Validator {
+    profile: Profile,
+    overrides: HashMap<Rule, Option<Severity>>,
+}
+
+impl Validator {
+    /// Creates a `Validator` for the given profile.
+    pub fn new(profile: Profile) -> Self {
+        Self {
+            profile,
+            overrides: HashMap::new(),
+        }
+    }
+
+    /// Returns the profile of the validator.
+    pub fn profile(&self) -> Profile {
+        self.profile
+    }
+
+    /// Reports findings of `rule` with `severity`, regardless of the profile.
+    pub fn rule_severity(mut self, rule: Rule, severity: Severity) -> Self {
+        self.overrides.insert(rule, Some(severity));
+        self
+    }
+
+    /// Stops reporting findings of `rule`.
+    pub fn disable(mut self, rule: Rule) -> Self {
+        self.overrides.insert(rule, None);
+        self
+    }
+
+    /// Returns the severity findings of `rule` are reported with, or `None`
+    /// if the rule is disabled.
+    pub fn severity(&self, rule: Rule) -> Option<Severity> {
+        match self.overrides.get(&rule) {
+            Some(severity) => *severity,
+            None => self.profile.severity(rule),
+        }
+    }
+
+    /// Validates the playlist and returns every finding of an enabled rule.
+    pub fn validate(&self, playlist: &Playlist) -> ValidationReport {
+        // The versions RFC 8216bis defines are checked against the profile
+        // here, in tag order; the playlist reports the others.
+        let defined = 1..=Profile::Rfc8216bis.max_version();
+        let undefined = self.profile.max_version() + 1..=*defined.end();
+        let mut errors = Findings::default();
+        for (index, tag) in playlist.tags.iter().enumerate() {
+            if let Tag::ExtXVersion(version) = tag {
+                if undefined.contains(version) {
+                    errors.push_at(index, ValidationError::InvalidVersion(*version));
+                }
+            }
+        }
+        let mut playlist_errors = playlist.validation_errors();
+        playlist_errors.retain(|error| match error {
+            ValidationError::InvalidVersion(version) => !defined.contains(version),
+            _ => true,
+        });
+        errors.append(playlist_errors);
+        if self.profile == Profile::Rfc8216 {
+            validate_rfc8216_tags(playlist, &mut errors);
+        }
+        authoring::validate_authoring(playlist, &mut errors);
+        self.report(errors)
+    }
+
+    /// Checks that `next` is a valid reload of the live media playlist
+    /// `previous`, as `Playlist::validate_update` does. Locations refer to
+    /// `next`.
+    pub fn validate_update(&self, previous: &Playlist, next: &Playlist) -> ValidationReport {
+        let mut errors = Findings::default();
+        update::validate_update(previous, next, &mut errors);
+        self.report(errors)
+    }
+
+    /// Assigns severities to `errors`, dropping disabled rules.
+    fn report(&self, errors: Findings) -> ValidationReport {
+        let diagnostics = errors
+            .into_iter()
+            .filter_map(|(tag_index, error)| {
+                let rule = error.rule();
+                self.severity(rule).map(|severity| Diagnostic {
+                    rule,
+                    severity,
+                    error,
+                    tag_index,
+                    line: None,
+                })
+            })
+            .collect();
+        ValidationReport { diagnostics }
+    }
+}
+
+/// Reports the tags that RFC 8216 does not define.
+fn validate_rfc8216_tags(playlist: &Playlist, errors: &mut Findings) {
+    for (index, tag) in playlist.tags.iter().enumerate() {
+        if matches!(
+            tag,
+            Tag::ExtXGap
+                | Tag::ExtXBitrate(_)
+                | Tag::ExtXDefine(_)
+                | Tag::ExtXServerControl { .. }
+                | Tag::ExtXPartInf { .. }
+                | Tag::ExtXPart { .. }
+                | Tag::ExtXPreloadHint { .. }
+                | Tag::ExtXRenditionReport { .. }
+                | Tag::ExtXSkip { .. }
+        ) {
+            errors.push_at(
+                index,
+                ValidationError::UnsupportedTag(tag.name().to_string()),
+            );
+        }
+    }
+}
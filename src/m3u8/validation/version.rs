@@ -0,0 +1,129 @@
+//! Protocol version compatibility (RFC 8216 section 7 and RFC 8216bis).
+//!
+//! Each playlist feature introduced after the first protocol version requires
+//! the playlist to declare at least that version with `EXT-X-VERSION`. This
+//! module finds the features a playlist uses and the versions they require.
+
+use crate::m3u8::playlist::Playlist;
+use crate::m3u8::tags::Tag;
+use crate::m3u8::validation::{Findings, ValidationError};
+
+/// The highest protocol version defined by RFC 8216bis.
+pub(crate) const MAX_VERSION: u8 = 12;
+
+/// A playlist feature that requires a minimum protocol version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionRequirement {
+    /// The lowest protocol version supporting the feature.
+    pub version: u8,
+    /// A description of the feature.
+    pub feature: &'static str,
+    /// Index of the tag using the feature in `Playlist::tags`.
+    pub tag_index: usize,
+}
+
+/// Returns the version requirements of a single tag.
+fn tag_requirements(tag: &Tag) -> Vec<(u8, &'static str)> {
+    let mut requirements = Vec::new();
+    match tag {
+        Tag::ExtXKey {
+            iv,
+            keyformat,
+            keyformatversions,
+            ..
+        } => {
+            if iv.is_some() {
+                requirements.push((2, "IV attribute of EXT-X-KEY"));
+            }
+            if keyformat.is_some() || keyformatversions.is_some() {
+                requirements.push((5, "KEYFORMAT and KEYFORMATVERSIONS attributes of EXT-X-KEY"));
+            }
+        }
+        Tag::ExtInf(_, duration, _) if !duration.is_integer() => {
+            requirements.push((3, "floating-point EXTINF duration"));
+        }
+        Tag::ExtXByteRange(_) => requirements.push((4, "EXT-X-BYTERANGE")),
+        // Without EXT-X-I-FRAMES-ONLY, EXT-X-MAP requires version 6 rather than 5.
+        Tag::ExtXMap { .. } => requirements.push((6, "EXT-X-MAP")),
+        Tag::ExtXMedia {
+            instream_id: Some(instream_id),
+            ..
+        } if instream_id.starts_with("SERVICE") => {
+            requirements.push((7, "SERVICE value of the INSTREAM-ID attribute"));
+        }
+        Tag::ExtXDefine(_) => requirements.push((8, "EXT-X-DEFINE")),
+        Tag::ExtXSkip { .. } => requirements.push((9, "EXT-X-SKIP")),
+        _ => {}
+    }
+    requirements
+}
+
+impl Playlist {
+    /// Returns every feature used by the playlist that requires a protocol
+    /// version above 1, in tag order.
+    pub fn version_requirements(&self) -> Vec<VersionRequirement> {
+        self.tags
+            .iter()
+            .enumerate()
+            .flat_map(|(tag_index, tag)| {
+                tag_requirements(tag)
+                    .into_iter()
+                    .map(move |(version, feature)| VersionRequirement {
+                        version,
+                        feature,
+                        tag_index,
+                    })
+            })
+            .collect()
+    }
+
+    /// Returns the lowest protocol version that supports every feature used by
+    /// the playlist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use m3u8_parser::m3u8::playlist::Playlist;
+    ///
+    /// let playlist = Playlist::from_reader(
+    ///     "#EXTM3U\n#EXT-X-TARGETDURATION:10\n#EXTINF:9.5,\nfirst.ts\n".as_bytes(),
+    /// )
+    /// .unwrap();
+    /// assert_eq!(playlist.minimum_version(), 3);
+    /// ```
+    pub fn minimum_version(&self) -> u8 {
+        self.version_requirements()
+            .iter()
+            .map(|requirement| requirement.version)
+            .max()
+            .unwrap_or(1)
+    }
+
+    /// Returns the version declared by the first `EXT-X-VERSION` tag, if any.
+    pub fn declared_version(&self) -> Option<u8> {
+        self.tags.iter().find_map(|tag| match tag {
+            Tag::ExtXVersion(version) => Some(*version),
+            _ => None,
+        })
+    }
+}
+
+/// Reports the features that require a higher version than the declared one.
+pub(crate) fn validate_version(playlist: &Playlist, errors: &mut Findings) {
+    let declared = playlist.declared_version().unwrap_or(1);
+    let mut reported = Vec::new();
+
+    for requirement in playlist.version_requirements() {
+        if requirement.version > declared && !reported.contains(&requirement.feature) {
+            reported.push(requirement.feature);
+            errors.push_at(
+                requirement.tag_index,
+                ValidationError::VersionTooLow {
+                    declared,
+                    required: requirement.version,
+                    feature: requirement.feature.to_string(),
+                },
+            );
+        }
+    }
+}